@@ -3,8 +3,8 @@ use chrono::{TimeZone, Utc};
 use random::datetime::GenerateTime;
 
 fn main() {
-    let start = Utc.ymd(2001, 9, 9).and_hms_milli(0, 0, 0, 0);
-    let end = Utc.ymd(2001, 9, 10).and_hms_milli(0, 0, 0, 0);
+    let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
 
     let between = start.generate_until(&end).unwrap();
     println!("Time range between {} to {}", start, end);
@@ -1,4 +1,4 @@
-use random::tz::Tz;
+use random::tz::{Tz, Zone};
 
 fn main() {
     let tz = Tz::tz_by_iso_code("IN");
@@ -6,4 +6,12 @@ fn main() {
 
     let random_tz = Tz::get_random_tz().unwrap();
     println!("{:#?}", random_tz);
+
+    let zone = Zone::by_name("Asia/Calcutta").unwrap().unwrap();
+    println!(
+        "Asia/Calcutta (alias of {}): UTC{}, dst: {}",
+        zone.name(),
+        zone.utc_offset(),
+        zone.is_dst()
+    );
 }
@@ -0,0 +1,410 @@
+/*
+Username & account name generators
+Enterprise naming conventions (jsmith, john.smith, smithj01),
+down-level (DOMAIN\jsmith) & UPN (jsmith@corp.example.com) names
+and service accounts (svc_sql, sa-backup)
+*/
+
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+
+use crate::core::constants::SERVICE_ACCOUNT_APPLICATIONS;
+use crate::core::utils::{ascii_fold, strip_digits};
+use crate::names::{Gender, Locale, PersonName};
+
+/// Maximum length of a pre-windows 2000 logon name (sAMAccountName)
+pub const MAX_USERNAME_LENGTH: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsernameFormat {
+    /// jsmith
+    FirstInitialLast,
+    /// john.smith
+    FirstDotLast,
+    /// johnsmith
+    FirstLast,
+    /// smithj
+    LastFirstInitial,
+    /// smithj01
+    LastFirstInitialNumber,
+}
+
+impl UsernameFormat {
+    pub const ALL: &'static [UsernameFormat] = &[
+        UsernameFormat::FirstInitialLast,
+        UsernameFormat::FirstDotLast,
+        UsernameFormat::FirstLast,
+        UsernameFormat::LastFirstInitial,
+        UsernameFormat::LastFirstInitialNumber,
+    ];
+
+    pub fn random() -> UsernameFormat {
+        //! Gets a random username convention
+        *Self::ALL
+            .choose(&mut rand::thread_rng())
+            .unwrap_or(&UsernameFormat::FirstInitialLast)
+    }
+
+    fn base(&self, name: &PersonName) -> String {
+        let first = sanitize(&name.first_name);
+        let last = sanitize(&name.last_name);
+        let initial = first.chars().next().map(String::from).unwrap_or_default();
+        match self {
+            UsernameFormat::FirstInitialLast => format!("{}{}", initial, last),
+            UsernameFormat::FirstDotLast => format!("{}.{}", first, last),
+            UsernameFormat::FirstLast => format!("{}{}", first, last),
+            UsernameFormat::LastFirstInitial | UsernameFormat::LastFirstInitialNumber => {
+                format!("{}{}", last, initial)
+            }
+        }
+    }
+
+    fn numbered(&self) -> bool {
+        *self == UsernameFormat::LastFirstInitialNumber
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceAccountStyle {
+    /// svc_sql
+    SvcUnderscore,
+    /// svc-sql
+    SvcDash,
+    /// sa-backup
+    SaDash,
+    /// sql_svc
+    AppSuffix,
+    /// gmsa-sql$
+    Gmsa,
+}
+
+impl ServiceAccountStyle {
+    pub const ALL: &'static [ServiceAccountStyle] = &[
+        ServiceAccountStyle::SvcUnderscore,
+        ServiceAccountStyle::SvcDash,
+        ServiceAccountStyle::SaDash,
+        ServiceAccountStyle::AppSuffix,
+        ServiceAccountStyle::Gmsa,
+    ];
+
+    fn render(&self, application: &str) -> String {
+        match self {
+            ServiceAccountStyle::SvcUnderscore => format!("svc_{}", application),
+            ServiceAccountStyle::SvcDash => format!("svc-{}", application),
+            ServiceAccountStyle::SaDash => format!("sa-{}", application),
+            ServiceAccountStyle::AppSuffix => format!("{}_svc", application),
+            ServiceAccountStyle::Gmsa => format!("gmsa-{}$", application),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountName {
+    /// logon name, eg: jsmith
+    pub username: String,
+    /// eg: CORP\jsmith
+    pub down_level_logon_name: String,
+    /// eg: jsmith@corp.example.com
+    pub user_principal_name: String,
+}
+
+fn sanitize(name_part: &str) -> String {
+    // usernames are plain lower case ascii; digits are
+    // reserved for the de-duplication suffix
+    strip_digits(&ascii_fold(name_part))
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    text.chars().take(max_length).collect()
+}
+
+pub fn netbios_name(dns_domain: &str) -> String {
+    /*
+    Derive the NetBIOS domain name from a dns domain
+    :param dns_domain: eg: corp.example.com
+    :return: upper case NetBIOS name (max 15 chars), eg: CORP
+    */
+    let label = dns_domain.split('.').next().unwrap_or(dns_domain);
+    let name: String = label
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    truncate(&name, 15).to_ascii_uppercase()
+}
+
+pub fn down_level_logon_name(domain: &str, username: &str) -> String {
+    /*
+    Qualify a username with its NetBIOS domain
+    :param domain: NetBIOS or dns domain name, eg: CORP or corp.example.com
+    :param username: eg: jsmith
+    :return: eg: CORP\jsmith
+    */
+    format!("{}\\{}", netbios_name(domain), username)
+}
+
+pub fn user_principal_name(username: &str, upn_suffix: &str) -> String {
+    /*
+    User principal name (UPN) of a username
+    :param username: eg: jsmith
+    :param upn_suffix: dns domain, eg: corp.example.com
+    :return: eg: jsmith@corp.example.com
+    */
+    format!("{}@{}", username, upn_suffix.to_ascii_lowercase())
+}
+
+pub fn username(name: &PersonName, format: Option<UsernameFormat>) -> String {
+    /*
+    Generate a username for a person following a naming convention.
+    No uniqueness is tracked, use `UsernameGenerator` for batches.
+    :param name: person's name
+    :param format: username convention - random if not given
+    :return: username, eg: jsmith
+    */
+    let format = format.unwrap_or_else(UsernameFormat::random);
+    let base = truncate(&format.base(name), MAX_USERNAME_LENGTH);
+    if format.numbered() {
+        format!("{}01", truncate(&base, MAX_USERNAME_LENGTH - 2))
+    } else {
+        base
+    }
+}
+
+pub fn service_account(application: Option<&str>, style: Option<ServiceAccountStyle>) -> String {
+    /*
+    Generate a service account name
+    :param application: application the account runs, eg: sql - random if not given
+    :param style: naming style of the service account - random if not given
+    :return: service account name, eg: svc_sql, sa-backup
+    */
+    let mut trng = rand::thread_rng();
+    let application = match application {
+        Some(x) => sanitize(x),
+        None => SERVICE_ACCOUNT_APPLICATIONS
+            .choose(&mut trng)
+            .unwrap_or(&SERVICE_ACCOUNT_APPLICATIONS[0])
+            .to_string(),
+    };
+    let style = style.unwrap_or_else(|| {
+        *ServiceAccountStyle::ALL
+            .choose(&mut trng)
+            .unwrap_or(&ServiceAccountStyle::SvcUnderscore)
+    });
+    style.render(&application)
+}
+
+pub fn service_accounts<'a>(
+    application: Option<&'a str>,
+    style: Option<ServiceAccountStyle>,
+) -> impl Iterator<Item = String> + 'a {
+    /*
+    Endless stream of service account names, see service_account;
+    no uniqueness is tracked, use `UsernameGenerator` for that
+    :return: iterator of service account names
+    */
+    std::iter::repeat_with(move || service_account(application, style))
+}
+
+/// Generates usernames that are unique across a batch
+///
+/// ## Example
+/// ```rust
+/// use random::accounts::{UsernameFormat, UsernameGenerator};
+/// use random::names::person_name;
+///
+/// let mut generator = UsernameGenerator::new(UsernameFormat::LastFirstInitialNumber, None);
+/// for _ in 0..10 {
+///     let name = person_name(None, None);
+///     println!("{} -> {}", name, generator.username(&name));
+/// }
+/// assert_eq!(generator.len(), 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UsernameGenerator {
+    format: UsernameFormat,
+    max_length: usize,
+    issued: HashSet<String>,
+}
+
+impl UsernameGenerator {
+    pub fn new(format: UsernameFormat, max_length: Option<usize>) -> Self {
+        //! New generator for a username convention; `max_length`
+        //! defaults to the 20 char sAMAccountName limit
+        UsernameGenerator {
+            format,
+            max_length: max_length.unwrap_or(MAX_USERNAME_LENGTH).max(4),
+            issued: HashSet::new(),
+        }
+    }
+
+    pub fn format(&self) -> UsernameFormat {
+        self.format
+    }
+
+    pub fn len(&self) -> usize {
+        //! Number of names issued (or reserved) so far
+        self.issued.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issued.is_empty()
+    }
+
+    pub fn contains(&self, username: &str) -> bool {
+        self.issued.contains(&username.to_ascii_lowercase())
+    }
+
+    pub fn reserve(&mut self, username: &str) -> bool {
+        //! Mark an existing name as taken; returns false if it already was.
+        //! Names are compared case-insensitively, like windows does
+        self.issued.insert(username.to_ascii_lowercase())
+    }
+
+    fn unique(&mut self, base: &str, numbered: bool, tail: &str) -> String {
+        let max_length = self.max_length.saturating_sub(tail.len());
+        if !numbered {
+            let candidate = format!("{}{}", truncate(base, max_length), tail);
+            if self.reserve(&candidate) {
+                return candidate;
+            }
+        }
+        let mut n: u32 = 1;
+        loop {
+            let suffix = if numbered {
+                format!("{:02}", n)
+            } else {
+                n.to_string()
+            };
+            let stem = truncate(base, max_length.saturating_sub(suffix.len()));
+            let candidate = format!("{}{}{}", stem, suffix, tail);
+            if self.reserve(&candidate) {
+                return candidate;
+            }
+            n += 1;
+        }
+    }
+
+    pub fn username(&mut self, name: &PersonName) -> String {
+        //! Unique username for a person; collisions get a number
+        //! appended, eg: jsmith, jsmith1, jsmith2 or smithj01, smithj02
+        let base = self.format.base(name);
+        self.unique(&base, self.format.numbered(), "")
+    }
+
+    pub fn next_username(
+        &mut self,
+        gender: Option<Gender>,
+        locale: Option<Locale>,
+    ) -> (PersonName, String) {
+        //! Generate a random person along with their unique username
+        let name = crate::names::person_name(gender, locale);
+        let username = self.username(&name);
+        (name, username)
+    }
+
+    pub fn account(&mut self, name: &PersonName, dns_domain: &str) -> AccountName {
+        //! Unique username of a person with its domain qualified forms
+        let username = self.username(name);
+        AccountName {
+            down_level_logon_name: down_level_logon_name(dns_domain, &username),
+            user_principal_name: user_principal_name(&username, dns_domain),
+            username,
+        }
+    }
+
+    pub fn service_account(
+        &mut self,
+        application: Option<&str>,
+        style: Option<ServiceAccountStyle>,
+    ) -> String {
+        //! Unique service account name, see `service_account`
+        let name = service_account(application, style);
+        // keep the trailing `$` of managed service accounts
+        match name.strip_suffix('$') {
+            Some(stem) => self.unique(stem, false, "$"),
+            None => self.unique(&name, false, ""),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn john_smith() -> PersonName {
+        PersonName {
+            first_name: "John".to_string(),
+            last_name: "Smith".to_string(),
+            gender: Gender::Male,
+            locale: Locale::EnUs,
+        }
+    }
+
+    #[test]
+    fn test_username_formats() {
+        let name = john_smith();
+        let expected = [
+            (UsernameFormat::FirstInitialLast, "jsmith"),
+            (UsernameFormat::FirstDotLast, "john.smith"),
+            (UsernameFormat::FirstLast, "johnsmith"),
+            (UsernameFormat::LastFirstInitial, "smithj"),
+            (UsernameFormat::LastFirstInitialNumber, "smithj01"),
+        ];
+        for (format, username_str) in expected {
+            assert_eq!(username(&name, Some(format)), username_str);
+        }
+    }
+
+    #[test]
+    fn test_unique_usernames() {
+        let name = john_smith();
+
+        let mut generator = UsernameGenerator::new(UsernameFormat::FirstInitialLast, None);
+        assert_eq!(generator.username(&name), "jsmith");
+        assert_eq!(generator.username(&name), "jsmith1");
+        assert_eq!(generator.username(&name), "jsmith2");
+
+        let mut generator = UsernameGenerator::new(UsernameFormat::LastFirstInitialNumber, None);
+        assert!(generator.reserve("SMITHJ01"));
+        assert_eq!(generator.username(&name), "smithj02");
+        assert_eq!(generator.len(), 2);
+    }
+
+    #[test]
+    fn test_domain_names() {
+        let mut generator = UsernameGenerator::new(UsernameFormat::FirstInitialLast, None);
+        let account = generator.account(&john_smith(), "corp.example.com");
+        assert_eq!(account.down_level_logon_name, "CORP\\jsmith");
+        assert_eq!(account.user_principal_name, "jsmith@corp.example.com");
+    }
+
+    #[test]
+    fn test_service_accounts() {
+        assert_eq!(
+            service_account(Some("SQL"), Some(ServiceAccountStyle::SvcUnderscore)),
+            "svc_sql"
+        );
+        assert_eq!(
+            service_account(Some("backup"), Some(ServiceAccountStyle::SaDash)),
+            "sa-backup"
+        );
+
+        let mut generator = UsernameGenerator::new(UsernameFormat::FirstInitialLast, None);
+        let style = Some(ServiceAccountStyle::Gmsa);
+        assert_eq!(generator.service_account(Some("sql"), style), "gmsa-sql$");
+        assert_eq!(generator.service_account(Some("sql"), style), "gmsa-sql1$");
+    }
+
+    #[test]
+    fn test_iterators() {
+        assert!(
+            service_accounts(Some("sql"), Some(ServiceAccountStyle::SvcUnderscore))
+                .take(10)
+                .all(|a| a == "svc_sql")
+        );
+        assert_eq!(service_accounts(None, None).take(10).count(), 10);
+    }
+}
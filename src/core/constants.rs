@@ -1000,12 +1000,391 @@ pub static SERVER_APPLICATION_CODES: &[&str] = &[
     "epms",
 ];
 
-pub static ALL_NAMES: &[&str] = &[""];
-/*
-__filename = get_path("names.txt")
-with open(__filename) as f:
-    l = lazy_read(file_obj=f, buff_size=2048)
-    for elem in l:
-        all_names.append(elem.split("\n"))
-all_names = list(filter(None, set(flatten(all_names))))
-*/
+pub static SERVICE_ACCOUNT_APPLICATIONS: &[&str] = &[
+    "sql",
+    "backup",
+    "iis",
+    "exchange",
+    "sccm",
+    "scom",
+    "sharepoint",
+    "veeam",
+    "jenkins",
+    "ansible",
+    "splunk",
+    "ldap",
+    "git",
+    "ftp",
+    "smtp",
+    "print",
+    "scan",
+    "monitor",
+    "web",
+    "crm",
+    "erp",
+    "adsync",
+    "vpn",
+    "docker",
+];
+
+pub static COMMON_PASSWORDS: &[&str] = &[
+    "123456",
+    "password",
+    "123456789",
+    "12345678",
+    "12345",
+    "qwerty",
+    "abc123",
+    "111111",
+    "1234567",
+    "password1",
+    "1234567890",
+    "123123",
+    "000000",
+    "iloveyou",
+    "1234",
+    "qwerty123",
+    "1q2w3e4r",
+    "admin",
+    "letmein",
+    "welcome",
+    "monkey",
+    "dragon",
+    "football",
+    "baseball",
+    "sunshine",
+    "princess",
+    "master",
+    "shadow",
+    "trustno1",
+    "superman",
+    "qwertyuiop",
+    "passw0rd",
+    "Password1",
+    "Password123",
+    "P@ssw0rd",
+    "P@ssword1",
+    "Welcome1",
+    "Welcome123",
+    "Changeme1",
+    "changeme",
+    "Winter2020!",
+    "Summer2021!",
+    "Spring2022",
+    "Autumn2019",
+    "administrator",
+    "root",
+    "toor",
+    "guest",
+    "test123",
+    "Qwerty1!",
+];
+
+// (iso alpha-2 code, international calling code)
+pub static COUNTRY_CALLING_CODES: &[(&str, &str)] = &[
+    ("AD", "376"),
+    ("AF", "93"),
+    ("AG", "1268"),
+    ("AL", "355"),
+    ("AM", "374"),
+    ("AO", "244"),
+    ("AR", "54"),
+    ("AT", "43"),
+    ("AU", "61"),
+    ("AZ", "994"),
+    ("BB", "1246"),
+    ("BD", "880"),
+    ("BE", "32"),
+    ("BF", "226"),
+    ("BG", "359"),
+    ("BH", "973"),
+    ("BI", "257"),
+    ("BJ", "229"),
+    ("BN", "673"),
+    ("BO", "591"),
+    ("BR", "55"),
+    ("BS", "1242"),
+    ("BT", "975"),
+    ("BW", "267"),
+    ("BY", "375"),
+    ("BZ", "501"),
+    ("CA", "1"),
+    ("CD", "243"),
+    ("CG", "242"),
+    ("CI", "225"),
+    ("CL", "56"),
+    ("CM", "237"),
+    ("CN", "86"),
+    ("CO", "57"),
+    ("CR", "506"),
+    ("CU", "53"),
+    ("CV", "238"),
+    ("CY", "357"),
+    ("CZ", "420"),
+    ("DE", "49"),
+    ("DJ", "253"),
+    ("DK", "45"),
+    ("DM", "1767"),
+    ("DO", "1809"),
+    ("EC", "593"),
+    ("EE", "372"),
+    ("EG", "20"),
+    ("ER", "291"),
+    ("ET", "251"),
+    ("FI", "358"),
+    ("FJ", "679"),
+    ("FR", "33"),
+    ("GA", "241"),
+    ("GE", "995"),
+    ("GH", "233"),
+    ("GM", "220"),
+    ("GN", "224"),
+    ("GR", "30"),
+    ("GT", "502"),
+    ("HT", "509"),
+    ("GW", "245"),
+    ("GY", "592"),
+    ("HN", "504"),
+    ("HU", "36"),
+    ("ID", "62"),
+    ("IE", "353"),
+    ("IL", "972"),
+    ("IN", "91"),
+    ("IQ", "964"),
+    ("IR", "98"),
+    ("IS", "354"),
+    ("IT", "39"),
+    ("JM", "1876"),
+    ("JO", "962"),
+    ("JP", "81"),
+    ("KE", "254"),
+    ("KG", "996"),
+    ("KI", "686"),
+    ("KP", "850"),
+    ("KR", "82"),
+    ("KW", "965"),
+    ("LB", "961"),
+    ("LI", "423"),
+    ("LR", "231"),
+    ("LS", "266"),
+    ("LT", "370"),
+    ("LU", "352"),
+    ("LV", "371"),
+    ("LY", "218"),
+    ("MG", "261"),
+    ("MH", "692"),
+    ("MK", "389"),
+    ("ML", "223"),
+    ("MM", "95"),
+    ("MN", "976"),
+    ("MR", "222"),
+    ("MT", "356"),
+    ("MU", "230"),
+    ("MV", "960"),
+    ("MW", "265"),
+    ("MX", "52"),
+    ("MY", "60"),
+    ("MZ", "258"),
+    ("NA", "264"),
+    ("NE", "227"),
+    ("NG", "234"),
+    ("NI", "505"),
+    ("NL", "31"),
+    ("NO", "47"),
+    ("NP", "977"),
+    ("NR", "674"),
+    ("NZ", "64"),
+    ("OM", "968"),
+    ("PA", "507"),
+    ("PE", "51"),
+    ("PG", "675"),
+    ("PH", "63"),
+    ("PK", "92"),
+    ("PL", "48"),
+    ("PT", "351"),
+    ("PW", "680"),
+    ("PY", "595"),
+    ("QA", "974"),
+    ("RO", "40"),
+    ("RU", "7"),
+    ("RW", "250"),
+    ("SA", "966"),
+    ("SB", "677"),
+    ("SC", "248"),
+    ("SD", "249"),
+    ("SE", "46"),
+    ("SG", "65"),
+    ("SI", "386"),
+    ("SK", "421"),
+    ("SL", "232"),
+    ("SM", "378"),
+    ("SN", "221"),
+    ("SO", "252"),
+    ("SR", "597"),
+    ("ST", "239"),
+    ("SY", "963"),
+    ("TG", "228"),
+    ("TH", "66"),
+    ("TJ", "992"),
+    ("TM", "993"),
+    ("TN", "216"),
+    ("TO", "676"),
+    ("TR", "90"),
+    ("TT", "1868"),
+    ("TV", "688"),
+    ("TZ", "255"),
+    ("UA", "380"),
+    ("UG", "256"),
+    ("US", "1"),
+    ("UY", "598"),
+    ("UZ", "998"),
+    ("VA", "379"),
+    ("VE", "58"),
+    ("VN", "84"),
+    ("VU", "678"),
+    ("YE", "967"),
+    ("ZM", "260"),
+    ("ZW", "263"),
+    ("DZ", "213"),
+    ("BA", "387"),
+    ("KH", "855"),
+    ("CF", "236"),
+    ("TD", "235"),
+    ("KM", "269"),
+    ("HR", "385"),
+    ("TL", "670"),
+    ("SV", "503"),
+    ("GQ", "240"),
+    ("GD", "1473"),
+    ("KZ", "7"),
+    ("LA", "856"),
+    ("FM", "691"),
+    ("MD", "373"),
+    ("MC", "377"),
+    ("ME", "382"),
+    ("MA", "212"),
+    ("KN", "1869"),
+    ("LC", "1758"),
+    ("VC", "1784"),
+    ("WS", "685"),
+    ("RS", "381"),
+    ("ZA", "27"),
+    ("ES", "34"),
+    ("LK", "94"),
+    ("SZ", "268"),
+    ("CH", "41"),
+    ("AE", "971"),
+    ("GB", "44"),
+];
+
+// (department, job titles)
+pub static DEPARTMENT_JOB_TITLES: &[(&str, &[&str])] = &[
+    (
+        "Engineering",
+        &[
+            "Software Engineer",
+            "Senior Software Engineer",
+            "Staff Engineer",
+            "Engineering Manager",
+            "QA Engineer",
+            "DevOps Engineer",
+            "Site Reliability Engineer",
+        ],
+    ),
+    (
+        "Information Technology",
+        &[
+            "System Administrator",
+            "Network Engineer",
+            "IT Support Specialist",
+            "Helpdesk Analyst",
+            "IT Manager",
+            "Database Administrator",
+            "Cloud Architect",
+        ],
+    ),
+    (
+        "Security",
+        &[
+            "Security Analyst",
+            "SOC Analyst",
+            "Security Engineer",
+            "Penetration Tester",
+            "Incident Responder",
+            "CISO",
+        ],
+    ),
+    (
+        "Finance",
+        &[
+            "Accountant",
+            "Financial Analyst",
+            "Controller",
+            "Payroll Specialist",
+            "Accounts Payable Clerk",
+            "CFO",
+        ],
+    ),
+    (
+        "Human Resources",
+        &[
+            "HR Generalist",
+            "Recruiter",
+            "HR Business Partner",
+            "Talent Acquisition Manager",
+            "HR Director",
+        ],
+    ),
+    (
+        "Sales",
+        &[
+            "Account Executive",
+            "Sales Development Representative",
+            "Sales Manager",
+            "Account Manager",
+            "VP of Sales",
+        ],
+    ),
+    (
+        "Marketing",
+        &[
+            "Marketing Specialist",
+            "Content Writer",
+            "SEO Specialist",
+            "Product Marketing Manager",
+            "Marketing Director",
+        ],
+    ),
+    (
+        "Legal",
+        &[
+            "Legal Counsel",
+            "Paralegal",
+            "Compliance Officer",
+            "General Counsel",
+        ],
+    ),
+    (
+        "Operations",
+        &[
+            "Operations Manager",
+            "Office Manager",
+            "Facilities Coordinator",
+            "Logistics Coordinator",
+            "COO",
+        ],
+    ),
+    (
+        "Customer Support",
+        &[
+            "Support Agent",
+            "Customer Success Manager",
+            "Support Team Lead",
+            "Technical Support Engineer",
+        ],
+    ),
+    (
+        "Executive",
+        &["CEO", "CTO", "Executive Assistant", "Chief of Staff"],
+    ),
+];
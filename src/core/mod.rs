@@ -1,2 +1,3 @@
 pub mod constants;
+pub mod names;
 pub mod utils;
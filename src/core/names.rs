@@ -0,0 +1,1010 @@
+/*
+Bundled first / last name datasets, grouped by locale.
+Every locale carries male, female and gender-neutral first names
+plus a list of common family names. Non-latin scripts are romanized.
+*/
+
+// en_US
+pub static EN_US_MALE: &[&str] = &[
+    "James",
+    "John",
+    "Robert",
+    "Michael",
+    "William",
+    "David",
+    "Richard",
+    "Joseph",
+    "Thomas",
+    "Charles",
+    "Christopher",
+    "Daniel",
+    "Matthew",
+    "Anthony",
+    "Mark",
+    "Donald",
+    "Steven",
+    "Paul",
+    "Andrew",
+    "Joshua",
+    "Kenneth",
+    "Kevin",
+    "Brian",
+    "George",
+    "Timothy",
+    "Ronald",
+    "Edward",
+    "Jason",
+    "Jeffrey",
+    "Ryan",
+];
+pub static EN_US_FEMALE: &[&str] = &[
+    "Mary",
+    "Patricia",
+    "Jennifer",
+    "Linda",
+    "Elizabeth",
+    "Barbara",
+    "Susan",
+    "Jessica",
+    "Sarah",
+    "Karen",
+    "Lisa",
+    "Nancy",
+    "Betty",
+    "Margaret",
+    "Sandra",
+    "Ashley",
+    "Kimberly",
+    "Emily",
+    "Donna",
+    "Michelle",
+    "Carol",
+    "Amanda",
+    "Dorothy",
+    "Melissa",
+    "Deborah",
+    "Stephanie",
+    "Rebecca",
+    "Sharon",
+    "Laura",
+    "Cynthia",
+];
+pub static EN_US_NEUTRAL: &[&str] = &[
+    "Alex", "Jordan", "Taylor", "Morgan", "Casey", "Riley", "Jamie", "Avery", "Quinn", "Parker",
+    "Skyler", "Cameron", "Dakota", "Reese", "Rowan",
+];
+pub static EN_US_LAST: &[&str] = &[
+    "Smith",
+    "Johnson",
+    "Williams",
+    "Brown",
+    "Jones",
+    "Garcia",
+    "Miller",
+    "Davis",
+    "Rodriguez",
+    "Martinez",
+    "Hernandez",
+    "Lopez",
+    "Gonzalez",
+    "Wilson",
+    "Anderson",
+    "Thomas",
+    "Taylor",
+    "Moore",
+    "Jackson",
+    "Martin",
+    "Lee",
+    "Perez",
+    "Thompson",
+    "White",
+    "Harris",
+    "Sanchez",
+    "Clark",
+    "Ramirez",
+    "Lewis",
+    "Robinson",
+    "Walker",
+    "Young",
+    "Allen",
+    "King",
+    "Wright",
+];
+
+// en_GB
+pub static EN_GB_MALE: &[&str] = &[
+    "Oliver",
+    "George",
+    "Harry",
+    "Jack",
+    "Jacob",
+    "Noah",
+    "Charlie",
+    "Muhammad",
+    "Thomas",
+    "Oscar",
+    "William",
+    "James",
+    "Henry",
+    "Leo",
+    "Alfie",
+    "Joshua",
+    "Freddie",
+    "Archie",
+    "Ethan",
+    "Isaac",
+    "Alexander",
+    "Joseph",
+    "Edward",
+    "Samuel",
+    "Max",
+];
+pub static EN_GB_FEMALE: &[&str] = &[
+    "Olivia",
+    "Amelia",
+    "Isla",
+    "Ava",
+    "Emily",
+    "Isabella",
+    "Mia",
+    "Poppy",
+    "Ella",
+    "Lily",
+    "Jessica",
+    "Sophie",
+    "Grace",
+    "Evie",
+    "Charlotte",
+    "Harriet",
+    "Florence",
+    "Freya",
+    "Ruby",
+    "Daisy",
+    "Alice",
+    "Phoebe",
+    "Matilda",
+    "Eleanor",
+    "Imogen",
+];
+pub static EN_GB_NEUTRAL: &[&str] = &[
+    "Sam", "Charlie", "Frankie", "Robin", "Ashley", "Jesse", "Billie", "Ellis", "Harley", "Jude",
+];
+pub static EN_GB_LAST: &[&str] = &[
+    "Smith", "Jones", "Taylor", "Brown", "Williams", "Wilson", "Johnson", "Davies", "Robinson",
+    "Wright", "Thompson", "Evans", "Walker", "White", "Roberts", "Green", "Hall", "Wood",
+    "Jackson", "Clarke", "Patel", "Hughes", "Edwards", "Turner", "Hill", "Cooper", "Ward",
+    "Morris", "Baker", "Harrison",
+];
+
+// de_DE
+pub static DE_DE_MALE: &[&str] = &[
+    "Lukas",
+    "Leon",
+    "Finn",
+    "Jonas",
+    "Paul",
+    "Felix",
+    "Maximilian",
+    "Elias",
+    "Ben",
+    "Noah",
+    "Moritz",
+    "Julian",
+    "Tim",
+    "Jan",
+    "Niklas",
+    "Tobias",
+    "Stefan",
+    "Jürgen",
+    "Klaus",
+    "Matthias",
+    "Andreas",
+    "Thomas",
+    "Michael",
+    "Wolfgang",
+    "Uwe",
+];
+pub static DE_DE_FEMALE: &[&str] = &[
+    "Mia",
+    "Emma",
+    "Hannah",
+    "Sofia",
+    "Anna",
+    "Lea",
+    "Lena",
+    "Marie",
+    "Emilia",
+    "Lina",
+    "Laura",
+    "Johanna",
+    "Katharina",
+    "Sabine",
+    "Petra",
+    "Monika",
+    "Ursula",
+    "Claudia",
+    "Birgit",
+    "Jutta",
+    "Sabrina",
+    "Nicole",
+    "Julia",
+    "Franziska",
+    "Jana",
+];
+pub static DE_DE_NEUTRAL: &[&str] = &[
+    "Kim", "Luca", "Maxi", "Toni", "Sascha", "Kai", "Eike", "Mika", "Janne", "Niko",
+];
+pub static DE_DE_LAST: &[&str] = &[
+    "Müller",
+    "Schmidt",
+    "Schneider",
+    "Fischer",
+    "Weber",
+    "Meyer",
+    "Wagner",
+    "Becker",
+    "Schulz",
+    "Hoffmann",
+    "Schäfer",
+    "Koch",
+    "Bauer",
+    "Richter",
+    "Klein",
+    "Wolf",
+    "Schröder",
+    "Neumann",
+    "Schwarz",
+    "Zimmermann",
+    "Braun",
+    "Krüger",
+    "Hofmann",
+    "Hartmann",
+    "Lange",
+    "Schmitt",
+    "Werner",
+    "Schmitz",
+    "Krause",
+    "Meier",
+];
+
+// fr_FR
+pub static FR_FR_MALE: &[&str] = &[
+    "Gabriel",
+    "Louis",
+    "Raphaël",
+    "Jules",
+    "Adam",
+    "Lucas",
+    "Léo",
+    "Hugo",
+    "Arthur",
+    "Nathan",
+    "Théo",
+    "Nicolas",
+    "Julien",
+    "Pierre",
+    "Antoine",
+    "Mathieu",
+    "Sébastien",
+    "François",
+    "Philippe",
+    "Olivier",
+    "Laurent",
+    "Christophe",
+    "Guillaume",
+    "Thierry",
+    "Émile",
+];
+pub static FR_FR_FEMALE: &[&str] = &[
+    "Emma",
+    "Jade",
+    "Louise",
+    "Alice",
+    "Chloé",
+    "Lina",
+    "Léa",
+    "Manon",
+    "Camille",
+    "Inès",
+    "Juliette",
+    "Sarah",
+    "Marie",
+    "Nathalie",
+    "Isabelle",
+    "Sylvie",
+    "Catherine",
+    "Françoise",
+    "Céline",
+    "Sophie",
+    "Valérie",
+    "Aurélie",
+    "Hélène",
+    "Margaux",
+    "Élodie",
+];
+pub static FR_FR_NEUTRAL: &[&str] = &[
+    "Camille",
+    "Dominique",
+    "Claude",
+    "Maxime",
+    "Sacha",
+    "Alix",
+    "Lou",
+    "Charlie",
+    "Morgane",
+    "Eden",
+];
+pub static FR_FR_LAST: &[&str] = &[
+    "Martin", "Bernard", "Thomas", "Petit", "Robert", "Richard", "Durand", "Dubois", "Moreau",
+    "Laurent", "Simon", "Michel", "Lefèvre", "Leroy", "Roux", "David", "Bertrand", "Morel",
+    "Fournier", "Girard", "Bonnet", "Dupont", "Lambert", "Fontaine", "Rousseau", "Vincent",
+    "Muller", "Lefebvre", "Faure", "André",
+];
+
+// es_ES
+pub static ES_ES_MALE: &[&str] = &[
+    "Antonio",
+    "Manuel",
+    "José",
+    "Francisco",
+    "David",
+    "Juan",
+    "Javier",
+    "Daniel",
+    "Carlos",
+    "Jesús",
+    "Alejandro",
+    "Miguel",
+    "Rafael",
+    "Pablo",
+    "Sergio",
+    "Fernando",
+    "Jorge",
+    "Luis",
+    "Alberto",
+    "Álvaro",
+    "Diego",
+    "Adrián",
+    "Raúl",
+    "Enrique",
+    "Ramón",
+];
+pub static ES_ES_FEMALE: &[&str] = &[
+    "María", "Carmen", "Ana", "Isabel", "Laura", "Cristina", "Marta", "Lucía", "Pilar", "Elena",
+    "Paula", "Sara", "Raquel", "Rosa", "Manuela", "Beatriz", "Silvia", "Nuria", "Patricia",
+    "Irene", "Andrea", "Rocío", "Mercedes", "Alba", "Julia",
+];
+pub static ES_ES_NEUTRAL: &[&str] = &[
+    "Alexis",
+    "Ariel",
+    "Cruz",
+    "Guadalupe",
+    "Reyes",
+    "Trinidad",
+    "Noa",
+    "Andrea",
+    "Celeste",
+    "Paz",
+];
+pub static ES_ES_LAST: &[&str] = &[
+    "García",
+    "Rodríguez",
+    "González",
+    "Fernández",
+    "López",
+    "Martínez",
+    "Sánchez",
+    "Pérez",
+    "Gómez",
+    "Martín",
+    "Jiménez",
+    "Ruiz",
+    "Hernández",
+    "Díaz",
+    "Moreno",
+    "Muñoz",
+    "Álvarez",
+    "Romero",
+    "Alonso",
+    "Gutiérrez",
+    "Navarro",
+    "Torres",
+    "Domínguez",
+    "Vázquez",
+    "Ramos",
+    "Gil",
+    "Ramírez",
+    "Serrano",
+    "Blanco",
+    "Molina",
+];
+
+// it_IT
+pub static IT_IT_MALE: &[&str] = &[
+    "Leonardo",
+    "Francesco",
+    "Alessandro",
+    "Lorenzo",
+    "Mattia",
+    "Andrea",
+    "Gabriele",
+    "Riccardo",
+    "Tommaso",
+    "Edoardo",
+    "Marco",
+    "Giuseppe",
+    "Giovanni",
+    "Antonio",
+    "Luca",
+    "Paolo",
+    "Stefano",
+    "Roberto",
+    "Massimo",
+    "Salvatore",
+    "Fabio",
+    "Davide",
+    "Simone",
+    "Matteo",
+    "Nicola",
+];
+pub static IT_IT_FEMALE: &[&str] = &[
+    "Sofia",
+    "Giulia",
+    "Aurora",
+    "Alice",
+    "Ginevra",
+    "Emma",
+    "Giorgia",
+    "Greta",
+    "Beatrice",
+    "Anna",
+    "Maria",
+    "Francesca",
+    "Chiara",
+    "Federica",
+    "Valentina",
+    "Sara",
+    "Silvia",
+    "Elena",
+    "Paola",
+    "Roberta",
+    "Alessia",
+    "Martina",
+    "Elisa",
+    "Laura",
+    "Rosa",
+];
+pub static IT_IT_NEUTRAL: &[&str] = &[
+    "Celeste", "Andrea", "Fiore", "Vale", "Ale", "Sole", "Michi", "Fede", "Gabri", "Dani",
+];
+pub static IT_IT_LAST: &[&str] = &[
+    "Rossi", "Russo", "Ferrari", "Esposito", "Bianchi", "Romano", "Colombo", "Ricci", "Marino",
+    "Greco", "Bruno", "Gallo", "Conti", "De Luca", "Mancini", "Costa", "Giordano", "Rizzo",
+    "Lombardi", "Moretti", "Barbieri", "Fontana", "Santoro", "Mariani", "Rinaldi", "Caruso",
+    "Ferrara", "Galli", "Martini", "Leone",
+];
+
+// pt_BR
+pub static PT_BR_MALE: &[&str] = &[
+    "Miguel",
+    "Arthur",
+    "Heitor",
+    "Bernardo",
+    "Davi",
+    "Gabriel",
+    "Pedro",
+    "Lorenzo",
+    "Matheus",
+    "Lucas",
+    "João",
+    "Rafael",
+    "Gustavo",
+    "Felipe",
+    "Bruno",
+    "Thiago",
+    "Rodrigo",
+    "Marcelo",
+    "Leandro",
+    "Fábio",
+    "Eduardo",
+    "Vinícius",
+    "André",
+    "Paulo",
+    "Caio",
+];
+pub static PT_BR_FEMALE: &[&str] = &[
+    "Alice",
+    "Sophia",
+    "Helena",
+    "Valentina",
+    "Laura",
+    "Isabella",
+    "Manuela",
+    "Júlia",
+    "Heloísa",
+    "Luiza",
+    "Maria",
+    "Ana",
+    "Beatriz",
+    "Camila",
+    "Fernanda",
+    "Juliana",
+    "Larissa",
+    "Letícia",
+    "Mariana",
+    "Patrícia",
+    "Renata",
+    "Vanessa",
+    "Gabriela",
+    "Amanda",
+    "Bruna",
+];
+pub static PT_BR_NEUTRAL: &[&str] = &[
+    "Ariel", "Darcy", "Jaci", "Juracy", "Noah", "Eli", "Cris", "Dani", "Juli", "Sasha",
+];
+pub static PT_BR_LAST: &[&str] = &[
+    "Silva",
+    "Santos",
+    "Oliveira",
+    "Souza",
+    "Rodrigues",
+    "Ferreira",
+    "Alves",
+    "Pereira",
+    "Lima",
+    "Gomes",
+    "Costa",
+    "Ribeiro",
+    "Martins",
+    "Carvalho",
+    "Almeida",
+    "Lopes",
+    "Soares",
+    "Fernandes",
+    "Vieira",
+    "Barbosa",
+    "Rocha",
+    "Dias",
+    "Nascimento",
+    "Andrade",
+    "Moreira",
+    "Nunes",
+    "Marques",
+    "Machado",
+    "Mendes",
+    "Freitas",
+];
+
+// nl_NL
+pub static NL_NL_MALE: &[&str] = &[
+    "Daan", "Sem", "Lucas", "Levi", "Finn", "Milan", "Bram", "Thijs", "Jesse", "Ruben", "Lars",
+    "Sander", "Joost", "Pieter", "Jan", "Kees", "Willem", "Hendrik", "Maarten", "Jeroen", "Bas",
+    "Koen", "Stijn", "Wouter", "Niels",
+];
+pub static NL_NL_FEMALE: &[&str] = &[
+    "Emma", "Julia", "Mila", "Tess", "Sophie", "Zoë", "Sara", "Nora", "Fenna", "Lotte", "Anouk",
+    "Sanne", "Femke", "Lieke", "Marieke", "Ingrid", "Annelies", "Esther", "Ilse", "Mirjam", "Eva",
+    "Fleur", "Iris", "Noor", "Roos",
+];
+pub static NL_NL_NEUTRAL: &[&str] = &[
+    "Sam", "Noa", "Robin", "Kim", "Jip", "Luca", "Sascha", "Jente", "Dani", "Jo",
+];
+pub static NL_NL_LAST: &[&str] = &[
+    "de Jong",
+    "Jansen",
+    "de Vries",
+    "van den Berg",
+    "van Dijk",
+    "Bakker",
+    "Janssen",
+    "Visser",
+    "Smit",
+    "Meijer",
+    "de Boer",
+    "Mulder",
+    "de Groot",
+    "Bos",
+    "Vos",
+    "Peters",
+    "Hendriks",
+    "van Leeuwen",
+    "Dekker",
+    "Brouwer",
+    "de Wit",
+    "Dijkstra",
+    "Smits",
+    "de Graaf",
+    "van der Meer",
+    "Kok",
+    "Jacobs",
+    "de Haan",
+    "Vermeulen",
+    "van den Heuvel",
+];
+
+// sv_SE
+pub static SV_SE_MALE: &[&str] = &[
+    "Lars", "Mikael", "Anders", "Johan", "Erik", "Per", "Karl", "Peter", "Thomas", "Jan", "Daniel",
+    "Fredrik", "Hans", "Andreas", "Stefan", "Mats", "Magnus", "Oskar", "Nils", "Björn", "Gustav",
+    "Axel", "Olof", "Håkan", "Göran",
+];
+pub static SV_SE_FEMALE: &[&str] = &[
+    "Anna", "Eva", "Maria", "Karin", "Sara", "Kristina", "Lena", "Emma", "Kerstin", "Ingrid",
+    "Malin", "Sofia", "Linnéa", "Elin", "Ida", "Johanna", "Astrid", "Birgitta", "Märta", "Frida",
+    "Hanna", "Agnes", "Ebba", "Saga", "Maja",
+];
+pub static SV_SE_NEUTRAL: &[&str] = &[
+    "Kim", "Alex", "Robin", "Love", "Charlie", "Vide", "Sam", "Eli", "Tintin", "Micke",
+];
+pub static SV_SE_LAST: &[&str] = &[
+    "Andersson",
+    "Johansson",
+    "Karlsson",
+    "Nilsson",
+    "Eriksson",
+    "Larsson",
+    "Olsson",
+    "Persson",
+    "Svensson",
+    "Gustafsson",
+    "Pettersson",
+    "Jonsson",
+    "Jansson",
+    "Hansson",
+    "Bengtsson",
+    "Jönsson",
+    "Lindberg",
+    "Jakobsson",
+    "Magnusson",
+    "Olofsson",
+    "Lindström",
+    "Lindqvist",
+    "Lindgren",
+    "Berg",
+    "Axelsson",
+    "Bergström",
+    "Lundberg",
+    "Lind",
+    "Lundgren",
+    "Mattsson",
+];
+
+// pl_PL
+pub static PL_PL_MALE: &[&str] = &[
+    "Piotr",
+    "Krzysztof",
+    "Andrzej",
+    "Tomasz",
+    "Paweł",
+    "Jan",
+    "Michał",
+    "Marcin",
+    "Jakub",
+    "Adam",
+    "Łukasz",
+    "Marek",
+    "Grzegorz",
+    "Mateusz",
+    "Wojciech",
+    "Mariusz",
+    "Dariusz",
+    "Zbigniew",
+    "Maciej",
+    "Kamil",
+    "Szymon",
+    "Antoni",
+    "Jerzy",
+    "Rafał",
+    "Bartosz",
+];
+pub static PL_PL_FEMALE: &[&str] = &[
+    "Anna",
+    "Maria",
+    "Katarzyna",
+    "Małgorzata",
+    "Agnieszka",
+    "Barbara",
+    "Ewa",
+    "Krystyna",
+    "Magdalena",
+    "Elżbieta",
+    "Joanna",
+    "Aleksandra",
+    "Monika",
+    "Zofia",
+    "Teresa",
+    "Danuta",
+    "Natalia",
+    "Julia",
+    "Karolina",
+    "Marta",
+    "Beata",
+    "Dorota",
+    "Halina",
+    "Jadwiga",
+    "Zuzanna",
+];
+pub static PL_PL_NEUTRAL: &[&str] = &[
+    "Sasza", "Ala", "Kuba", "Oli", "Nikola", "Ari", "Noa", "Eli", "Kris", "Fran",
+];
+pub static PL_PL_LAST: &[&str] = &[
+    "Nowak",
+    "Kowalski",
+    "Wiśniewski",
+    "Wójcik",
+    "Kowalczyk",
+    "Kamiński",
+    "Lewandowski",
+    "Zieliński",
+    "Szymański",
+    "Woźniak",
+    "Dąbrowski",
+    "Kozłowski",
+    "Jankowski",
+    "Mazur",
+    "Wojciechowski",
+    "Kwiatkowski",
+    "Krawczyk",
+    "Kaczmarek",
+    "Piotrowski",
+    "Grabowski",
+    "Zając",
+    "Pawłowski",
+    "Michalski",
+    "Król",
+    "Wieczorek",
+    "Jabłoński",
+    "Wróbel",
+    "Nowakowski",
+    "Majewski",
+    "Olszewski",
+];
+
+// ja_JP (romanized)
+pub static JA_JP_MALE: &[&str] = &[
+    "Haruto", "Yuto", "Sota", "Yuki", "Hayato", "Haruki", "Ryusei", "Koki", "Sora", "Sosuke",
+    "Hiroshi", "Takashi", "Kenji", "Daiki", "Shota", "Takumi", "Kazuki", "Naoki", "Satoshi",
+    "Akira", "Makoto", "Tsubasa", "Ren", "Kaito", "Riku",
+];
+pub static JA_JP_FEMALE: &[&str] = &[
+    "Yui", "Aoi", "Hina", "Sakura", "Mei", "Yuna", "Rin", "Koharu", "Himari", "Akari", "Yoko",
+    "Keiko", "Tomoko", "Naoko", "Yumi", "Ayumi", "Haruka", "Misaki", "Nanami", "Emi", "Kana",
+    "Megumi", "Aya", "Mai", "Saki",
+];
+pub static JA_JP_NEUTRAL: &[&str] = &[
+    "Hikaru", "Makoto", "Akira", "Kaoru", "Nao", "Haru", "Michi", "Yuu", "Shinobu", "Tsubasa",
+];
+pub static JA_JP_LAST: &[&str] = &[
+    "Sato",
+    "Suzuki",
+    "Takahashi",
+    "Tanaka",
+    "Watanabe",
+    "Ito",
+    "Yamamoto",
+    "Nakamura",
+    "Kobayashi",
+    "Kato",
+    "Yoshida",
+    "Yamada",
+    "Sasaki",
+    "Yamaguchi",
+    "Matsumoto",
+    "Inoue",
+    "Kimura",
+    "Hayashi",
+    "Shimizu",
+    "Yamazaki",
+    "Mori",
+    "Abe",
+    "Ikeda",
+    "Hashimoto",
+    "Yamashita",
+    "Ishikawa",
+    "Nakajima",
+    "Maeda",
+    "Fujita",
+    "Ogawa",
+];
+
+// zh_CN (pinyin)
+pub static ZH_CN_MALE: &[&str] = &[
+    "Wei", "Hao", "Yang", "Jun", "Lei", "Qiang", "Ming", "Jie", "Tao", "Bin", "Chao", "Gang",
+    "Peng", "Hui", "Long", "Feng", "Bo", "Jian", "Zhiwei", "Haoran", "Yuxuan", "Zihao", "Junjie",
+    "Yichen", "Tianyu",
+];
+pub static ZH_CN_FEMALE: &[&str] = &[
+    "Fang", "Na", "Min", "Jing", "Li", "Yan", "Juan", "Xiuying", "Xia", "Ping", "Lan", "Hong",
+    "Yun", "Ying", "Mei", "Lin", "Xue", "Qian", "Ting", "Zihan", "Yutong", "Xinyi", "Shiyu",
+    "Yiyi", "Ruoxi",
+];
+pub static ZH_CN_NEUTRAL: &[&str] = &[
+    "Yu", "Xin", "Chen", "Ning", "An", "Kai", "Le", "Rui", "Yi", "Zhen",
+];
+pub static ZH_CN_LAST: &[&str] = &[
+    "Wang", "Li", "Zhang", "Liu", "Chen", "Yang", "Huang", "Zhao", "Wu", "Zhou", "Xu", "Sun", "Ma",
+    "Zhu", "Hu", "Guo", "He", "Gao", "Lin", "Luo", "Zheng", "Liang", "Xie", "Song", "Tang", "Han",
+    "Feng", "Deng", "Cao", "Peng",
+];
+
+// hi_IN
+pub static HI_IN_MALE: &[&str] = &[
+    "Aarav",
+    "Vivaan",
+    "Aditya",
+    "Vihaan",
+    "Arjun",
+    "Sai",
+    "Reyansh",
+    "Ayaan",
+    "Krishna",
+    "Ishaan",
+    "Rahul",
+    "Amit",
+    "Rajesh",
+    "Suresh",
+    "Vikram",
+    "Sanjay",
+    "Anil",
+    "Sunil",
+    "Rohit",
+    "Manoj",
+    "Deepak",
+    "Nikhil",
+    "Karthik",
+    "Pranav",
+    "Siddharth",
+];
+pub static HI_IN_FEMALE: &[&str] = &[
+    "Saanvi",
+    "Aanya",
+    "Aadhya",
+    "Ananya",
+    "Pari",
+    "Diya",
+    "Myra",
+    "Sara",
+    "Ira",
+    "Priya",
+    "Pooja",
+    "Neha",
+    "Sunita",
+    "Anita",
+    "Kavita",
+    "Deepa",
+    "Lakshmi",
+    "Meera",
+    "Divya",
+    "Shreya",
+    "Nisha",
+    "Rekha",
+    "Swati",
+    "Aishwarya",
+    "Kavya",
+];
+pub static HI_IN_NEUTRAL: &[&str] = &[
+    "Kiran", "Sonu", "Jyoti", "Ira", "Avni", "Noor", "Shashi", "Santosh", "Sweety", "Dev",
+];
+pub static HI_IN_LAST: &[&str] = &[
+    "Sharma",
+    "Verma",
+    "Gupta",
+    "Singh",
+    "Kumar",
+    "Patel",
+    "Shah",
+    "Mehta",
+    "Iyer",
+    "Nair",
+    "Reddy",
+    "Rao",
+    "Joshi",
+    "Desai",
+    "Chopra",
+    "Malhotra",
+    "Kapoor",
+    "Bhat",
+    "Pillai",
+    "Menon",
+    "Agarwal",
+    "Banerjee",
+    "Chatterjee",
+    "Mukherjee",
+    "Das",
+    "Ghosh",
+    "Jain",
+    "Kulkarni",
+    "Pandey",
+    "Mishra",
+];
+
+// ru_RU (romanized)
+pub static RU_RU_MALE: &[&str] = &[
+    "Aleksandr",
+    "Sergey",
+    "Dmitry",
+    "Andrey",
+    "Aleksey",
+    "Maksim",
+    "Evgeny",
+    "Ivan",
+    "Mikhail",
+    "Artem",
+    "Nikolay",
+    "Vladimir",
+    "Pavel",
+    "Denis",
+    "Roman",
+    "Oleg",
+    "Igor",
+    "Yury",
+    "Viktor",
+    "Konstantin",
+    "Anton",
+    "Kirill",
+    "Ilya",
+    "Vadim",
+    "Timur",
+];
+pub static RU_RU_FEMALE: &[&str] = &[
+    "Elena",
+    "Olga",
+    "Natalia",
+    "Tatiana",
+    "Irina",
+    "Svetlana",
+    "Anna",
+    "Maria",
+    "Ekaterina",
+    "Yulia",
+    "Anastasia",
+    "Marina",
+    "Galina",
+    "Lyudmila",
+    "Daria",
+    "Oksana",
+    "Victoria",
+    "Ksenia",
+    "Polina",
+    "Alina",
+    "Valentina",
+    "Nadezhda",
+    "Vera",
+    "Sofia",
+    "Larisa",
+];
+pub static RU_RU_NEUTRAL: &[&str] = &[
+    "Sasha", "Zhenya", "Valya", "Shura", "Slava", "Tosha", "Styopa", "Kolya", "Vitya", "Lyuba",
+];
+pub static RU_RU_LAST: &[&str] = &[
+    "Ivanov",
+    "Smirnov",
+    "Kuznetsov",
+    "Popov",
+    "Vasiliev",
+    "Petrov",
+    "Sokolov",
+    "Mikhailov",
+    "Novikov",
+    "Fedorov",
+    "Morozov",
+    "Volkov",
+    "Alekseev",
+    "Lebedev",
+    "Semenov",
+    "Egorov",
+    "Pavlov",
+    "Kozlov",
+    "Stepanov",
+    "Nikolaev",
+    "Orlov",
+    "Andreev",
+    "Makarov",
+    "Nikitin",
+    "Zakharov",
+    "Zaitsev",
+    "Soloviev",
+    "Borisov",
+    "Yakovlev",
+    "Grigoriev",
+];
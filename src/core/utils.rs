@@ -42,7 +42,7 @@ pub fn hex_pattern(pattern: Option<&str>, upper: Option<bool>) -> String {
     };
 
     let rg = regex::Regex::new(r"\^").unwrap();
-    let result = rg.replace_all(&pattern, |_caps: &regex::Captures| {
+    let result = rg.replace_all(pattern, |_caps: &regex::Captures| {
         processed_letters
             .chars()
             .choose(&mut rand::thread_rng())
@@ -66,3 +66,77 @@ def lazy_read(file_obj, buff_size=1024):
             break
         yield data
 */
+
+pub fn ascii_fold(text: &str) -> String {
+    /*
+    Fold latin diacritics down to their plain ascii letter so that
+    names can be used for usernames, hostnames, emails, etc...
+    :param text: text to fold
+    :return: ascii only string; characters with no mapping are dropped
+            eg: ascii_fold("Jürgen Müller") will return "Jurgen Muller"
+    */
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            folded.push(c);
+            continue;
+        }
+        let replacement = match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ą' => "a",
+            'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ą' => "A",
+            'æ' => "ae",
+            'Æ' => "AE",
+            'ç' | 'ć' | 'č' => "c",
+            'Ç' | 'Ć' | 'Č' => "C",
+            'è' | 'é' | 'ê' | 'ë' | 'ę' | 'ě' => "e",
+            'È' | 'É' | 'Ê' | 'Ë' | 'Ę' | 'Ě' => "E",
+            'ì' | 'í' | 'î' | 'ï' => "i",
+            'Ì' | 'Í' | 'Î' | 'Ï' => "I",
+            'ł' => "l",
+            'Ł' => "L",
+            'ñ' | 'ń' | 'ň' => "n",
+            'Ñ' | 'Ń' | 'Ň' => "N",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+            'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => "O",
+            'œ' => "oe",
+            'Œ' => "OE",
+            'ś' | 'š' => "s",
+            'Ś' | 'Š' => "S",
+            'ß' => "ss",
+            'ù' | 'ú' | 'û' | 'ü' | 'ů' => "u",
+            'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ů' => "U",
+            'ý' | 'ÿ' => "y",
+            'Ý' => "Y",
+            'ź' | 'ż' | 'ž' => "z",
+            'Ź' | 'Ż' | 'Ž' => "Z",
+            _ => "",
+        };
+        folded.push_str(replacement);
+    }
+    folded
+}
+
+pub fn strip_digits(text: &str) -> String {
+    /*
+    Remove all the digits from a given string
+    :param text: text to strip
+    :return: string without any digits
+            eg: strip_digits("jsmith01") will return "jsmith"
+    */
+    text.chars().filter(|c| !c.is_ascii_digit()).collect()
+}
+
+pub fn hex_byte(text: &str) -> Option<u8> {
+    /*
+    Parse a byte from exactly two hex digits; from_str_radix alone
+    would take a '+' sign, or 1 to 3 digits with leading zeros
+    :param text: two hex digits
+    :return: the byte, or None for anything else
+            eg: hex_byte("3e") will return Some(62), hex_byte("+e") None
+    */
+    if text.len() == 2 && text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        u8::from_str_radix(text, 16).ok()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,483 @@
+/*
+Credential generators
+Passwords following a policy, deliberately weak passwords for audits
+and their hashes (NTLM, bcrypt, SHA-512 crypt, PBKDF2) to build
+/etc/shadow & AD dump fixtures
+*/
+
+use base64::Engine;
+use md4::{Digest, Md4};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use sha2::Sha256;
+
+use crate::core::constants::{COMMON_PASSWORDS, DUMMY_CORPS, ENG_NOUNS};
+
+type Error = String;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.?/";
+const CRYPT_SALT_CHARS: &str = "./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// LM hash of an empty password, as found in every modern AD dump
+pub const EMPTY_LM_HASH: &str = "aad3b435b51404eeaad3b435b51404ee";
+pub const DEFAULT_BCRYPT_COST: u32 = 10;
+pub const DEFAULT_SHA512_CRYPT_ROUNDS: usize = 5_000;
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 260_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub lowercase: bool,
+    pub uppercase: bool,
+    pub digits: bool,
+    pub symbols: bool,
+    /// reject passwords containing an english noun (see `ENG_NOUNS`)
+    pub no_dictionary_words: bool,
+    /// shortest dictionary word considered when `no_dictionary_words` is set
+    pub min_word_length: usize,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        PasswordPolicy {
+            min_length: 12,
+            max_length: 16,
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+            no_dictionary_words: true,
+            min_word_length: 4,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    fn classes(&self) -> Vec<&'static str> {
+        [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, class)| *class)
+        .collect()
+    }
+
+    fn dictionary_word(&self, password: &str) -> Option<&'static str> {
+        let password = password.to_ascii_lowercase();
+        ENG_NOUNS
+            .iter()
+            .filter(|word| word.len() >= self.min_word_length)
+            .find(|word| password.contains(*word))
+            .copied()
+    }
+
+    pub fn validate(&self, password: &str) -> Result<(), Error> {
+        //! Check a password against the policy, returns the first
+        //! rule the password breaks
+        let length = password.chars().count();
+        if length < self.min_length || length > self.max_length {
+            return Err(format!(
+                "password length {} is outside {}..={}",
+                length, self.min_length, self.max_length
+            ));
+        }
+        for class in self.classes() {
+            if !password.chars().any(|c| class.contains(c)) {
+                return Err(format!("password has none of the characters: {}", class));
+            }
+        }
+        if self.no_dictionary_words {
+            if let Some(word) = self.dictionary_word(password) {
+                return Err(format!("password contains the dictionary word '{}'", word));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn password(policy: Option<&PasswordPolicy>) -> Result<String, Error> {
+    /*
+    Generate a password satisfying a policy
+    :param policy: password policy - `PasswordPolicy::default()` if not given
+    :return: password as str, or error if the policy can not be satisfied
+    */
+    let default_policy = PasswordPolicy::default();
+    let policy = policy.unwrap_or(&default_policy);
+
+    let classes = policy.classes();
+    if classes.is_empty() {
+        return Err(String::from("policy does not allow any characters"));
+    }
+    if policy.min_length > policy.max_length || policy.max_length < classes.len() {
+        return Err(format!(
+            "policy length {}..={} can not hold {} character classes",
+            policy.min_length,
+            policy.max_length,
+            classes.len()
+        ));
+    }
+    let all_chars: Vec<char> = classes.concat().chars().collect();
+
+    let mut trng = rand::thread_rng();
+    for _ in 0..1000 {
+        let length = trng.gen_range(policy.min_length.max(classes.len())..=policy.max_length);
+
+        // one of each required class, the rest from all of them
+        let mut chars: Vec<char> = classes
+            .iter()
+            .filter_map(|class| {
+                class
+                    .chars()
+                    .collect::<Vec<char>>()
+                    .choose(&mut trng)
+                    .copied()
+            })
+            .collect();
+        while chars.len() < length {
+            chars.push(*all_chars.choose(&mut trng).unwrap_or(&'x'));
+        }
+        chars.shuffle(&mut trng);
+
+        let candidate: String = chars.into_iter().collect();
+        if policy.validate(&candidate).is_ok() {
+            return Ok(candidate);
+        }
+    }
+    Err(String::from(
+        "could not generate a password satisfying the policy",
+    ))
+}
+
+pub fn passwords<'a>(
+    policy: Option<&'a PasswordPolicy>,
+) -> impl Iterator<Item = Result<String, Error>> + 'a {
+    /*
+    Endless stream of passwords satisfying a policy, see password
+    :return: iterator of passwords
+    */
+    std::iter::repeat_with(move || password(policy))
+}
+
+pub fn common_password() -> &'static str {
+    /*
+    Pick a password from a list of the most commonly used passwords
+    :return: password as str, eg: 123456, P@ssw0rd
+    */
+    COMMON_PASSWORDS
+        .choose(&mut rand::thread_rng())
+        .unwrap_or(&COMMON_PASSWORDS[0])
+}
+
+pub fn weak_password() -> String {
+    /*
+    Generate a deliberately weak password for audit testing, following the
+    patterns people actually use: common passwords, season/month + year,
+    company name + digits, dictionary word + digits, leetspeak words
+    :return: weak password as str, eg: Summer2021!, Acme123, Tr@in3r1
+    */
+    const SEASONS: &[&str] = &["Spring", "Summer", "Autumn", "Fall", "Winter"];
+    const MONTHS: &[&str] = &[
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    const ENDINGS: &[&str] = &["", "!", "1", "123", "@", "#"];
+
+    let mut trng = rand::thread_rng();
+    let year = trng.gen_range(2015..2026);
+    let ending = ENDINGS.choose(&mut trng).unwrap_or(&"");
+    let noun = ENG_NOUNS.choose(&mut trng).unwrap_or(&ENG_NOUNS[0]);
+
+    match trng.gen_range(0..6) {
+        0 => common_password().to_string(),
+        1 => format!(
+            "{}{}{}",
+            SEASONS.choose(&mut trng).unwrap_or(&SEASONS[0]),
+            year,
+            ending
+        ),
+        2 => format!(
+            "{}{}{}",
+            MONTHS.choose(&mut trng).unwrap_or(&MONTHS[0]),
+            year,
+            ending
+        ),
+        3 => format!(
+            "{}{}",
+            DUMMY_CORPS.choose(&mut trng).unwrap_or(&DUMMY_CORPS[0]),
+            ["123", "1", "2024", "!", "@1"]
+                .choose(&mut trng)
+                .unwrap_or(&"123")
+        ),
+        4 => format!("{}{}", capitalize(noun), trng.gen_range(1..10000)),
+        _ => {
+            let leet: String = capitalize(noun)
+                .chars()
+                .map(|c| match c {
+                    'a' => '@',
+                    'o' => '0',
+                    'e' => '3',
+                    's' => '$',
+                    'i' => '1',
+                    _ => c,
+                })
+                .collect();
+            format!("{}{}", leet, ending)
+        }
+    }
+}
+
+pub fn weak_passwords() -> impl Iterator<Item = String> {
+    /*
+    Endless stream of deliberately weak passwords, see weak_password
+    :return: iterator of weak passwords
+    */
+    std::iter::repeat_with(weak_password)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn crypt_salt(length: usize) -> String {
+    let salt_chars: Vec<char> = CRYPT_SALT_CHARS.chars().collect();
+    let mut trng = rand::thread_rng();
+    (0..length)
+        .map(|_| *salt_chars.choose(&mut trng).unwrap_or(&'.'))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashFormat {
+    /// MD4 of the UTF-16LE password, as stored by windows / AD
+    Ntlm,
+    /// `$2b$10$...`
+    Bcrypt,
+    /// `$6$salt$...`, the linux /etc/shadow default
+    Sha512Crypt,
+    /// `pbkdf2_sha256$260000$salt$...`, django style
+    Pbkdf2Sha256,
+}
+
+pub fn ntlm_hash(password: &str) -> String {
+    /*
+    NTLM (NT) hash of a password
+    :param password: plain text password
+    :return: lower case hex NT hash
+    */
+    let utf16: Vec<u8> = password
+        .encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect();
+    Md4::digest(&utf16)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn bcrypt_hash(password: &str, cost: Option<u32>) -> Result<String, Error> {
+    /*
+    bcrypt hash of a password with a random salt
+    :param password: plain text password
+    :param cost: log2 work factor (4..=31) - DEFAULT_BCRYPT_COST if not given
+    :return: `$2b$` modular crypt string
+    */
+    let cost = cost.unwrap_or(DEFAULT_BCRYPT_COST);
+    let salt: [u8; 16] = rand::random();
+    match bcrypt::hash_with_salt(password, cost, salt) {
+        Ok(parts) => Ok(parts.format_for_version(bcrypt::Version::TwoB)),
+        Err(e) => Err(format!("Error: {}", e)),
+    }
+}
+
+pub fn sha512_crypt_hash(password: &str, rounds: Option<usize>) -> Result<String, Error> {
+    /*
+    SHA-512 crypt hash of a password with a random salt
+    :param password: plain text password
+    :param rounds: number of rounds - DEFAULT_SHA512_CRYPT_ROUNDS if not given
+    :return: `$6$` modular crypt string, as found in /etc/shadow
+    */
+    let rounds = rounds.unwrap_or(DEFAULT_SHA512_CRYPT_ROUNDS);
+    let params = match sha_crypt::Sha512Params::new(rounds) {
+        Ok(p) => p,
+        Err(_) => return Err(format!("Error: invalid sha512 crypt rounds {}", rounds)),
+    };
+    let salt = crypt_salt(16);
+    let hash = match sha_crypt::sha512_crypt_b64(password.as_bytes(), salt.as_bytes(), &params) {
+        Ok(h) => h,
+        Err(_) => return Err(String::from("Error: sha512 crypt failed")),
+    };
+    if rounds == DEFAULT_SHA512_CRYPT_ROUNDS {
+        Ok(format!("$6${}${}", salt, hash))
+    } else {
+        Ok(format!("$6$rounds={}${}${}", rounds, salt, hash))
+    }
+}
+
+pub fn pbkdf2_sha256_hash(password: &str, iterations: Option<u32>) -> String {
+    /*
+    PBKDF2-HMAC-SHA256 hash of a password with a random salt
+    :param password: plain text password
+    :param iterations: number of iterations - DEFAULT_PBKDF2_ITERATIONS if not given
+    :return: django style `pbkdf2_sha256$<iterations>$<salt>$<base64 hash>`
+    */
+    let iterations = iterations.unwrap_or(DEFAULT_PBKDF2_ITERATIONS);
+    let salt: String = rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(22)
+        .map(char::from)
+        .collect();
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), iterations, &mut hash);
+    format!(
+        "pbkdf2_sha256${}${}${}",
+        iterations,
+        salt,
+        base64::engine::general_purpose::STANDARD.encode(hash)
+    )
+}
+
+pub fn hash_password(password: &str, format: HashFormat) -> Result<String, Error> {
+    /*
+    Hash a password in one of the common formats using default parameters
+    :param password: plain text password
+    :param format: hash format
+    :return: hash as str
+    */
+    match format {
+        HashFormat::Ntlm => Ok(ntlm_hash(password)),
+        HashFormat::Bcrypt => bcrypt_hash(password, None),
+        HashFormat::Sha512Crypt => sha512_crypt_hash(password, None),
+        HashFormat::Pbkdf2Sha256 => Ok(pbkdf2_sha256_hash(password, None)),
+    }
+}
+
+pub fn shadow_entry(username: &str, password_hash: &str, last_change: Option<u32>) -> String {
+    /*
+    Build an /etc/shadow line
+    :param username: login name
+    :param password_hash: crypt hash, eg: from `sha512_crypt_hash`
+    :param last_change: days since epoch of the last password change - random if not given
+    :return: eg: jsmith:$6$...:19000:0:99999:7:::
+    */
+    let last_change = last_change.unwrap_or_else(|| rand::thread_rng().gen_range(16000..20000));
+    format!(
+        "{}:{}:{}:0:99999:7:::",
+        username, password_hash, last_change
+    )
+}
+
+pub fn pwdump_entry(username: &str, rid: u32, password: &str) -> String {
+    /*
+    Build a pwdump / secretsdump style line of an AD account
+    :param username: account name, eg: jsmith or CORP\jsmith
+    :param rid: relative id of the account, eg: 1104
+    :param password: plain text password to derive the NT hash from
+    :return: eg: jsmith:1104:aad3b435b51404eeaad3b435b51404ee:<nt hash>:::
+    */
+    format!(
+        "{}:{}:{}:{}:::",
+        username,
+        rid,
+        EMPTY_LM_HASH,
+        ntlm_hash(password)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_policy() {
+        let policy = PasswordPolicy::default();
+        for _ in 0..50 {
+            let pwd = password(Some(&policy)).unwrap();
+            assert!(policy.validate(&pwd).is_ok());
+        }
+
+        let digits_only = PasswordPolicy {
+            min_length: 6,
+            max_length: 6,
+            lowercase: false,
+            uppercase: false,
+            digits: true,
+            symbols: false,
+            no_dictionary_words: false,
+            min_word_length: 4,
+        };
+        let pin = password(Some(&digits_only)).unwrap();
+        assert_eq!(pin.len(), 6);
+        assert!(pin.chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let policy = PasswordPolicy::default();
+        assert!(policy.validate("Short1!").is_err());
+        assert!(policy.validate("Xq7!pianoZk2#").is_err());
+        assert!(policy.validate("Xq7!pZk2#mWq").is_ok());
+
+        let impossible = PasswordPolicy {
+            lowercase: false,
+            uppercase: false,
+            digits: false,
+            symbols: false,
+            ..PasswordPolicy::default()
+        };
+        assert!(password(Some(&impossible)).is_err());
+    }
+
+    #[test]
+    fn test_ntlm_hash() {
+        assert_eq!(ntlm_hash("password"), "8846f7eaee8fb117ad06bdd830b7586c");
+        assert_eq!(
+            pwdump_entry("jsmith", 1104, "password"),
+            "jsmith:1104:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c:::"
+        );
+    }
+
+    #[test]
+    fn test_crypt_hashes() {
+        let hash = sha512_crypt_hash("password", None).unwrap();
+        assert!(hash.starts_with("$6$"));
+        assert_eq!(hash.split('$').count(), 4);
+
+        let hash = bcrypt_hash("password", Some(4)).unwrap();
+        assert!(hash.starts_with("$2b$04$"));
+        assert!(bcrypt::verify("password", &hash).unwrap());
+
+        let hash = pbkdf2_sha256_hash("password", Some(1000));
+        assert!(hash.starts_with("pbkdf2_sha256$1000$"));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let policy = PasswordPolicy::default();
+        assert!(passwords(Some(&policy))
+            .take(10)
+            .all(|p| policy.validate(&p.unwrap()).is_ok()));
+        assert!(weak_passwords().take(10).all(|p| !p.is_empty()));
+    }
+}
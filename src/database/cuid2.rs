@@ -0,0 +1,165 @@
+/*
+CUID2 generators
+Collision resistant ids: a random letter followed by the base36
+SHA3-512 hash of the time, some entropy, a counter & a fingerprint
+of the generating process
+*/
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use sha3::{Digest, Sha3_512};
+
+use super::encode_base;
+
+type Error = String;
+
+const BASE36_ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const DEFAULT_LENGTH: usize = 24;
+const MAX_LENGTH: usize = 32;
+const FINGERPRINT_LENGTH: usize = 32;
+/// counters start at a random value below this
+const INITIAL_COUNT_MAX: u64 = 476_782_367;
+
+fn base36(number: u64) -> String {
+    match number {
+        0 => String::from("0"),
+        _ => encode_base(&number.to_be_bytes(), BASE36_ALPHABET),
+    }
+}
+
+fn entropy<R: Rng + ?Sized>(rng: &mut R, length: usize) -> String {
+    (0..length)
+        .map(|_| BASE36_ALPHABET[rng.gen_range(0..36)] as char)
+        .collect()
+}
+
+fn hash(input: &str) -> String {
+    // the first character is biased, so it is dropped
+    let digest = Sha3_512::digest(input.as_bytes());
+    encode_base(&digest, BASE36_ALPHABET)
+        .chars()
+        .skip(1)
+        .collect()
+}
+
+/// Generates CUID2s of one process
+///
+/// ## Example
+/// ```rust
+/// use random::database::Cuid2Generator;
+///
+/// let mut generator = Cuid2Generator::new(None).unwrap();
+/// let id = generator.cuid2();
+/// assert_eq!(id.len(), 24);
+/// assert!(id.starts_with(|c: char| c.is_ascii_lowercase()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cuid2Generator {
+    length: usize,
+    counter: u64,
+    fingerprint: String,
+}
+
+impl Cuid2Generator {
+    pub fn new(length: Option<usize>) -> Result<Self, Error> {
+        //! New generator of `length` (2 to 32, 24 if not given) character ids
+        Cuid2Generator::with_rng(&mut rand::thread_rng(), length)
+    }
+
+    pub(crate) fn with_rng<R: Rng + ?Sized>(
+        rng: &mut R,
+        length: Option<usize>,
+    ) -> Result<Self, Error> {
+        let length = length.unwrap_or(DEFAULT_LENGTH);
+        if !(2..=MAX_LENGTH).contains(&length) {
+            return Err(format!(
+                "Error: a cuid2 has 2 to {} characters, not {}",
+                MAX_LENGTH, length
+            ));
+        }
+        let fingerprint = hash(&entropy(rng, FINGERPRINT_LENGTH))
+            .chars()
+            .take(FINGERPRINT_LENGTH)
+            .collect();
+        Ok(Cuid2Generator {
+            length,
+            counter: rng.gen_range(0..INITIAL_COUNT_MAX),
+            fingerprint,
+        })
+    }
+
+    pub(crate) fn cuid2_with<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        time: &DateTime<Utc>,
+    ) -> String {
+        let first_letter = BASE36_ALPHABET[rng.gen_range(10..36)] as char;
+        let input = format!(
+            "{}{}{}{}",
+            base36(time.timestamp_millis().max(0) as u64),
+            entropy(rng, self.length),
+            base36(self.counter),
+            self.fingerprint
+        );
+        self.counter += 1;
+        let hashed: String = hash(&input).chars().skip(1).take(self.length - 1).collect();
+        format!("{}{}", first_letter, hashed)
+    }
+
+    pub fn cuid2(&mut self) -> String {
+        //! Id for the current time
+        self.cuid2_with(&mut rand::thread_rng(), &Utc::now())
+    }
+
+    pub fn cuid2_at(&mut self, time: &DateTime<Utc>) -> String {
+        //! Id made at the given time
+        self.cuid2_with(&mut rand::thread_rng(), time)
+    }
+}
+
+pub fn cuid2() -> String {
+    /*
+    Generate a CUID2
+    :return: 24 characters, eg: tz4a98xxat96iws9zmbrgj3a
+    */
+    cuid2_with_time(&Utc::now())
+}
+
+pub fn cuid2_with_time(time: &DateTime<Utc>) -> String {
+    /*
+    Generate a CUID2 made at the given time
+    :param time: eg: a time of GenerateTime
+    :return: 24 characters
+    */
+    let mut trng = rand::thread_rng();
+    match Cuid2Generator::with_rng(&mut trng, None) {
+        Ok(mut generator) => generator.cuid2_with(&mut trng, time),
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_cuid2() {
+        let mut generator = Cuid2Generator::new(Some(10)).unwrap();
+        let ids = (0..1000)
+            .map(|_| generator.cuid2())
+            .collect::<HashSet<String>>();
+        assert_eq!(ids.len(), 1000);
+        for id in &ids {
+            assert_eq!(id.len(), 10);
+            assert!(id.starts_with(|c: char| c.is_ascii_lowercase()));
+            assert!(id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+        }
+        assert_eq!(cuid2().len(), DEFAULT_LENGTH);
+        assert_eq!(Cuid2Generator::new(Some(32)).unwrap().cuid2().len(), 32);
+        assert!(Cuid2Generator::new(Some(33)).is_err());
+        assert!(Cuid2Generator::new(Some(1)).is_err());
+    }
+}
@@ -0,0 +1,417 @@
+/*
+Relational dataset generators
+Several tables generated together: foreign keys only refer to rows
+that exist, child tables get a number of rows per parent row and
+tables come out in the order they can be inserted in
+*/
+
+use std::collections::HashSet;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Poisson};
+
+use super::schema::{KeyValues, Row, Table, Value};
+use super::sql::Dialect;
+
+type Error = String;
+
+/// Number of rows of a child table per row of its parent table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cardinality {
+    Exactly(usize),
+    /// uniformly between the two, both included
+    Between(usize, usize),
+    /// poisson distributed with the given mean
+    Poisson(f64),
+}
+
+impl Cardinality {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<usize, Error> {
+        match self {
+            Cardinality::Exactly(n) => Ok(*n),
+            Cardinality::Between(min, max) if min <= max => Ok(rng.gen_range(*min..=*max)),
+            // a poisson distribution needs a positive mean, but no rows on average is fine
+            Cardinality::Poisson(mean) if *mean == 0.0 => Ok(0),
+            Cardinality::Poisson(mean) => {
+                let poisson = Poisson::new(*mean)
+                    .map_err(|e| format!("Error: poisson mean {}: {}", mean, e))?;
+                Ok(poisson.sample(rng) as usize)
+            }
+            cardinality => Err(format!("Error: invalid cardinality {:?}", cardinality)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Rows {
+    Count(usize),
+    PerParent {
+        column: String,
+        cardinality: Cardinality,
+    },
+}
+
+/// A generated table along with its rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableRows {
+    pub table: Table,
+    pub rows: Vec<Row>,
+}
+
+/// Tables to generate together; the same seed gives the same data
+///
+/// ## Example
+/// ```rust
+/// use random::database::{Cardinality, Column, ColumnType, Dataset, Dialect, Table};
+///
+/// let customers = Table::new(
+///     "customers",
+///     vec![
+///         Column::new("id", ColumnType::Serial).primary_key(),
+///         Column::new("name", ColumnType::FullName),
+///     ],
+/// )
+/// .unwrap();
+/// let orders = Table::new(
+///     "orders",
+///     vec![
+///         Column::new("id", ColumnType::Uuid).primary_key(),
+///         Column::new("customer_id", ColumnType::Serial).references("customers", "id"),
+///         Column::new("total", ColumnType::Decimal { precision: 8, scale: 2 }),
+///     ],
+/// )
+/// .unwrap();
+///
+/// let mut dataset = Dataset::new(Some(42));
+/// dataset
+///     .children(orders, "customer_id", Cardinality::Poisson(3.0))
+///     .table(customers, 50);
+/// // customers come first, whatever order the tables were added in
+/// let tables = dataset.generate().unwrap();
+/// assert_eq!(tables[0].table.name, "customers");
+/// println!("{}", dataset.sql(Dialect::Postgres).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    seed: Option<u64>,
+    tables: Vec<(Table, Rows)>,
+}
+
+impl Dataset {
+    pub fn new(seed: Option<u64>) -> Self {
+        //! New dataset; random every time when no seed is given.
+        //! With a seed, everything but ULIDs (which carry the
+        //! time they are made at) is the same on every run
+        Dataset {
+            seed,
+            tables: Vec::new(),
+        }
+    }
+
+    pub fn table(&mut self, table: Table, rows: usize) -> &mut Self {
+        //! Add a table with a fixed number of rows
+        self.tables.push((table, Rows::Count(rows)));
+        self
+    }
+
+    pub fn children(&mut self, table: Table, column: &str, cardinality: Cardinality) -> &mut Self {
+        //! Add a child table: `cardinality` rows of it for every row
+        //! of the table the foreign key `column` refers to
+        self.tables.push((
+            table,
+            Rows::PerParent {
+                column: column.to_string(),
+                cardinality,
+            },
+        ));
+        self
+    }
+
+    fn insert_order(&self) -> Result<Vec<usize>, Error> {
+        // topological sort; ties keep the order the tables were added in
+        let names = self
+            .tables
+            .iter()
+            .map(|(t, _)| t.name.as_str())
+            .collect::<Vec<&str>>();
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(format!("Error: '{}' is in the dataset twice", name));
+            }
+        }
+        let mut dependencies = Vec::new();
+        for (table, _) in &self.tables {
+            let mut parents = HashSet::new();
+            for foreign_key in table.columns.iter().filter_map(|c| c.references.as_ref()) {
+                if foreign_key.table == table.name {
+                    return Err(format!(
+                        "Error: '{}' refers to itself, which a dataset cannot generate",
+                        table.name
+                    ));
+                }
+                match names.iter().position(|n| *n == foreign_key.table) {
+                    Some(parent) => {
+                        parents.insert(parent);
+                    }
+                    None => {
+                        return Err(format!(
+                            "Error: '{}' refers to '{}' which is not in the dataset",
+                            table.name, foreign_key.table
+                        ))
+                    }
+                }
+            }
+            dependencies.push(parents);
+        }
+
+        let mut order = Vec::with_capacity(self.tables.len());
+        while order.len() < self.tables.len() {
+            let next = (0..self.tables.len())
+                .find(|i| !order.contains(i) && dependencies[*i].iter().all(|p| order.contains(p)));
+            match next {
+                Some(i) => order.push(i),
+                None => {
+                    let cycle = (0..self.tables.len())
+                        .filter(|i| !order.contains(i))
+                        .map(|i| names[i])
+                        .collect::<Vec<&str>>();
+                    return Err(format!(
+                        "Error: foreign keys of {} form a cycle",
+                        cycle.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    pub fn generate(&self) -> Result<Vec<TableRows>, Error> {
+        //! Generate every table, in insert order: a table
+        //! comes after all the tables it refers to
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut generated: Vec<TableRows> = Vec::with_capacity(self.tables.len());
+        let mut keys = KeyValues::new();
+
+        for index in self.insert_order()? {
+            let (table, rows) = &self.tables[index];
+            // the values every foreign key of the table may take
+            for foreign_key in table.columns.iter().filter_map(|c| c.references.as_ref()) {
+                if let Some(parent) = generated.iter().find(|g| g.table.name == foreign_key.table) {
+                    let values = parent
+                        .table
+                        .column_values(&parent.rows, &foreign_key.column)
+                        .ok_or_else(|| {
+                            format!("Error: '{}' has no such column", foreign_key.key())
+                        })?;
+                    keys.insert(
+                        foreign_key.key(),
+                        values.into_iter().filter(|v| *v != Value::Null).collect(),
+                    );
+                }
+            }
+
+            let mut state = table.state();
+            let mut table_rows = Vec::new();
+            match rows {
+                Rows::Count(count) => {
+                    for _ in 0..*count {
+                        table_rows.push(table.row_with(&mut rng, &mut state, Some(&keys), &[])?);
+                    }
+                }
+                Rows::PerParent {
+                    column,
+                    cardinality,
+                } => {
+                    let column_index = table.column_index(column).ok_or_else(|| {
+                        format!("Error: '{}' has no column '{}'", table.name, column)
+                    })?;
+                    let foreign_key =
+                        table.columns[column_index]
+                            .references
+                            .as_ref()
+                            .ok_or_else(|| {
+                                format!("Error: '{}.{}' is not a foreign key", table.name, column)
+                            })?;
+                    let parent_keys = keys.get(&foreign_key.key()).cloned().unwrap_or_default();
+                    for parent_key in parent_keys {
+                        for _ in 0..cardinality.sample(&mut rng)? {
+                            table_rows.push(table.row_with(
+                                &mut rng,
+                                &mut state,
+                                Some(&keys),
+                                &[(column_index, parent_key.clone())],
+                            )?);
+                        }
+                    }
+                }
+            }
+            generated.push(TableRows {
+                table: table.clone(),
+                rows: table_rows,
+            });
+        }
+        Ok(generated)
+    }
+
+    pub fn sql(&self, dialect: Dialect) -> Result<String, Error> {
+        //! Generate every table as one SQL script: all the
+        //! CREATE TABLE statements, then all the INSERTs, each table's
+        //! followed by those advancing its serial sequences
+        let tables = self.generate()?;
+        let mut script = String::new();
+        for generated in &tables {
+            script.push_str(&dialect.create_table(&generated.table));
+            script.push('\n');
+        }
+        for generated in &tables {
+            if !generated.rows.is_empty() {
+                script.push_str(&dialect.insert(&generated.table, &generated.rows, None));
+                script.push('\n');
+            }
+        }
+        Ok(script)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::{Column, ColumnType};
+
+    fn shop() -> Dataset {
+        let customers = Table::new(
+            "customers",
+            vec![
+                Column::new("id", ColumnType::Serial).primary_key(),
+                Column::new("email", ColumnType::Email).unique(),
+            ],
+        )
+        .unwrap();
+        let products = Table::new(
+            "products",
+            vec![
+                Column::new("sku", ColumnType::Uuid).primary_key(),
+                Column::new("name", ColumnType::Text(Some(40))),
+            ],
+        )
+        .unwrap();
+        let orders = Table::new(
+            "orders",
+            vec![
+                Column::new("id", ColumnType::Serial).primary_key(),
+                Column::new("customer_id", ColumnType::Serial).references("customers", "id"),
+            ],
+        )
+        .unwrap();
+        let items = Table::new(
+            "order_items",
+            vec![
+                Column::new("order_id", ColumnType::Serial).references("orders", "id"),
+                Column::new("sku", ColumnType::Uuid).references("products", "sku"),
+                Column::new("quantity", ColumnType::Integer { min: 1, max: 5 }),
+            ],
+        )
+        .unwrap();
+
+        let mut dataset = Dataset::new(Some(7));
+        dataset
+            .children(items, "order_id", Cardinality::Between(1, 4))
+            .children(orders, "customer_id", Cardinality::Poisson(2.0))
+            .table(products, 20)
+            .table(customers, 30);
+        dataset
+    }
+
+    #[test]
+    fn test_referential_integrity() {
+        let tables = shop().generate().unwrap();
+        let names = tables
+            .iter()
+            .map(|t| t.table.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, ["products", "customers", "orders", "order_items"]);
+
+        let keys = |table: &TableRows, column: &str| {
+            table
+                .table
+                .column_values(&table.rows, column)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.to_string())
+                .collect::<HashSet<String>>()
+        };
+        let (products, customers, orders, items) = (&tables[0], &tables[1], &tables[2], &tables[3]);
+        assert_eq!(products.rows.len(), 20);
+        assert_eq!(customers.rows.len(), 30);
+        assert!(keys(orders, "customer_id").is_subset(&keys(customers, "id")));
+        assert!(keys(items, "order_id").is_subset(&keys(orders, "id")));
+        assert!(keys(items, "sku").is_subset(&keys(products, "sku")));
+        // every order has between 1 & 4 items
+        assert_eq!(keys(items, "order_id"), keys(orders, "id"));
+        assert!(items.rows.len() >= orders.rows.len() && items.rows.len() <= 4 * orders.rows.len());
+
+        // same seed, same data
+        assert_eq!(shop().generate().unwrap(), tables);
+        assert!(shop()
+            .sql(Dialect::Sqlite)
+            .unwrap()
+            .starts_with("CREATE TABLE \"products\""));
+    }
+
+    #[test]
+    fn test_invalid_datasets() {
+        let table = |name: &str, parent: &str| {
+            Table::new(
+                name,
+                vec![
+                    Column::new("id", ColumnType::Serial).primary_key(),
+                    Column::new("parent_id", ColumnType::Serial).references(parent, "id"),
+                ],
+            )
+            .unwrap()
+        };
+        let mut cycle = Dataset::new(None);
+        cycle.table(table("a", "b"), 1).table(table("b", "a"), 1);
+        assert!(cycle.generate().is_err());
+
+        let mut missing = Dataset::new(None);
+        missing.table(table("a", "z"), 1);
+        assert!(missing.generate().is_err());
+
+        let mut itself = Dataset::new(None);
+        itself.table(table("a", "a"), 1);
+        assert!(itself.generate().is_err());
+
+        let root = Table::new(
+            "a",
+            vec![Column::new("id", ColumnType::Serial).primary_key()],
+        )
+        .unwrap();
+        for mean in [-1.0, f64::NAN] {
+            let mut invalid = Dataset::new(None);
+            invalid.table(root.clone(), 1).children(
+                table("b", "a"),
+                "parent_id",
+                Cardinality::Poisson(mean),
+            );
+            assert!(invalid.generate().is_err());
+        }
+        let mut empty = Dataset::new(None);
+        empty.table(root.clone(), 3).children(
+            table("b", "a"),
+            "parent_id",
+            Cardinality::Poisson(0.0),
+        );
+        let tables = empty.generate().unwrap();
+        assert_eq!(tables[0].rows.len(), 3);
+        assert!(tables[1].rows.is_empty());
+
+        let mut twice = Dataset::new(None);
+        twice.table(root.clone(), 1).table(root, 1);
+        assert!(twice.generate().is_err());
+    }
+}
@@ -0,0 +1,792 @@
+/*
+MongoDB document generators
+Documents with nested objects & arrays generated from a template
+in the style of MongoDB's $jsonSchema validator, written as
+(relaxed) Extended JSON for mongoimport or as raw BSON
+
+A template is a JSON schema of a document, eg:
+{
+  "bsonType": "object",
+  "required": ["_id", "name", "created"],
+  "properties": {
+    "_id": { "bsonType": "objectId" },
+    "name": { "type": "string", "format": "full-name" },
+    "age": { "bsonType": "int", "minimum": 18, "maximum": 90 },
+    "created": { "bsonType": "date", "minimum": "2020-01-01T00:00:00Z" },
+    "tags": { "type": "array", "items": { "enum": ["a", "b"] }, "maxItems": 3 }
+  }
+}
+
+- `type` / `bsonType`: object, array, string, int, long, integer, double,
+  number, bool / boolean, null, objectId, date; a list picks one of them
+- `properties` & `required`: the fields of an object, in order; fields
+  not required are left out of 1 in 5 documents
+- `items`, `minItems` (0) & `maxItems` (5): the elements of an array
+- `minimum` & `maximum`: bounds of numbers (0 to 1000) and of dates
+  (RFC 3339 or YYYY-MM-DD; the last 365 days)
+- `enum` & `const`: the values a field takes
+- `format` of strings: email, uuid, ulid, ipv4, mac, date, date-time,
+  first-name, last-name, full-name, username, phone, company; words
+  of up to `maxLength` (255) characters otherwise
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, SecondsFormat, TimeZone, Utc};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde_json::Value as Json;
+
+use super::objectid::ObjectId;
+use super::schema::{generate_value, ColumnType, Value};
+use crate::names::PersonName;
+
+type Error = String;
+
+/// share of the documents that leave out a field which is not required
+const OPTIONAL_ABSENT_PROBABILITY: f64 = 0.2;
+const DEFAULT_MAX_ITEMS: usize = 5;
+const DEFAULT_MAX_NUMBER: f64 = 1000.0;
+
+/// A BSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bson {
+    Null,
+    Boolean(bool),
+    Int32(i32),
+    Int64(i64),
+    Double(f64),
+    String(String),
+    ObjectId(ObjectId),
+    /// milliseconds since the unix epoch
+    DateTime(i64),
+    Array(Vec<Bson>),
+    Document(Document),
+}
+
+/// A BSON document: fields in the order they were inserted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    fields: Vec<(String, Bson)>,
+}
+
+impl Bson {
+    fn element_type(&self) -> u8 {
+        match self {
+            Bson::Double(_) => 0x01,
+            Bson::String(_) => 0x02,
+            Bson::Document(_) => 0x03,
+            Bson::Array(_) => 0x04,
+            Bson::ObjectId(_) => 0x07,
+            Bson::Boolean(_) => 0x08,
+            Bson::DateTime(_) => 0x09,
+            Bson::Null => 0x0A,
+            Bson::Int32(_) => 0x10,
+            Bson::Int64(_) => 0x12,
+        }
+    }
+
+    fn write_bson(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Bson::Null => {}
+            Bson::Boolean(x) => buffer.push(*x as u8),
+            Bson::Int32(x) => buffer.extend_from_slice(&x.to_le_bytes()),
+            Bson::Int64(x) | Bson::DateTime(x) => buffer.extend_from_slice(&x.to_le_bytes()),
+            Bson::Double(x) => buffer.extend_from_slice(&x.to_le_bytes()),
+            Bson::String(x) => {
+                buffer.extend_from_slice(&(x.len() as i32 + 1).to_le_bytes());
+                buffer.extend_from_slice(x.as_bytes());
+                buffer.push(0);
+            }
+            Bson::ObjectId(x) => buffer.extend_from_slice(&x.bytes()),
+            Bson::Array(values) => write_elements(
+                buffer,
+                values.iter().enumerate().map(|(i, v)| (i.to_string(), v)),
+            ),
+            Bson::Document(document) => write_elements(
+                buffer,
+                document.fields.iter().map(|(k, v)| (k.to_string(), v)),
+            ),
+        }
+    }
+
+    pub fn to_extended_json(&self) -> String {
+        //! Relaxed Extended JSON (v2) of the value
+        match self {
+            Bson::Null => String::from("null"),
+            Bson::Boolean(x) => x.to_string(),
+            Bson::Int32(x) => x.to_string(),
+            Bson::Int64(x) => x.to_string(),
+            Bson::Double(x) if x.is_nan() => String::from("{\"$numberDouble\":\"NaN\"}"),
+            Bson::Double(x) if x.is_infinite() => format!(
+                "{{\"$numberDouble\":\"{}Infinity\"}}",
+                if *x < 0.0 { "-" } else { "" }
+            ),
+            // a double keeps its decimal point, eg: 1.0
+            Bson::Double(x) => format!("{:?}", x),
+            Bson::String(x) => json_string(x),
+            Bson::ObjectId(x) => format!("{{\"$oid\":\"{}\"}}", x),
+            Bson::DateTime(millis) => match Utc.timestamp_millis_opt(*millis).single() {
+                // ISO 8601 only for the years 1970 to 9999
+                Some(time) if *millis >= 0 && time.year() <= 9999 => {
+                    format!(
+                        "{{\"$date\":\"{}\"}}",
+                        time.to_rfc3339_opts(SecondsFormat::Millis, true)
+                    )
+                }
+                _ => format!("{{\"$date\":{{\"$numberLong\":\"{}\"}}}}", millis),
+            },
+            Bson::Array(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|v| v.to_extended_json())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            Bson::Document(document) => document.to_extended_json(),
+        }
+    }
+}
+
+impl From<Value> for Bson {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => Bson::Null,
+            Value::Boolean(x) => Bson::Boolean(x),
+            Value::Integer(x) => Bson::Int64(x),
+            Value::Float(x) => Bson::Double(x),
+            Value::Timestamp(x) => Bson::DateTime(x.timestamp_millis()),
+            value => Bson::String(value.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Bson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_extended_json())
+    }
+}
+
+fn json_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_else(|_| String::from("\"\""))
+}
+
+fn write_elements<'a>(buffer: &mut Vec<u8>, elements: impl Iterator<Item = (String, &'a Bson)>) {
+    // int32 size, the elements & a trailing 0; the size includes itself
+    let start = buffer.len();
+    buffer.extend_from_slice(&[0; 4]);
+    for (key, value) in elements {
+        buffer.push(value.element_type());
+        buffer.extend_from_slice(key.as_bytes());
+        buffer.push(0);
+        value.write_bson(buffer);
+    }
+    buffer.push(0);
+    let size = (buffer.len() - start) as i32;
+    buffer[start..start + 4].copy_from_slice(&size.to_le_bytes());
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Document::default()
+    }
+
+    pub fn insert(&mut self, key: &str, value: Bson) -> Result<(), Error> {
+        //! Set a field: replaced in place when it exists, added last otherwise.
+        //! Keys cannot hold NUL characters, which end keys in BSON
+        if key.contains('\0') {
+            return Err(format!("Error: invalid key {:?}", key));
+        }
+        match self.fields.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.fields.push((key.to_string(), value)),
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Bson> {
+        self.fields.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|(k, _)| k.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Bson)> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn to_extended_json(&self) -> String {
+        //! Relaxed Extended JSON (v2) of the document, on one line
+        format!(
+            "{{{}}}",
+            self.fields
+                .iter()
+                .map(|(k, v)| format!("{}:{}", json_string(k), v.to_extended_json()))
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+
+    pub fn to_bson(&self) -> Vec<u8> {
+        //! The document as BSON bytes
+        let mut buffer = Vec::new();
+        Bson::Document(self.clone()).write_bson(&mut buffer);
+        buffer
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_extended_json())
+    }
+}
+
+/// A field of a template, checked & with its defaults filled in
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Object(Vec<(String, Node, bool)>),
+    Array {
+        items: Box<Node>,
+        min: usize,
+        max: usize,
+    },
+    Int32(i32, i32),
+    Int64(i64, i64),
+    Double(f64, f64),
+    Boolean,
+    Null,
+    ObjectId,
+    /// milliseconds since the unix epoch
+    DateTime(i64, i64),
+    DateTimeString(i64, i64),
+    /// values of the generators of the table columns
+    Generated(ColumnType),
+    OneOf(Vec<Node>),
+    Const(Bson),
+}
+
+fn json_to_bson(json: &Json) -> Result<Bson, Error> {
+    Ok(match json {
+        Json::Null => Bson::Null,
+        Json::Bool(x) => Bson::Boolean(*x),
+        Json::Number(x) => match (x.as_i64(), x.as_f64()) {
+            (Some(x), _) => i32::try_from(x).map(Bson::Int32).unwrap_or(Bson::Int64(x)),
+            (None, Some(x)) => Bson::Double(x),
+            _ => return Err(format!("Error: {} is out of range", x)),
+        },
+        Json::String(x) => Bson::String(x.clone()),
+        Json::Array(values) => Bson::Array(
+            values
+                .iter()
+                .map(json_to_bson)
+                .collect::<Result<Vec<Bson>, Error>>()?,
+        ),
+        Json::Object(fields) => {
+            let mut document = Document::new();
+            for (key, value) in fields {
+                document.insert(key, json_to_bson(value)?)?;
+            }
+            Bson::Document(document)
+        }
+    })
+}
+
+fn parse_time(json: &Json, path: &str) -> Result<DateTime<Utc>, Error> {
+    let text = json
+        .as_str()
+        .ok_or_else(|| format!("Error: {}: a date bound is a string", path))?;
+    DateTime::parse_from_rfc3339(text)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .map(|d| Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).unwrap_or_default()))
+        })
+        .map_err(|_| format!("Error: {}: invalid date '{}'", path, text))
+}
+
+fn parse_node(json: &Json, path: &str) -> Result<Node, Error> {
+    let schema = json
+        .as_object()
+        .ok_or_else(|| format!("Error: {}: a schema is an object", path))?;
+    if let Some(value) = schema.get("const") {
+        return Ok(Node::Const(json_to_bson(value)?));
+    }
+    if let Some(values) = schema.get("enum") {
+        let values = values
+            .as_array()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| format!("Error: {}: enum is a list of values", path))?;
+        return Ok(Node::OneOf(
+            values
+                .iter()
+                .map(|v| json_to_bson(v).map(Node::Const))
+                .collect::<Result<Vec<Node>, Error>>()?,
+        ));
+    }
+    let kind = schema
+        .get("bsonType")
+        .or_else(|| schema.get("type"))
+        .or_else(|| schema.get("properties").map(|_| &Json::Null))
+        .ok_or_else(|| format!("Error: {}: no type", path))?;
+    match kind {
+        Json::Array(kinds) if !kinds.is_empty() => {
+            let mut nodes = Vec::new();
+            for kind in kinds {
+                let mut schema = schema.clone();
+                schema.remove("bsonType");
+                schema.insert(String::from("type"), kind.clone());
+                nodes.push(parse_node(&Json::Object(schema), path)?);
+            }
+            Ok(Node::OneOf(nodes))
+        }
+        // properties without a type: an object
+        Json::Null => parse_typed(schema, "object", path),
+        Json::String(kind) => parse_typed(schema, kind, path),
+        _ => Err(format!("Error: {}: invalid type {}", path, kind)),
+    }
+}
+
+fn parse_typed(
+    schema: &serde_json::Map<String, Json>,
+    kind: &str,
+    path: &str,
+) -> Result<Node, Error> {
+    let number = |key: &str, default: f64| -> Result<f64, Error> {
+        match schema.get(key) {
+            Some(value) => value
+                .as_f64()
+                .ok_or_else(|| format!("Error: {}: {} is a number", path, key)),
+            None => Ok(default),
+        }
+    };
+    let count = |key: &str| -> Result<Option<usize>, Error> {
+        match schema.get(key) {
+            Some(value) => value
+                .as_u64()
+                .map(|x| Some(x as usize))
+                .ok_or_else(|| format!("Error: {}: {} is a count", path, key)),
+            None => Ok(None),
+        }
+    };
+    let bounds = |kind: &str| -> Result<(f64, f64), Error> {
+        let (min, max) = (
+            number("minimum", 0.0)?,
+            number("maximum", DEFAULT_MAX_NUMBER)?,
+        );
+        if min > max {
+            return Err(format!("Error: {}: empty {} range", path, kind));
+        }
+        Ok((min, max))
+    };
+    let whole_bounds = |kind: &str| -> Result<(f64, f64), Error> {
+        // fractional bounds rounded inwards, eg: 1.5 to 1.7 has no int
+        let (min, max) = bounds(kind)?;
+        let (min, max) = (min.ceil(), max.floor());
+        if min > max {
+            return Err(format!("Error: {}: empty {} range", path, kind));
+        }
+        Ok((min, max))
+    };
+    let times = || -> Result<(i64, i64), Error> {
+        let end = match schema.get("maximum") {
+            Some(json) => parse_time(json, path)?,
+            None => Utc::now(),
+        };
+        let start = match schema.get("minimum") {
+            Some(json) => parse_time(json, path)?,
+            None => end - Duration::days(365),
+        };
+        if start > end {
+            return Err(format!("Error: {}: empty date range", path));
+        }
+        Ok((start.timestamp_millis(), end.timestamp_millis()))
+    };
+
+    Ok(match kind {
+        "object" => {
+            let required = match schema.get("required") {
+                Some(required) => Some(
+                    required
+                        .as_array()
+                        .ok_or_else(|| format!("Error: {}: required is a list", path))?
+                        .iter()
+                        .filter_map(|r| r.as_str())
+                        .collect::<Vec<&str>>(),
+                ),
+                None => None,
+            };
+            let mut fields = Vec::new();
+            if let Some(properties) = schema.get("properties") {
+                let properties = properties
+                    .as_object()
+                    .ok_or_else(|| format!("Error: {}: properties is an object", path))?;
+                for (name, property) in properties {
+                    if name.contains('\0') {
+                        return Err(format!("Error: {}: invalid field name {:?}", path, name));
+                    }
+                    let node = parse_node(property, &format!("{}.{}", path, name))?;
+                    // without a required list every field is
+                    let is_required = required.as_ref().is_none_or(|r| r.contains(&name.as_str()));
+                    fields.push((name.clone(), node, is_required));
+                }
+            }
+            Node::Object(fields)
+        }
+        "array" => {
+            let items = match schema.get("items") {
+                Some(items) => parse_node(items, &format!("{}[]", path))?,
+                None => return Err(format!("Error: {}: an array needs items", path)),
+            };
+            let min = count("minItems")?.unwrap_or(0);
+            let max = count("maxItems")?.unwrap_or_else(|| min.max(DEFAULT_MAX_ITEMS));
+            if min > max {
+                return Err(format!("Error: {}: minItems is above maxItems", path));
+            }
+            Node::Array {
+                items: Box::new(items),
+                min,
+                max,
+            }
+        }
+        "int" => {
+            let (min, max) = whole_bounds(kind)?;
+            if min < f64::from(i32::MIN) || max > f64::from(i32::MAX) {
+                return Err(format!("Error: {}: out of range of an int", path));
+            }
+            Node::Int32(min as i32, max as i32)
+        }
+        "long" | "integer" => {
+            let (min, max) = whole_bounds(kind)?;
+            let (min, max) = (min as i64, max as i64);
+            // integers that fit are ints, like the shell & drivers make them
+            match (i32::try_from(min), i32::try_from(max)) {
+                (Ok(min), Ok(max)) if kind == "integer" => Node::Int32(min, max),
+                _ => Node::Int64(min, max),
+            }
+        }
+        "double" | "number" => {
+            let (min, max) = bounds(kind)?;
+            Node::Double(min, max)
+        }
+        "bool" | "boolean" => Node::Boolean,
+        "null" => Node::Null,
+        "objectId" => Node::ObjectId,
+        "date" => {
+            let (start, end) = times()?;
+            Node::DateTime(start, end)
+        }
+        "string" => {
+            let format = schema.get("format").and_then(|f| f.as_str()).unwrap_or("");
+            match format {
+                "date" => {
+                    let (start, end) = times()?;
+                    let date = |millis: i64| {
+                        Utc.timestamp_millis_opt(millis)
+                            .single()
+                            .map(|t| t.date_naive())
+                            .unwrap_or_default()
+                    };
+                    Node::Generated(ColumnType::Date {
+                        start: date(start),
+                        end: date(end),
+                    })
+                }
+                "date-time" => {
+                    let (start, end) = times()?;
+                    Node::DateTimeString(start, end)
+                }
+                format => Node::Generated(match format {
+                    "email" => ColumnType::Email,
+                    "uuid" => ColumnType::Uuid,
+                    "ulid" => ColumnType::Ulid,
+                    "ipv4" => ColumnType::IpAddress,
+                    "mac" => ColumnType::MacAddress,
+                    "first-name" => ColumnType::FirstName,
+                    "last-name" => ColumnType::LastName,
+                    "full-name" => ColumnType::FullName,
+                    "username" => ColumnType::Username,
+                    "phone" => ColumnType::PhoneNumber,
+                    "company" => ColumnType::Company,
+                    _ => ColumnType::Text(Some(count("maxLength")?.unwrap_or(255).max(1))),
+                }),
+            }
+        }
+        _ => return Err(format!("Error: {}: unsupported type '{}'", path, kind)),
+    })
+}
+
+impl Node {
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R, person: &mut Option<PersonName>) -> Bson {
+        let millis = |rng: &mut R, start: i64, end: i64| rng.gen_range(start..=end);
+        match self {
+            Node::Object(fields) => {
+                // the name fields of an object all describe the same person
+                let mut person = None;
+                let mut document = Document::new();
+                for (name, node, required) in fields {
+                    if !required && rng.gen_bool(OPTIONAL_ABSENT_PROBABILITY) {
+                        continue;
+                    }
+                    let value = node.generate(rng, &mut person);
+                    document.fields.push((name.clone(), value));
+                }
+                Bson::Document(document)
+            }
+            Node::Array { items, min, max } => {
+                // each item is someone else, eg: an array of emails
+                let count = rng.gen_range(*min..=*max);
+                Bson::Array((0..count).map(|_| items.generate(rng, &mut None)).collect())
+            }
+            Node::Int32(min, max) => Bson::Int32(rng.gen_range(*min..=*max)),
+            Node::Int64(min, max) => Bson::Int64(rng.gen_range(*min..=*max)),
+            Node::Double(min, max) if min < max => Bson::Double(rng.gen_range(*min..*max)),
+            Node::Double(min, _) => Bson::Double(*min),
+            Node::Boolean => Bson::Boolean(rng.gen()),
+            Node::Null => Bson::Null,
+            Node::ObjectId => Bson::ObjectId(ObjectId::generate()),
+            Node::DateTime(start, end) => Bson::DateTime(millis(rng, *start, *end)),
+            Node::DateTimeString(start, end) => Bson::String(
+                Utc.timestamp_millis_opt(millis(rng, *start, *end))
+                    .single()
+                    .map(|t| t.to_rfc3339_opts(SecondsFormat::Millis, true))
+                    .unwrap_or_default(),
+            ),
+            Node::Generated(column_type) => {
+                let person =
+                    person.get_or_insert_with(|| crate::names::person_name_with(rng, None, None));
+                Bson::from(generate_value(rng, column_type, person, 0))
+            }
+            Node::OneOf(nodes) => match nodes.choose(rng) {
+                Some(node) => node.generate(rng, person),
+                None => Bson::Null,
+            },
+            Node::Const(value) => value.clone(),
+        }
+    }
+}
+
+/// ## Example
+/// ```rust
+/// use random::database::DocumentTemplate;
+///
+/// let template = DocumentTemplate::new(
+///     r#"{
+///         "required": ["_id", "name", "created", "orders"],
+///         "properties": {
+///             "_id": { "bsonType": "objectId" },
+///             "name": { "type": "string", "format": "full-name" },
+///             "email": { "type": "string", "format": "email" },
+///             "created": { "bsonType": "date", "minimum": "2021-01-01" },
+///             "orders": {
+///                 "type": "array",
+///                 "maxItems": 3,
+///                 "items": {
+///                     "properties": {
+///                         "total": { "type": "number", "maximum": 500 },
+///                         "status": { "enum": ["new", "paid", "shipped"] }
+///                     }
+///                 }
+///             }
+///         }
+///     }"#,
+/// )
+/// .unwrap();
+/// let document = template.document();
+/// assert!(document.get("_id").is_some());
+/// // newline delimited, for mongoimport
+/// println!("{}", template.extended_json(10));
+/// // concatenated documents, like a mongodump .bson file
+/// let bson = template.bson(10);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentTemplate {
+    root: Node,
+}
+
+impl DocumentTemplate {
+    pub fn new(template: &str) -> Result<Self, Error> {
+        //! New template of a document, from its JSON schema
+        let json: Json = serde_json::from_str(template)
+            .map_err(|e| format!("Error: invalid template: {}", e))?;
+        DocumentTemplate::from_json(&json)
+    }
+
+    pub fn from_json(template: &Json) -> Result<Self, Error> {
+        //! New template of a document, from its parsed JSON schema
+        let root = parse_node(template, "$")?;
+        match root {
+            Node::Object(_) => Ok(DocumentTemplate { root }),
+            _ => Err(String::from("Error: a template describes an object")),
+        }
+    }
+
+    pub fn document(&self) -> Document {
+        //! Generate a document
+        self.document_with(&mut rand::thread_rng())
+    }
+
+    pub(crate) fn document_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Document {
+        match self.root.generate(rng, &mut None) {
+            Bson::Document(document) => document,
+            _ => Document::new(),
+        }
+    }
+
+    pub fn documents(&self, count: usize) -> Vec<Document> {
+        //! Generate documents
+        let mut trng = rand::thread_rng();
+        (0..count).map(|_| self.document_with(&mut trng)).collect()
+    }
+
+    pub fn extended_json(&self, count: usize) -> String {
+        //! Generate documents as Extended JSON, one per line
+        self.documents(count)
+            .iter()
+            .map(|d| format!("{}\n", d.to_extended_json()))
+            .collect()
+    }
+
+    pub fn bson(&self, count: usize) -> Vec<u8> {
+        //! Generate documents as BSON, one after another
+        self.documents(count)
+            .iter()
+            .flat_map(|d| d.to_bson())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encodings() {
+        let mut document = Document::new();
+        document
+            .insert("hello", Bson::String("world".into()))
+            .unwrap();
+        // the example of bsonspec.org
+        assert_eq!(
+            document.to_bson(),
+            b"\x16\x00\x00\x00\x02hello\x00\x06\x00\x00\x00world\x00\x00".to_vec()
+        );
+        assert!(document.insert("a\0b", Bson::Null).is_err());
+
+        let id = ObjectId::parse_str("5f1d7c3a9a8b7c6d5e4f3a2b").unwrap();
+        document.insert("_id", Bson::ObjectId(id)).unwrap();
+        document
+            .insert("at", Bson::DateTime(1_577_836_800_000))
+            .unwrap();
+        document.insert("old", Bson::DateTime(-1)).unwrap();
+        document
+            .insert("n", Bson::Array(vec![Bson::Int32(1), Bson::Double(2.0)]))
+            .unwrap();
+        document.insert("x", Bson::Double(f64::NAN)).unwrap();
+        assert_eq!(
+            document.to_extended_json(),
+            "{\"hello\":\"world\",\"_id\":{\"$oid\":\"5f1d7c3a9a8b7c6d5e4f3a2b\"},\"at\":{\"$date\":\"2020-01-01T00:00:00.000Z\"},\"old\":{\"$date\":{\"$numberLong\":\"-1\"}},\"n\":[1,2.0],\"x\":{\"$numberDouble\":\"NaN\"}}"
+        );
+        let bson = document.to_bson();
+        assert_eq!(
+            i32::from_le_bytes([bson[0], bson[1], bson[2], bson[3]]) as usize,
+            bson.len()
+        );
+    }
+
+    #[test]
+    fn test_template() {
+        let template = DocumentTemplate::new(
+            r#"{
+                "bsonType": "object",
+                "required": ["_id", "age", "tags", "address", "kind"],
+                "properties": {
+                    "_id": { "bsonType": "objectId" },
+                    "age": { "bsonType": "int", "minimum": 18, "maximum": 90 },
+                    "tags": { "type": "array", "items": { "enum": ["a", "b"] }, "minItems": 1, "maxItems": 3 },
+                    "address": { "properties": { "city": { "type": "string", "maxLength": 10 } } },
+                    "kind": { "const": "user" },
+                    "note": { "type": ["string", "null"] }
+                }
+            }"#,
+        )
+        .unwrap();
+        for document in template.documents(50) {
+            assert_eq!(
+                document
+                    .keys()
+                    .filter(|k| *k != "note")
+                    .collect::<Vec<&str>>(),
+                ["_id", "age", "tags", "address", "kind"]
+            );
+            match document.get("age") {
+                Some(Bson::Int32(age)) => assert!((18..=90).contains(age)),
+                age => panic!("unexpected age {:?}", age),
+            }
+            match document.get("tags") {
+                Some(Bson::Array(tags)) => assert!((1..=3).contains(&tags.len())),
+                tags => panic!("unexpected tags {:?}", tags),
+            }
+            match document.get("address") {
+                Some(Bson::Document(address)) => match address.get("city") {
+                    Some(Bson::String(city)) => assert!(city.len() <= 10),
+                    city => panic!("unexpected city {:?}", city),
+                },
+                address => panic!("unexpected address {:?}", address),
+            }
+            assert_eq!(document.get("kind"), Some(&Bson::String("user".into())));
+        }
+        assert_eq!(template.extended_json(4).lines().count(), 4);
+
+        assert!(DocumentTemplate::new("{\"type\": \"string\"}").is_err());
+        assert!(
+            DocumentTemplate::new("{\"properties\": {\"a\": {\"type\": \"decimal\"}}}").is_err()
+        );
+        assert!(DocumentTemplate::new("{\"properties\": {\"a\": {\"type\": \"array\"}}}").is_err());
+        for kind in &["int", "long", "integer"] {
+            let fractional = format!(
+                "{{\"properties\": {{\"a\": {{\"bsonType\": \"{}\", \"minimum\": 1.5, \"maximum\": 1.7}}}}}}",
+                kind
+            );
+            assert!(DocumentTemplate::new(&fractional).is_err(), "{}", kind);
+        }
+        let whole = DocumentTemplate::new(
+            "{\"properties\": {\"a\": {\"bsonType\": \"int\", \"minimum\": 1.5, \"maximum\": 2.5}}, \"required\": [\"a\"]}",
+        )
+        .unwrap();
+        assert_eq!(whole.document().get("a"), Some(&Bson::Int32(2)));
+
+        let contacts = DocumentTemplate::new(
+            r#"{
+                "required": ["emails"],
+                "properties": {
+                    "emails": { "type": "array", "items": { "type": "string", "format": "email" }, "minItems": 8, "maxItems": 8 }
+                }
+            }"#,
+        )
+        .unwrap();
+        match contacts.document().get("emails") {
+            Some(Bson::Array(emails)) => {
+                let users = emails
+                    .iter()
+                    .map(|e| match e {
+                        Bson::String(email) => email.split('@').next().unwrap_or_default(),
+                        email => panic!("unexpected email {:?}", email),
+                    })
+                    .collect::<std::collections::HashSet<&str>>();
+                assert!(users.len() > 1);
+            }
+            emails => panic!("unexpected emails {:?}", emails),
+        }
+    }
+}
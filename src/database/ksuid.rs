@@ -0,0 +1,126 @@
+/*
+KSUID generators
+32 bit seconds since 2014-05-13 & 128 random bits, written as
+27 base62 characters that sort by time
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeZone, Utc};
+use rand::Rng;
+
+use super::{decode_base, encode_base};
+
+type Error = String;
+
+const BASE62_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// ## Example
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use random::database::Ksuid;
+///
+/// let time = Utc.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap();
+/// let ksuid = Ksuid::with_time(&time).unwrap();
+/// assert_eq!(ksuid.timestamp(), time);
+/// assert_eq!(ksuid.to_string().parse::<Ksuid>(), Ok(ksuid));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ksuid([u8; 20]);
+
+impl Ksuid {
+    /// unix time of the KSUID epoch
+    pub const EPOCH: i64 = 1_400_000_000;
+
+    pub fn generate() -> Self {
+        //! New KSUID for the current time
+        Ksuid::with_time_rng(&mut rand::thread_rng(), &Utc::now()).unwrap_or(Ksuid([0; 20]))
+    }
+
+    pub fn with_time(time: &DateTime<Utc>) -> Result<Self, Error> {
+        //! New KSUID for the given time (2014-05-13 to 2150-06-19)
+        Ksuid::with_time_rng(&mut rand::thread_rng(), time)
+    }
+
+    pub(crate) fn with_time_rng<R: Rng + ?Sized>(
+        rng: &mut R,
+        time: &DateTime<Utc>,
+    ) -> Result<Self, Error> {
+        let seconds = u32::try_from(time.timestamp() - Ksuid::EPOCH)
+            .map_err(|_| format!("Error: {} is out of range of a KSUID", time))?;
+        let mut bytes = [0u8; 20];
+        bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+        rng.fill(&mut bytes[4..]);
+        Ok(Ksuid(bytes))
+    }
+
+    pub fn from_bytes(bytes: [u8; 20]) -> Self {
+        Ksuid(bytes)
+    }
+
+    pub fn bytes(&self) -> [u8; 20] {
+        self.0
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        //! Creation time, to the second
+        let seconds = u32::from_be_bytes([self.0[0], self.0[1], self.0[2], self.0[3]]);
+        Utc.timestamp_opt(i64::from(seconds) + Ksuid::EPOCH, 0)
+            .single()
+            .unwrap_or_default()
+    }
+
+    pub fn payload(&self) -> [u8; 16] {
+        //! The 16 random bytes
+        let mut payload = [0u8; 16];
+        payload.copy_from_slice(&self.0[4..]);
+        payload
+    }
+}
+
+impl fmt::Display for Ksuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:0>27}", encode_base(&self.0, BASE62_ALPHABET))
+    }
+}
+
+impl FromStr for Ksuid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Error: invalid KSUID '{}'", s);
+        if s.len() != 27 {
+            return Err(invalid());
+        }
+        let bytes = decode_base(s, BASE62_ALPHABET, 20).map_err(|_| invalid())?;
+        let bytes = <[u8; 20]>::try_from(bytes.as_slice()).map_err(|_| invalid())?;
+        Ok(Ksuid(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ksuid() {
+        let ksuid: Ksuid = "0ujtsYcgvSTl8PAuAdqWYSMnLOv".parse().unwrap();
+        assert_eq!(ksuid.timestamp().timestamp(), 1507608047);
+        assert_eq!(
+            ksuid.payload(),
+            [
+                0xb5, 0xa1, 0xcd, 0x34, 0xb5, 0xf9, 0x9d, 0x11, 0x54, 0xfb, 0x68, 0x53, 0x34, 0x5c,
+                0x97, 0x35
+            ]
+        );
+        assert_eq!(ksuid.to_string(), "0ujtsYcgvSTl8PAuAdqWYSMnLOv");
+        assert!("aWgEPTl1tmebfsQzFP4bxwgy80V".parse::<Ksuid>().is_ok());
+        assert!("aWgEPTl1tmebfsQzFP4bxwgy80W".parse::<Ksuid>().is_err());
+
+        let before_epoch = Utc.with_ymd_and_hms(2014, 1, 1, 0, 0, 0).unwrap();
+        assert!(Ksuid::with_time(&before_epoch).is_err());
+        assert_eq!(Ksuid::generate().to_string().len(), 27);
+    }
+}
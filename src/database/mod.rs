@@ -0,0 +1,93 @@
+/*
+Database related generators
+Keys of the popular databases: MongoDB ObjectIds, ULIDs, KSUIDs,
+Snowflake IDs, NanoIDs & CUID2s; the time based ones can be made
+at any given time so they line up with generated event times.
+Rows of SQL tables described by a schema, as INSERT statements,
+and whole datasets of related tables from a single seed, which
+can be written straight into a SQLite file (`sqlite` feature).
+MongoDB documents from a schema, as Extended JSON or BSON
+*/
+
+mod cuid2;
+mod dataset;
+mod document;
+mod ksuid;
+mod nanoid;
+mod objectid;
+mod schema;
+mod snowflake;
+mod sql;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod ulid;
+
+pub use cuid2::{cuid2, cuid2_with_time, Cuid2Generator};
+pub use dataset::{Cardinality, Dataset, TableRows};
+pub use document::{Bson, Document, DocumentTemplate};
+pub use ksuid::Ksuid;
+pub use nanoid::{nanoid, NANOID_ALPHABET};
+pub use objectid::{mongo_objectid, mongo_objectids, ObjectId};
+pub use schema::{Column, ColumnType, ForeignKey, KeyValues, Row, Table, Value};
+pub use snowflake::{SnowflakeGenerator, DISCORD_EPOCH, TWITTER_EPOCH};
+pub use sql::Dialect;
+#[cfg(feature = "sqlite")]
+pub use sqlite::write_sqlite;
+pub use ulid::Ulid;
+
+type Error = String;
+
+fn encode_base(bytes: &[u8], alphabet: &[u8]) -> String {
+    // big endian bytes to digits of the alphabet, without leading zeros
+    let base = alphabet.len() as u32;
+    let mut number = bytes.to_vec();
+    let mut digits = Vec::new();
+    while number.iter().any(|b| *b != 0) {
+        let mut remainder = 0u32;
+        for byte in number.iter_mut() {
+            let value = (remainder << 8) | u32::from(*byte);
+            *byte = (value / base) as u8;
+            remainder = value % base;
+        }
+        digits.push(alphabet[remainder as usize]);
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+fn decode_base(text: &str, alphabet: &[u8], size: usize) -> Result<Vec<u8>, Error> {
+    // digits of the alphabet to `size` big endian bytes
+    let base = alphabet.len() as u32;
+    let mut number = vec![0u8; size];
+    for c in text.bytes() {
+        let digit = alphabet
+            .iter()
+            .position(|a| *a == c)
+            .ok_or_else(|| format!("Error: invalid character '{}'", c as char))?;
+        let mut carry = digit as u32;
+        for byte in number.iter_mut().rev() {
+            let value = u32::from(*byte) * base + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(format!("Error: '{}' does not fit {} bytes", text, size));
+        }
+    }
+    Ok(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_encoding() {
+        let alphabet = b"0123456789abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(encode_base(&[0x01, 0x00], alphabet), "74");
+        assert_eq!(decode_base("74", alphabet, 2), Ok(vec![0x01, 0x00]));
+        assert_eq!(encode_base(&[0, 0], alphabet), "");
+        assert!(decode_base("zzzz", alphabet, 2).is_err());
+        assert!(decode_base("7-", alphabet, 2).is_err());
+    }
+}
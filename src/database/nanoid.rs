@@ -0,0 +1,69 @@
+/*
+NanoID generators
+Random, url friendly ids of any size & alphabet; there is no time
+component, so the same id can be used at any time
+*/
+
+use rand::Rng;
+
+type Error = String;
+
+/// the url safe alphabet NanoIDs use by default
+pub const NANOID_ALPHABET: &str =
+    "_-0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub(crate) fn nanoid_with<R: Rng + ?Sized>(rng: &mut R, size: usize, alphabet: &[char]) -> String {
+    // every character of the alphabet is equally likely
+    (0..size)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+        .collect()
+}
+
+pub fn nanoid(size: Option<usize>, alphabet: Option<&str>) -> Result<String, Error> {
+    /*
+    Generate a NanoID
+    :param size: number of characters - 21 if not given
+    :param alphabet: characters to use, eg: 0123456789abcdef - url safe ones if not given
+    :return: eg: V1StGXR8_Z5jdHi6B-myT
+    */
+    let alphabet = alphabet
+        .unwrap_or(NANOID_ALPHABET)
+        .chars()
+        .collect::<Vec<char>>();
+    if alphabet.is_empty() || alphabet.len() > 256 {
+        return Err(String::from(
+            "Error: alphabet must have between 1 and 256 characters",
+        ));
+    }
+    let mut unique = alphabet.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    if unique.len() != alphabet.len() {
+        return Err(String::from("Error: alphabet has repeated characters"));
+    }
+    Ok(nanoid_with(
+        &mut rand::thread_rng(),
+        size.unwrap_or(21),
+        &alphabet,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nanoid() {
+        let id = nanoid(None, None).unwrap();
+        assert_eq!(id.len(), 21);
+        assert!(id.chars().all(|c| NANOID_ALPHABET.contains(c)));
+
+        let id = nanoid(Some(10), Some("0123456789abcdef")).unwrap();
+        assert_eq!(id.len(), 10);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(nanoid(Some(5), Some("äö")).unwrap().chars().count(), 5);
+
+        assert!(nanoid(None, Some("")).is_err());
+        assert!(nanoid(None, Some("aab")).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+/*
+MongoDB ObjectId generators
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::core::utils::hex_byte;
+
+type Error = String;
+
+/// MongoDB ObjectId: 4 byte big endian unix timestamp, 5 random bytes
+/// unique to the process and a 3 byte counter starting at a random value
+///
+/// ## Example
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use random::database::ObjectId;
+///
+/// let time = Utc.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap();
+/// let oid = ObjectId::with_time(&time).unwrap();
+/// assert_eq!(oid.timestamp(), time);
+/// assert_eq!(oid.to_hex().parse::<ObjectId>(), Ok(oid));
+/// println!("ObjectId(\"{}\")", oid);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId([u8; 12]);
+
+fn process_unique() -> &'static [u8; 5] {
+    static PROCESS_UNIQUE: OnceLock<[u8; 5]> = OnceLock::new();
+    PROCESS_UNIQUE.get_or_init(rand::random)
+}
+
+fn next_counter() -> u32 {
+    static COUNTER: OnceLock<AtomicU32> = OnceLock::new();
+    COUNTER
+        .get_or_init(|| AtomicU32::new(rand::random::<u32>() & 0x00FF_FFFF))
+        .fetch_add(1, Ordering::Relaxed)
+        & 0x00FF_FFFF
+}
+
+impl ObjectId {
+    pub fn generate() -> Self {
+        //! New ObjectId for the current time
+        ObjectId::from_seconds(Utc::now().timestamp() as u32)
+    }
+
+    pub fn with_time(time: &DateTime<Utc>) -> Result<Self, Error> {
+        //! New ObjectId for the given time; the timestamp is
+        //! 32 bits of seconds, so 1970 to 2106 only
+        let seconds = u32::try_from(time.timestamp())
+            .map_err(|_| format!("Error: {} is out of range of an ObjectId", time))?;
+        Ok(ObjectId::from_seconds(seconds))
+    }
+
+    fn from_seconds(seconds: u32) -> Self {
+        let mut bytes = [0u8; 12];
+        bytes[0..4].copy_from_slice(&seconds.to_be_bytes());
+        bytes[4..9].copy_from_slice(process_unique());
+        bytes[9..12].copy_from_slice(&next_counter().to_be_bytes()[1..]);
+        ObjectId(bytes)
+    }
+
+    pub fn from_bytes(bytes: [u8; 12]) -> Self {
+        ObjectId(bytes)
+    }
+
+    pub fn bytes(&self) -> [u8; 12] {
+        self.0
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        //! Creation time, to the second
+        let seconds = u32::from_be_bytes([self.0[0], self.0[1], self.0[2], self.0[3]]);
+        Utc.timestamp_opt(i64::from(seconds), 0)
+            .single()
+            .unwrap_or_default()
+    }
+
+    pub fn process_unique(&self) -> [u8; 5] {
+        //! The 5 random bytes of the generating process
+        [self.0[4], self.0[5], self.0[6], self.0[7], self.0[8]]
+    }
+
+    pub fn counter(&self) -> u32 {
+        u32::from_be_bytes([0, self.0[9], self.0[10], self.0[11]])
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn parse_str(hex: &str) -> Result<Self, Error> {
+        //! ObjectId from its 24 hex characters
+        let invalid = || format!("Error: invalid ObjectId '{}'", hex);
+        if hex.len() != 24 {
+            return Err(invalid());
+        }
+        let mut bytes = [0u8; 12];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = hex
+                .get(2 * i..2 * i + 2)
+                .and_then(hex_byte)
+                .ok_or_else(invalid)?;
+        }
+        Ok(ObjectId(bytes))
+    }
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl FromStr for ObjectId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ObjectId::parse_str(s)
+    }
+}
+
+impl From<[u8; 12]> for ObjectId {
+    fn from(bytes: [u8; 12]) -> Self {
+        ObjectId(bytes)
+    }
+}
+
+pub fn mongo_objectid() -> String {
+    /*
+    Generate a MongoDB ObjectId for the current time
+    :return: 24 hex characters, eg: 5f1d7a3c9b1e8a4d2c6f0e11
+    */
+    ObjectId::generate().to_hex()
+}
+
+pub fn mongo_objectids<'a>(
+    times: impl IntoIterator<Item = &'a DateTime<Utc>>,
+) -> Result<Vec<ObjectId>, Error> {
+    /*
+    Generate ObjectIds at given times, eg: the output of GenerateTime
+    :param times: creation times of the ids
+    :return: one ObjectId per time
+    */
+    times.into_iter().map(ObjectId::with_time).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datetime::GenerateTime;
+
+    #[test]
+    fn test_objectid_layout() {
+        let oid: ObjectId = "5f1d7a3c9b1e8a4d2c6f0e11".parse().unwrap();
+        assert_eq!(
+            oid.timestamp(),
+            Utc.timestamp_opt(0x5f1d7a3c, 0).single().unwrap()
+        );
+        assert_eq!(oid.process_unique(), [0x9b, 0x1e, 0x8a, 0x4d, 0x2c]);
+        assert_eq!(oid.counter(), 0x6f0e11);
+        assert_eq!(oid.to_string(), "5f1d7a3c9b1e8a4d2c6f0e11");
+        assert!("5f1d7a3c9b1e8a4d2c6f0e1".parse::<ObjectId>().is_err());
+        assert!("5f1d7a3c9b1e8a4d2c6f0e1z".parse::<ObjectId>().is_err());
+        assert!("+f1d7a3c9b1e8a4d2c6f0e1a".parse::<ObjectId>().is_err());
+
+        let a = ObjectId::generate();
+        let b = ObjectId::generate();
+        assert_eq!(a.process_unique(), b.process_unique());
+        assert_ne!(a.counter(), b.counter());
+        assert_eq!(mongo_objectid().len(), 24);
+    }
+
+    #[test]
+    fn test_objectid_times() {
+        let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+        let times = start.generate_until_with_limit(&end, 100).unwrap();
+        let oids = mongo_objectids(&times).unwrap();
+        for (oid, time) in oids.iter().zip(&times) {
+            assert_eq!(oid.timestamp().timestamp(), time.timestamp());
+        }
+
+        let before_epoch = Utc.with_ymd_and_hms(1969, 12, 31, 0, 0, 0).unwrap();
+        assert!(ObjectId::with_time(&before_epoch).is_err());
+    }
+}
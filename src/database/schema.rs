@@ -0,0 +1,640 @@
+/*
+Schema driven row generators
+Tables of typed columns with constraints (primary key, unique,
+nullable, foreign key, enum) whose rows are filled by the crate's
+generators
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use uuid::{Builder, Uuid};
+
+use super::Ulid;
+use crate::core::constants::{COUNTRY_CALLING_CODES, DUMMY_CORPS, ENG_NOUNS, TLDS};
+use crate::names::PersonName;
+
+type Error = String;
+
+/// Values foreign key columns may take, by the referenced "table.column"
+pub type KeyValues = HashMap<String, Vec<Value>>;
+pub type Row = Vec<Value>;
+
+/// share of NULLs in nullable columns
+const NULL_PROBABILITY: f64 = 0.1;
+/// attempts at a value not generated yet before a unique column gives up
+const UNIQUE_ATTEMPTS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnType {
+    /// auto incrementing integer: 1, 2, 3...
+    Serial,
+    Integer {
+        min: i64,
+        max: i64,
+    },
+    Float {
+        min: f64,
+        max: f64,
+    },
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
+    Boolean,
+    /// words, of at most `max_length` characters; VARCHAR when given
+    Text(Option<usize>),
+    Uuid,
+    Ulid,
+    Date {
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    Timestamp {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    /// one of the given values
+    Enum(Vec<String>),
+    FirstName,
+    LastName,
+    FullName,
+    Username,
+    Email,
+    PhoneNumber,
+    Company,
+    IpAddress,
+    MacAddress,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForeignKey {
+    pub table: String,
+    pub column: String,
+}
+
+impl ForeignKey {
+    pub fn key(&self) -> String {
+        //! "table.column", the key of `KeyValues`
+        format!("{}.{}", self.table, self.column)
+    }
+}
+
+/// ## Example
+/// ```rust
+/// use random::database::{Column, ColumnType};
+///
+/// let id = Column::new("id", ColumnType::Serial).primary_key();
+/// let email = Column::new("email", ColumnType::Email).unique();
+/// let customer = Column::new("customer_id", ColumnType::Serial).references("customers", "id");
+/// let note = Column::new("note", ColumnType::Text(Some(200))).nullable();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub primary_key: bool,
+    pub unique: bool,
+    pub nullable: bool,
+    pub references: Option<ForeignKey>,
+}
+
+impl Column {
+    pub fn new(name: &str, column_type: ColumnType) -> Self {
+        //! New NOT NULL column without any other constraint
+        Column {
+            name: name.to_string(),
+            column_type,
+            primary_key: false,
+            unique: false,
+            nullable: false,
+            references: None,
+        }
+    }
+
+    pub fn primary_key(mut self) -> Self {
+        self.primary_key = true;
+        self.nullable = false;
+        self
+    }
+
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    pub fn nullable(mut self) -> Self {
+        self.nullable = !self.primary_key;
+        self
+    }
+
+    pub fn references(mut self, table: &str, column: &str) -> Self {
+        //! Foreign key: values are taken from the referenced column
+        self.references = Some(ForeignKey {
+            table: table.to_string(),
+            column: column.to_string(),
+        });
+        self
+    }
+
+    fn is_unique(&self) -> bool {
+        self.unique || self.primary_key
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    /// exact decimal, eg: 1234.56
+    Decimal(String),
+    Text(String),
+    Date(NaiveDate),
+    Timestamp(DateTime<Utc>),
+    Uuid(Uuid),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Boolean(x) => write!(f, "{}", x),
+            Value::Integer(x) => write!(f, "{}", x),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Decimal(x) | Value::Text(x) => write!(f, "{}", x),
+            Value::Date(x) => write!(f, "{}", x.format("%Y-%m-%d")),
+            Value::Timestamp(x) => write!(f, "{}", x.format("%Y-%m-%d %H:%M:%S%.6f")),
+            Value::Uuid(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// ## Example
+/// ```rust
+/// use random::database::{Column, ColumnType, Dialect, Table};
+///
+/// let customers = Table::new(
+///     "customers",
+///     vec![
+///         Column::new("id", ColumnType::Serial).primary_key(),
+///         Column::new("name", ColumnType::FullName),
+///         Column::new("email", ColumnType::Email).unique(),
+///         Column::new("tier", ColumnType::Enum(vec!["free".into(), "pro".into()])),
+///     ],
+/// )
+/// .unwrap();
+/// let rows = customers.rows(10, None).unwrap();
+/// println!("{}", Dialect::Postgres.create_table(&customers));
+/// println!("{}", Dialect::Postgres.insert(&customers, &rows, None));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+}
+
+/// What a table remembers while its rows are generated
+#[derive(Debug, Clone)]
+pub(crate) struct TableState {
+    serials: Vec<i64>,
+    issued: Vec<HashSet<String>>,
+    /// shuffled parent keys not drawn yet, of unique foreign keys
+    unused_keys: Vec<Option<Vec<Value>>>,
+}
+
+impl Table {
+    pub fn new(name: &str, columns: Vec<Column>) -> Result<Self, Error> {
+        //! New table; the columns are checked for consistency
+        if name.is_empty() || columns.is_empty() {
+            return Err(String::from("Error: a table needs a name and columns"));
+        }
+        let mut names = HashSet::new();
+        for column in &columns {
+            if !names.insert(column.name.to_ascii_lowercase()) {
+                return Err(format!(
+                    "Error: column '{}' of '{}' is defined twice",
+                    column.name, name
+                ));
+            }
+            let invalid = match &column.column_type {
+                ColumnType::Integer { min, max } => min > max,
+                ColumnType::Float { min, max } => !min.is_finite() || !max.is_finite() || min > max,
+                ColumnType::Decimal { precision, scale } => {
+                    *precision == 0 || scale > precision || *precision > 38
+                }
+                ColumnType::Text(Some(length)) => *length == 0,
+                ColumnType::Date { start, end } => start > end,
+                ColumnType::Timestamp { start, end } => start > end,
+                ColumnType::Enum(values) => values.is_empty(),
+                _ => false,
+            };
+            if invalid {
+                return Err(format!(
+                    "Error: invalid type of column '{}' of '{}': {:?}",
+                    column.name, name, column.column_type
+                ));
+            }
+        }
+        Ok(Table {
+            name: name.to_string(),
+            columns,
+        })
+    }
+
+    pub fn column_index(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == column)
+    }
+
+    pub fn column_values(&self, rows: &[Row], column: &str) -> Option<Vec<Value>> {
+        //! Values of one column, eg: the keys rows of other tables can refer to
+        let index = self.column_index(column)?;
+        Some(
+            rows.iter()
+                .filter_map(|row| row.get(index).cloned())
+                .collect(),
+        )
+    }
+
+    pub fn rows(&self, count: usize, keys: Option<&KeyValues>) -> Result<Vec<Row>, Error> {
+        //! Generate rows
+        //! - `count`: number of rows
+        //! - `keys`: values of the columns foreign keys refer to; required
+        //!   when the table has foreign keys
+        let mut trng = rand::thread_rng();
+        let mut state = self.state();
+        (0..count)
+            .map(|_| self.row_with(&mut trng, &mut state, keys, &[]))
+            .collect()
+    }
+
+    pub(crate) fn state(&self) -> TableState {
+        TableState {
+            serials: vec![0; self.columns.len()],
+            issued: vec![HashSet::new(); self.columns.len()],
+            unused_keys: vec![None; self.columns.len()],
+        }
+    }
+
+    fn unused_key<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        state: &mut TableState,
+        keys: Option<&KeyValues>,
+        index: usize,
+        foreign_key: &ForeignKey,
+    ) -> Result<Value, Error> {
+        // a unique foreign key draws the parent keys without replacement,
+        // so it fails only once every one of them is taken
+        let column = &self.columns[index];
+        if state.unused_keys[index].is_none() {
+            let mut unused = keys
+                .and_then(|k| k.get(&foreign_key.key()))
+                .cloned()
+                .ok_or_else(|| {
+                    format!(
+                        "Error: no values of {} for '{}.{}'",
+                        foreign_key.key(),
+                        self.name,
+                        column.name
+                    )
+                })?;
+            unused.shuffle(rng);
+            state.unused_keys[index] = Some(unused);
+        }
+        let unused = state.unused_keys[index].get_or_insert_with(Vec::new);
+        while let Some(value) = unused.pop() {
+            if state.issued[index].insert(unique_key(&value)) {
+                return Ok(value);
+            }
+        }
+        Err(format!(
+            "Error: ran out of unique values for '{}.{}'",
+            self.name, column.name
+        ))
+    }
+
+    pub(crate) fn row_with<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        state: &mut TableState,
+        keys: Option<&KeyValues>,
+        fixed: &[(usize, Value)],
+    ) -> Result<Row, Error> {
+        // the name columns of a row all describe the same person
+        let mut person: Option<PersonName> = None;
+        let mut row = Vec::with_capacity(self.columns.len());
+        for (index, column) in self.columns.iter().enumerate() {
+            if let Some((_, value)) = fixed.iter().find(|(i, _)| *i == index) {
+                row.push(value.clone());
+                continue;
+            }
+            if column.nullable && rng.gen_bool(NULL_PROBABILITY) {
+                row.push(Value::Null);
+                continue;
+            }
+            if let (Some(foreign_key), true) = (&column.references, column.is_unique()) {
+                row.push(self.unused_key(rng, state, keys, index, foreign_key)?);
+                continue;
+            }
+            let mut attempt = 0;
+            let value = loop {
+                let value = match &column.references {
+                    Some(foreign_key) => keys
+                        .and_then(|k| k.get(&foreign_key.key()))
+                        .and_then(|values| values.choose(rng))
+                        .cloned()
+                        .ok_or_else(|| {
+                            format!(
+                                "Error: no values of {} for '{}.{}'",
+                                foreign_key.key(),
+                                self.name,
+                                column.name
+                            )
+                        })?,
+                    None => {
+                        if let ColumnType::Serial = column.column_type {
+                            state.serials[index] += 1;
+                            Value::Integer(state.serials[index])
+                        } else {
+                            let person = person.get_or_insert_with(|| {
+                                crate::names::person_name_with(rng, None, None)
+                            });
+                            generate_value(rng, &column.column_type, person, attempt)
+                        }
+                    }
+                };
+                if !column.is_unique() || state.issued[index].insert(unique_key(&value)) {
+                    break value;
+                }
+                attempt += 1;
+                if attempt >= UNIQUE_ATTEMPTS {
+                    return Err(format!(
+                        "Error: ran out of unique values for '{}.{}'",
+                        self.name, column.name
+                    ));
+                }
+            };
+            row.push(value);
+        }
+        Ok(row)
+    }
+}
+
+fn unique_key(value: &Value) -> String {
+    // text is compared case-insensitively, like most collations do
+    match value {
+        Value::Text(x) => x.to_lowercase(),
+        _ => value.to_string(),
+    }
+}
+
+fn words<R: Rng + ?Sized>(rng: &mut R, max_length: usize) -> String {
+    let count = rng.gen_range(1..=8);
+    let text = (0..count)
+        .map(|_| *ENG_NOUNS.choose(rng).unwrap_or(&ENG_NOUNS[0]))
+        .collect::<Vec<&str>>()
+        .join(" ");
+    text.chars()
+        .take(max_length)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+pub(super) fn generate_value<R: Rng + ?Sized>(
+    rng: &mut R,
+    column_type: &ColumnType,
+    person: &PersonName,
+    attempt: usize,
+) -> Value {
+    // retries of unique person based columns get a number appended
+    let numbered = |text: String| match attempt {
+        0 => text,
+        _ => format!("{}{}", text, attempt),
+    };
+    match column_type {
+        ColumnType::Serial => Value::Integer(1),
+        ColumnType::Integer { min, max } => Value::Integer(rng.gen_range(*min..=*max)),
+        ColumnType::Float { min, max } => Value::Float(if min < max {
+            rng.gen_range(*min..*max)
+        } else {
+            *min
+        }),
+        ColumnType::Decimal { precision, scale } => {
+            let integer_digits = u32::from(precision - scale).min(18);
+            let integer = rng.gen_range(0..10u64.pow(integer_digits));
+            if *scale == 0 {
+                Value::Decimal(integer.to_string())
+            } else {
+                let fraction = (0..*scale)
+                    .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+                    .collect::<String>();
+                Value::Decimal(format!("{}.{}", integer, fraction))
+            }
+        }
+        ColumnType::Boolean => Value::Boolean(rng.gen()),
+        ColumnType::Text(max_length) => Value::Text(words(rng, max_length.unwrap_or(255))),
+        ColumnType::Uuid => {
+            let mut bytes = [0u8; 16];
+            rng.fill(&mut bytes);
+            Value::Uuid(Builder::from_random_bytes(bytes).into_uuid())
+        }
+        ColumnType::Ulid => {
+            let now = Utc::now();
+            Value::Text(
+                Ulid::with_time_rng(rng, &now)
+                    .map(|u| u.to_string())
+                    .unwrap_or_default(),
+            )
+        }
+        ColumnType::Date { start, end } => {
+            let days = end.signed_duration_since(*start).num_days();
+            Value::Date(*start + Duration::days(rng.gen_range(0..=days)))
+        }
+        ColumnType::Timestamp { start, end } => {
+            let micros = end
+                .signed_duration_since(*start)
+                .num_microseconds()
+                .unwrap_or(i64::MAX);
+            Value::Timestamp(*start + Duration::microseconds(rng.gen_range(0..=micros)))
+        }
+        ColumnType::Enum(values) => Value::Text(values.choose(rng).cloned().unwrap_or_default()),
+        ColumnType::FirstName => Value::Text(person.first_name.clone()),
+        ColumnType::LastName => Value::Text(person.last_name.clone()),
+        ColumnType::FullName => Value::Text(person.full_name()),
+        ColumnType::Username => Value::Text(numbered(person.username())),
+        ColumnType::Email => {
+            let domain = format!(
+                "{}.{}",
+                ENG_NOUNS.choose(rng).unwrap_or(&ENG_NOUNS[0]),
+                TLDS.choose(rng).unwrap_or(&TLDS[0])
+            );
+            Value::Text(format!("{}@{}", numbered(person.username()), domain))
+        }
+        ColumnType::PhoneNumber => {
+            let (_, calling_code) = COUNTRY_CALLING_CODES
+                .choose(rng)
+                .unwrap_or(&COUNTRY_CALLING_CODES[0]);
+            Value::Text(format!(
+                "+{} {:03} {:03} {:04}",
+                calling_code,
+                rng.gen_range(100..1000),
+                rng.gen_range(0..1000),
+                rng.gen_range(0..10000)
+            ))
+        }
+        ColumnType::Company => Value::Text(numbered(
+            DUMMY_CORPS
+                .choose(rng)
+                .unwrap_or(&DUMMY_CORPS[0])
+                .to_string(),
+        )),
+        ColumnType::IpAddress => Value::Text(
+            std::net::Ipv4Addr::new(
+                rng.gen_range(1..224),
+                rng.gen(),
+                rng.gen(),
+                rng.gen_range(1..255),
+            )
+            .to_string(),
+        ),
+        ColumnType::MacAddress => {
+            // locally administered, unicast
+            let mut bytes: [u8; 6] = rng.gen();
+            bytes[0] = (bytes[0] | 0x02) & 0xfe;
+            Value::Text(
+                bytes
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<Vec<String>>()
+                    .join(":"),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_rows() {
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let table = Table::new(
+            "customers",
+            vec![
+                Column::new("id", ColumnType::Serial).primary_key(),
+                Column::new("first_name", ColumnType::FirstName),
+                Column::new("last_name", ColumnType::LastName),
+                Column::new("username", ColumnType::Username).unique(),
+                Column::new("level", ColumnType::Integer { min: 1, max: 3 }),
+                Column::new("tier", ColumnType::Enum(vec!["free".into(), "pro".into()])),
+                Column::new("created", ColumnType::Timestamp { start, end }),
+                Column::new("note", ColumnType::Text(Some(20))).nullable(),
+            ],
+        )
+        .unwrap();
+
+        let rows = table.rows(200, None).unwrap();
+        assert_eq!(rows.len(), 200);
+        let ids = table.column_values(&rows, "id").unwrap();
+        assert_eq!(ids[0], Value::Integer(1));
+        assert_eq!(ids[199], Value::Integer(200));
+        let usernames = table
+            .column_values(&rows, "username")
+            .unwrap()
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<HashSet<String>>();
+        assert_eq!(usernames.len(), 200);
+
+        for row in &rows {
+            match (&row[1], &row[3]) {
+                (Value::Text(first), Value::Text(username)) => assert!(username
+                    .starts_with(&crate::core::utils::ascii_fold(first)[..1].to_lowercase())),
+                _ => panic!("unexpected values"),
+            }
+            assert!(matches!(row[4], Value::Integer(1..=3)));
+            assert!(row[5] == Value::Text("free".into()) || row[5] == Value::Text("pro".into()));
+            match &row[6] {
+                Value::Timestamp(x) => assert!(start <= *x && *x <= end),
+                _ => panic!("unexpected value"),
+            }
+            match &row[7] {
+                Value::Null => {}
+                Value::Text(x) => assert!(x.chars().count() <= 20),
+                _ => panic!("unexpected value"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unique_foreign_key() {
+        // one profile per user: every user is drawn once, in any order
+        let profiles = Table::new(
+            "profiles",
+            vec![
+                Column::new("id", ColumnType::Serial).primary_key(),
+                Column::new("user_id", ColumnType::Serial)
+                    .references("users", "id")
+                    .unique(),
+            ],
+        )
+        .unwrap();
+        let mut keys = KeyValues::new();
+        keys.insert("users.id".into(), (1..=200).map(Value::Integer).collect());
+        let rows = profiles.rows(200, Some(&keys)).unwrap();
+        let users = profiles
+            .column_values(&rows, "user_id")
+            .unwrap()
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<HashSet<String>>();
+        assert_eq!(users.len(), 200);
+        assert!(profiles.rows(201, Some(&keys)).is_err());
+        assert!(profiles.rows(1, None).is_err());
+    }
+
+    #[test]
+    fn test_constraints() {
+        assert!(Table::new("t", vec![]).is_err());
+        assert!(Table::new(
+            "t",
+            vec![
+                Column::new("a", ColumnType::Boolean),
+                Column::new("a", ColumnType::Boolean)
+            ]
+        )
+        .is_err());
+        assert!(Table::new("t", vec![Column::new("a", ColumnType::Enum(vec![]))]).is_err());
+
+        let flags = Table::new("t", vec![Column::new("a", ColumnType::Boolean).unique()]).unwrap();
+        assert!(flags.rows(2, None).is_ok());
+        assert!(flags.rows(3, None).is_err());
+
+        let orders = Table::new(
+            "orders",
+            vec![
+                Column::new("id", ColumnType::Uuid).primary_key(),
+                Column::new("customer_id", ColumnType::Serial).references("customers", "id"),
+            ],
+        )
+        .unwrap();
+        assert!(orders.rows(1, None).is_err());
+        let mut keys = KeyValues::new();
+        keys.insert(
+            "customers.id".to_string(),
+            vec![Value::Integer(7), Value::Integer(9)],
+        );
+        for row in orders.rows(20, Some(&keys)).unwrap() {
+            assert!(row[1] == Value::Integer(7) || row[1] == Value::Integer(9));
+        }
+    }
+}
@@ -0,0 +1,207 @@
+/*
+Snowflake ID generators
+Twitter/Discord style 64 bit ids: milliseconds since an epoch,
+a worker id and a per millisecond sequence number
+*/
+
+use chrono::{DateTime, TimeZone, Utc};
+
+type Error = String;
+
+/// Twitter's epoch: 2010-11-04T01:42:54.657Z, in unix milliseconds
+pub const TWITTER_EPOCH: i64 = 1_288_834_974_657;
+/// Discord's epoch: 2015-01-01T00:00:00Z, in unix milliseconds
+pub const DISCORD_EPOCH: i64 = 1_420_070_400_000;
+
+/// Generates Snowflake ids of one worker; the layout is, from the high bits,
+/// a zero sign bit, the timestamp, the worker id & the sequence number
+///
+/// ## Example
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use random::database::{SnowflakeGenerator, DISCORD_EPOCH};
+///
+/// let epoch = Utc.timestamp_millis_opt(DISCORD_EPOCH).unwrap();
+/// let mut generator = SnowflakeGenerator::new(Some(&epoch), Some(1), None, None).unwrap();
+/// let time = Utc.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap();
+/// let id = generator.id_at(&time).unwrap();
+/// assert_eq!(generator.decompose(id), (time, 1, 0));
+/// for id in generator.take(3) {
+///     println!("{}", id);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnowflakeGenerator {
+    epoch: i64,
+    worker_id: u64,
+    worker_bits: u32,
+    sequence_bits: u32,
+    last_millis: i64,
+    sequence: u64,
+}
+
+impl SnowflakeGenerator {
+    pub fn new(
+        epoch: Option<&DateTime<Utc>>,
+        worker_id: Option<u64>,
+        worker_bits: Option<u32>,
+        sequence_bits: Option<u32>,
+    ) -> Result<Self, Error> {
+        //! New generator
+        //! - `epoch`: time of id 0 - twitter's epoch if not given
+        //! - `worker_id`: id of the generating worker (datacenter & machine) - 0 if not given
+        //! - `worker_bits`: bits of the worker id - 10 if not given
+        //! - `sequence_bits`: bits of the sequence number - 12 if not given
+        let worker_bits = worker_bits.unwrap_or(10);
+        let sequence_bits = sequence_bits.unwrap_or(12);
+        if worker_bits + sequence_bits > 32 {
+            return Err(String::from(
+                "Error: worker & sequence bits leave less than 31 bits of timestamp",
+            ));
+        }
+        let worker_id = worker_id.unwrap_or(0);
+        if worker_id >> worker_bits != 0 {
+            return Err(format!(
+                "Error: worker id {} does not fit {} bits",
+                worker_id, worker_bits
+            ));
+        }
+        Ok(SnowflakeGenerator {
+            epoch: epoch.map(|e| e.timestamp_millis()).unwrap_or(TWITTER_EPOCH),
+            worker_id,
+            worker_bits,
+            sequence_bits,
+            last_millis: i64::MIN,
+            sequence: 0,
+        })
+    }
+
+    fn timestamp_bits(&self) -> u32 {
+        63 - self.worker_bits - self.sequence_bits
+    }
+
+    fn millis(&self, time: &DateTime<Utc>) -> Result<i64, Error> {
+        // milliseconds since the epoch, if the timestamp bits hold them
+        let millis = time.timestamp_millis() - self.epoch;
+        if millis < 0 || millis >> self.timestamp_bits() != 0 {
+            return Err(format!("Error: {} is out of range of the epoch", time));
+        }
+        Ok(millis)
+    }
+
+    pub fn id_at(&mut self, time: &DateTime<Utc>) -> Result<u64, Error> {
+        //! Id for the given time; consecutive ids of the same
+        //! millisecond get increasing sequence numbers. Times before
+        //! that of the last id are errors, as their ids could repeat
+        let millis = self.millis(time)?;
+        if millis < self.last_millis {
+            return Err(format!(
+                "Error: {} is before the time of the last id, the clock moved backwards",
+                time
+            ));
+        }
+        if millis == self.last_millis {
+            self.sequence += 1;
+            if self.sequence >> self.sequence_bits != 0 {
+                return Err(format!(
+                    "Error: sequence of {} exhausted, {} ids per millisecond at most",
+                    time,
+                    1u64 << self.sequence_bits
+                ));
+            }
+        } else {
+            self.last_millis = millis;
+            self.sequence = 0;
+        }
+        Ok(((millis as u64) << (self.worker_bits + self.sequence_bits))
+            | (self.worker_id << self.sequence_bits)
+            | self.sequence)
+    }
+
+    pub fn next_id(&mut self) -> Result<u64, Error> {
+        //! Id for the current time; waits for the next millisecond
+        //! once the sequence of the current one is exhausted, and for
+        //! the clock to catch up if it moved backwards. Fails when the
+        //! current time is out of range of the epoch, as waiting would
+        //! not help
+        loop {
+            let now = Utc::now();
+            self.millis(&now)?;
+            match self.id_at(&now) {
+                Ok(id) => return Ok(id),
+                Err(_) => std::thread::sleep(std::time::Duration::from_micros(100)),
+            }
+        }
+    }
+
+    pub fn decompose(&self, id: u64) -> (DateTime<Utc>, u64, u64) {
+        //! Time, worker id & sequence number of an id
+        let millis = (id >> (self.worker_bits + self.sequence_bits)) as i64 + self.epoch;
+        let worker_id = (id >> self.sequence_bits) & ((1 << self.worker_bits) - 1);
+        let sequence = id & ((1 << self.sequence_bits) - 1);
+        (
+            Utc.timestamp_millis_opt(millis)
+                .single()
+                .unwrap_or_default(),
+            worker_id,
+            sequence,
+        )
+    }
+}
+
+impl Iterator for SnowflakeGenerator {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        // ends once the current time is out of range of the epoch
+        self.next_id().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snowflake() {
+        // a real tweet id
+        let twitter = SnowflakeGenerator::new(None, None, None, None).unwrap();
+        let (time, worker_id, sequence) = twitter.decompose(1_212_092_628_029_698_048);
+        assert_eq!(time.timestamp_millis(), 1_577_820_376_771);
+        assert_eq!((worker_id, sequence), (327, 0));
+
+        let epoch = Utc.timestamp_millis_opt(DISCORD_EPOCH).unwrap();
+        let mut generator =
+            SnowflakeGenerator::new(Some(&epoch), Some(3), Some(4), Some(2)).unwrap();
+        let time = Utc.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap();
+        let ids = (0..4)
+            .map(|_| generator.id_at(&time).unwrap())
+            .collect::<Vec<u64>>();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(generator.decompose(ids[3]), (time, 3, 3));
+        assert!(generator.id_at(&time).is_err());
+        assert!(generator
+            .id_at(&(epoch - chrono::Duration::seconds(1)))
+            .is_err());
+        // back in time: the ids of that millisecond could repeat
+        let later = time + chrono::Duration::milliseconds(1);
+        let id = generator.id_at(&later).unwrap();
+        assert!(generator.id_at(&time).is_err());
+        assert!(generator.id_at(&later).unwrap() > id);
+
+        assert!(SnowflakeGenerator::new(None, Some(1024), None, None).is_err());
+        let mut generator = SnowflakeGenerator::new(None, None, None, None).unwrap();
+        let ids = generator.by_ref().take(5000).collect::<Vec<u64>>();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+
+        // out of range for good: an error, not an endless wait
+        let future = Utc::now() + chrono::Duration::days(1);
+        let mut generator = SnowflakeGenerator::new(Some(&future), None, None, None).unwrap();
+        assert!(generator.next_id().is_err());
+        assert_eq!(generator.next(), None);
+        // 31 bits of milliseconds last less than 25 days
+        let past = Utc::now() - chrono::Duration::days(100);
+        let mut generator = SnowflakeGenerator::new(Some(&past), None, None, Some(22)).unwrap();
+        assert!(generator.next_id().is_err());
+    }
+}
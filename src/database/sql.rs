@@ -0,0 +1,362 @@
+/*
+SQL generators
+CREATE TABLE & INSERT statements of generated rows for
+PostgreSQL, MySQL & SQLite
+*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::schema::{ColumnType, Row, Table, Value};
+
+type Error = String;
+
+/// rows per INSERT statement by default
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    pub const ALL: &'static [Dialect] = &[Dialect::Postgres, Dialect::MySql, Dialect::Sqlite];
+
+    pub fn quote_identifier(&self, identifier: &str) -> String {
+        match self {
+            Dialect::MySql => format!("`{}`", identifier.replace('`', "``")),
+            _ => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        }
+    }
+
+    pub fn quote_string(&self, text: &str) -> String {
+        //! String literal; MySQL also treats backslashes as escapes
+        match self {
+            Dialect::MySql => format!("'{}'", text.replace('\\', "\\\\").replace('\'', "''")),
+            _ => format!("'{}'", text.replace('\'', "''")),
+        }
+    }
+
+    pub fn literal(&self, value: &Value) -> String {
+        //! A value as it is written in a statement
+        match value {
+            Value::Null => String::from("NULL"),
+            Value::Boolean(x) => match self {
+                Dialect::Postgres => x.to_string().to_ascii_uppercase(),
+                _ => (*x as u8).to_string(),
+            },
+            Value::Integer(x) => x.to_string(),
+            Value::Float(x) if x.is_finite() => format!("{:?}", x),
+            Value::Float(_) => String::from("NULL"),
+            Value::Decimal(x) => x.clone(),
+            value => self.quote_string(&value.to_string()),
+        }
+    }
+
+    pub fn column_type(&self, column_type: &ColumnType) -> String {
+        //! Type of a column in a CREATE TABLE statement
+        let varchar = |length: usize| match self {
+            Dialect::Sqlite => String::from("TEXT"),
+            _ => format!("VARCHAR({})", length),
+        };
+        match column_type {
+            ColumnType::Serial => match self {
+                Dialect::Postgres => String::from("SERIAL"),
+                Dialect::MySql => String::from("INT AUTO_INCREMENT"),
+                Dialect::Sqlite => String::from("INTEGER"),
+            },
+            ColumnType::Integer { .. } => match self {
+                Dialect::Sqlite => String::from("INTEGER"),
+                _ => String::from("BIGINT"),
+            },
+            ColumnType::Float { .. } => match self {
+                Dialect::Postgres => String::from("DOUBLE PRECISION"),
+                Dialect::MySql => String::from("DOUBLE"),
+                Dialect::Sqlite => String::from("REAL"),
+            },
+            ColumnType::Decimal { precision, scale } => match self {
+                Dialect::Sqlite => String::from("NUMERIC"),
+                _ => format!("DECIMAL({}, {})", precision, scale),
+            },
+            ColumnType::Boolean => match self {
+                Dialect::Sqlite => String::from("INTEGER"),
+                _ => String::from("BOOLEAN"),
+            },
+            ColumnType::Text(Some(length)) => varchar(*length),
+            ColumnType::Text(None) => String::from("TEXT"),
+            ColumnType::Uuid => match self {
+                Dialect::Postgres => String::from("UUID"),
+                Dialect::MySql => String::from("CHAR(36)"),
+                Dialect::Sqlite => String::from("TEXT"),
+            },
+            ColumnType::Ulid => match self {
+                Dialect::Sqlite => String::from("TEXT"),
+                _ => String::from("CHAR(26)"),
+            },
+            ColumnType::Date { .. } => match self {
+                Dialect::Sqlite => String::from("TEXT"),
+                _ => String::from("DATE"),
+            },
+            ColumnType::Timestamp { .. } => match self {
+                Dialect::Postgres => String::from("TIMESTAMP"),
+                Dialect::MySql => String::from("DATETIME(6)"),
+                Dialect::Sqlite => String::from("TEXT"),
+            },
+            ColumnType::Enum(values) => match self {
+                Dialect::MySql => format!(
+                    "ENUM({})",
+                    values
+                        .iter()
+                        .map(|v| self.quote_string(v))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                _ => String::from("TEXT"),
+            },
+            ColumnType::FirstName | ColumnType::LastName => varchar(64),
+            ColumnType::FullName | ColumnType::Company => varchar(128),
+            ColumnType::Username => varchar(64),
+            ColumnType::Email => varchar(254),
+            ColumnType::PhoneNumber => varchar(32),
+            ColumnType::IpAddress => match self {
+                Dialect::Postgres => String::from("INET"),
+                _ => varchar(45),
+            },
+            ColumnType::MacAddress => match self {
+                Dialect::Postgres => String::from("MACADDR"),
+                _ => varchar(17),
+            },
+        }
+    }
+
+    pub fn create_table(&self, table: &Table) -> String {
+        //! CREATE TABLE statement of a table
+        let mut definitions = Vec::new();
+        for column in &table.columns {
+            // a serial foreign key holds the integers of the referenced serial
+            let column_type = match (&column.column_type, &column.references) {
+                (ColumnType::Serial, Some(_)) => match self {
+                    Dialect::MySql => String::from("INT"),
+                    _ => String::from("INTEGER"),
+                },
+                (column_type, _) => self.column_type(column_type),
+            };
+            let mut definition = format!("{} {}", self.quote_identifier(&column.name), column_type);
+            if column.primary_key {
+                definition.push_str(" PRIMARY KEY");
+            } else {
+                if !column.nullable {
+                    definition.push_str(" NOT NULL");
+                }
+                if column.unique {
+                    definition.push_str(" UNIQUE");
+                }
+            }
+            if let (ColumnType::Enum(values), Dialect::Postgres | Dialect::Sqlite) =
+                (&column.column_type, self)
+            {
+                definition.push_str(&format!(
+                    " CHECK ({} IN ({}))",
+                    self.quote_identifier(&column.name),
+                    values
+                        .iter()
+                        .map(|v| self.quote_string(v))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ));
+            }
+            definitions.push(definition);
+        }
+        // table level foreign keys: MySQL ignores them inline
+        for column in &table.columns {
+            if let Some(foreign_key) = &column.references {
+                definitions.push(format!(
+                    "FOREIGN KEY ({}) REFERENCES {} ({})",
+                    self.quote_identifier(&column.name),
+                    self.quote_identifier(&foreign_key.table),
+                    self.quote_identifier(&foreign_key.column)
+                ));
+            }
+        }
+        format!(
+            "CREATE TABLE {} (\n    {}\n);\n",
+            self.quote_identifier(&table.name),
+            definitions.join(",\n    ")
+        )
+    }
+
+    pub fn insert(&self, table: &Table, rows: &[Row], batch_size: Option<usize>) -> String {
+        //! INSERT statements of rows, followed by those moving the
+        //! sequences of serial columns past the inserted values
+        //! - `batch_size`: rows per (multi row) statement - 500 if not given
+        let batch_size = batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+        let columns = table
+            .columns
+            .iter()
+            .map(|c| self.quote_identifier(&c.name))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let mut statements = String::new();
+        for batch in rows.chunks(batch_size) {
+            let values = batch
+                .iter()
+                .map(|row| {
+                    format!(
+                        "({})",
+                        row.iter()
+                            .map(|v| self.literal(v))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(",\n    ");
+            statements.push_str(&format!(
+                "INSERT INTO {} ({}) VALUES\n    {};\n",
+                self.quote_identifier(&table.name),
+                columns,
+                values
+            ));
+        }
+        statements.push_str(&self.reset_serials(table, rows));
+        statements
+    }
+
+    pub fn reset_serials(&self, table: &Table, rows: &[Row]) -> String {
+        //! Statements moving the sequences of serial columns past the
+        //! values inserted explicitly, so that the next row the database
+        //! numbers itself does not collide; SQLite needs none
+        let mut statements = String::new();
+        for (index, column) in table.columns.iter().enumerate() {
+            // a serial foreign key is a plain integer without a sequence
+            if column.column_type != ColumnType::Serial || column.references.is_some() {
+                continue;
+            }
+            let max = rows
+                .iter()
+                .filter_map(|row| match row.get(index) {
+                    Some(Value::Integer(x)) => Some(*x),
+                    _ => None,
+                })
+                .max();
+            let max = match max {
+                Some(max) => max,
+                None => continue,
+            };
+            match self {
+                Dialect::Postgres => statements.push_str(&format!(
+                    "SELECT setval(pg_get_serial_sequence({}, {}), MAX({})) FROM {};\n",
+                    self.quote_string(&self.quote_identifier(&table.name)),
+                    self.quote_string(&column.name),
+                    self.quote_identifier(&column.name),
+                    self.quote_identifier(&table.name)
+                )),
+                Dialect::MySql => statements.push_str(&format!(
+                    "ALTER TABLE {} AUTO_INCREMENT = {};\n",
+                    self.quote_identifier(&table.name),
+                    max.saturating_add(1)
+                )),
+                Dialect::Sqlite => {}
+            }
+        }
+        statements
+    }
+}
+
+impl fmt::Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Dialect::Postgres => "postgres",
+            Dialect::MySql => "mysql",
+            Dialect::Sqlite => "sqlite",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "postgres" | "postgresql" | "pg" => Ok(Dialect::Postgres),
+            "mysql" | "mariadb" => Ok(Dialect::MySql),
+            "sqlite" | "sqlite3" => Ok(Dialect::Sqlite),
+            _ => Err(format!("Error: unknown sql dialect '{}'", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::Column;
+
+    #[test]
+    fn test_statements() {
+        let table = Table::new(
+            "orders",
+            vec![
+                Column::new("id", ColumnType::Serial).primary_key(),
+                Column::new("customer_id", ColumnType::Serial).references("customers", "id"),
+                Column::new("state", ColumnType::Enum(vec!["new".into(), "paid".into()])),
+                Column::new("note", ColumnType::Text(Some(40))).nullable(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            Dialect::Postgres.create_table(&table),
+            "CREATE TABLE \"orders\" (\n    \"id\" SERIAL PRIMARY KEY,\n    \"customer_id\" INTEGER NOT NULL,\n    \"state\" TEXT NOT NULL CHECK (\"state\" IN ('new', 'paid')),\n    \"note\" VARCHAR(40),\n    FOREIGN KEY (\"customer_id\") REFERENCES \"customers\" (\"id\")\n);\n"
+        );
+        assert!(Dialect::MySql
+            .create_table(&table)
+            .contains("`state` ENUM('new', 'paid') NOT NULL"));
+
+        let rows = vec![
+            vec![
+                Value::Integer(1),
+                Value::Integer(7),
+                Value::Text("new".into()),
+                Value::Text("it's a \\ test".into()),
+            ],
+            vec![
+                Value::Integer(2),
+                Value::Integer(7),
+                Value::Text("paid".into()),
+                Value::Null,
+            ],
+        ];
+        assert_eq!(
+            Dialect::Sqlite.insert(&table, &rows, None),
+            "INSERT INTO \"orders\" (\"id\", \"customer_id\", \"state\", \"note\") VALUES\n    (1, 7, 'new', 'it''s a \\ test'),\n    (2, 7, 'paid', NULL);\n"
+        );
+        assert!(Dialect::MySql
+            .insert(&table, &rows, Some(1))
+            .contains("'it''s a \\\\ test'"));
+        assert_eq!(
+            Dialect::Postgres
+                .insert(&table, &rows, Some(1))
+                .matches("INSERT")
+                .count(),
+            2
+        );
+        // only the primary key owns a sequence
+        assert!(Dialect::Postgres.insert(&table, &rows, None).ends_with(
+            ";\nSELECT setval(pg_get_serial_sequence('\"orders\"', 'id'), MAX(\"id\")) FROM \"orders\";\n"
+        ));
+        assert!(Dialect::MySql
+            .insert(&table, &rows, None)
+            .ends_with(";\nALTER TABLE `orders` AUTO_INCREMENT = 3;\n"));
+        assert_eq!(Dialect::Postgres.reset_serials(&table, &[]), "");
+
+        assert_eq!(Dialect::Postgres.literal(&Value::Boolean(true)), "TRUE");
+        assert_eq!(Dialect::MySql.literal(&Value::Boolean(true)), "1");
+        assert_eq!(Dialect::Sqlite.literal(&Value::Float(1.0)), "1.0");
+        for dialect in Dialect::ALL {
+            assert_eq!(dialect.to_string().parse::<Dialect>(), Ok(*dialect));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+/*
+SQLite writer
+Generated tables written straight into a SQLite database file,
+eg: a fixture database for tests that needs no database server
+*/
+
+use std::path::Path;
+
+use rusqlite::types::Value as SqliteValue;
+use rusqlite::{params_from_iter, Connection};
+
+use super::dataset::{Dataset, TableRows};
+use super::schema::Value;
+use super::sql::Dialect;
+
+type Error = String;
+
+fn sqlite_value(value: &Value) -> SqliteValue {
+    match value {
+        Value::Null => SqliteValue::Null,
+        Value::Boolean(x) => SqliteValue::Integer(*x as i64),
+        Value::Integer(x) => SqliteValue::Integer(*x),
+        Value::Float(x) if x.is_finite() => SqliteValue::Real(*x),
+        Value::Float(_) => SqliteValue::Null,
+        // NUMERIC columns turn well formed text into numbers
+        value => SqliteValue::Text(value.to_string()),
+    }
+}
+
+pub fn write_sqlite<P: AsRef<Path>>(path: P, tables: &[TableRows]) -> Result<usize, Error> {
+    /*
+    Write generated tables into a SQLite database file; the tables
+    are created in the given order and must not exist yet. Foreign
+    keys are enforced, so parent tables have to come first. It is all
+    one transaction: when anything fails, nothing is written
+    :param path: the database file, created if it does not exist
+    :param tables: generated tables, eg: of `Dataset::generate`
+    :return: number of rows inserted
+    */
+    let mut connection = Connection::open(path.as_ref())
+        .map_err(|e| format!("Error: cannot open '{}': {}", path.as_ref().display(), e))?;
+    connection
+        .execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Error: {}", e))?;
+
+    let dialect = Dialect::Sqlite;
+    let mut inserted = 0;
+    let transaction = connection
+        .transaction()
+        .map_err(|e| format!("Error: {}", e))?;
+    for generated in tables {
+        let table = &generated.table;
+        transaction
+            .execute_batch(&dialect.create_table(table))
+            .map_err(|e| format!("Error: cannot create '{}': {}", table.name, e))?;
+
+        let statement = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            dialect.quote_identifier(&table.name),
+            table
+                .columns
+                .iter()
+                .map(|c| dialect.quote_identifier(&c.name))
+                .collect::<Vec<String>>()
+                .join(", "),
+            vec!["?"; table.columns.len()].join(", ")
+        );
+        let mut insert = transaction
+            .prepare_cached(&statement)
+            .map_err(|e| format!("Error: {}", e))?;
+        for row in &generated.rows {
+            insert
+                .execute(params_from_iter(row.iter().map(sqlite_value)))
+                .map_err(|e| format!("Error: cannot insert into '{}': {}", table.name, e))?;
+        }
+        inserted += generated.rows.len();
+    }
+    // dropped without a commit on any error above: rolled back
+    transaction.commit().map_err(|e| format!("Error: {}", e))?;
+    Ok(inserted)
+}
+
+impl Dataset {
+    pub fn sqlite<P: AsRef<Path>>(&self, path: P) -> Result<Vec<TableRows>, Error> {
+        //! Generate every table into a SQLite database file, in one transaction
+        //! - `path`: the database file; its tables must not exist yet
+        //!
+        //! Returns the generated tables, eg: to check the database against
+        let tables = self.generate()?;
+        write_sqlite(path, &tables)?;
+        Ok(tables)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{Cardinality, Column, ColumnType, Table};
+
+    #[test]
+    fn test_write_sqlite() {
+        let customers = Table::new(
+            "customers",
+            vec![
+                Column::new("id", ColumnType::Serial).primary_key(),
+                Column::new("name", ColumnType::FullName),
+                Column::new("active", ColumnType::Boolean),
+                Column::new(
+                    "balance",
+                    ColumnType::Decimal {
+                        precision: 8,
+                        scale: 2,
+                    },
+                ),
+            ],
+        )
+        .unwrap();
+        let orders = Table::new(
+            "orders",
+            vec![
+                Column::new("id", ColumnType::Uuid).primary_key(),
+                Column::new("customer_id", ColumnType::Serial).references("customers", "id"),
+                Column::new("note", ColumnType::Text(Some(20))).nullable(),
+            ],
+        )
+        .unwrap();
+        let mut dataset = Dataset::new(Some(1));
+        dataset
+            .table(customers, 25)
+            .children(orders, "customer_id", Cardinality::Between(0, 3));
+
+        let path = std::env::temp_dir().join(format!("random-fixture-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let tables = dataset.sqlite(&path).unwrap();
+
+        let connection = Connection::open(&path).unwrap();
+        let count = |table: &str| -> usize {
+            connection
+                .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |r| {
+                    r.get::<_, i64>(0)
+                })
+                .unwrap() as usize
+        };
+        assert_eq!(count("customers"), 25);
+        assert_eq!(count("orders"), tables[1].rows.len());
+        let orphans: i64 = connection
+            .query_row(
+                "SELECT COUNT(*) FROM orders WHERE customer_id NOT IN (SELECT id FROM customers)",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(orphans, 0);
+        // the tables exist already
+        assert!(write_sqlite(&path, &tables).is_err());
+        drop(connection);
+        let _ = std::fs::remove_file(&path);
+
+        // children first: their rows violate the foreign key, and the
+        // tables created before that are rolled back with them
+        let reversed = tables.iter().rev().cloned().collect::<Vec<TableRows>>();
+        assert!(reversed[0].rows.iter().any(|row| row[1] != Value::Null));
+        assert!(write_sqlite(&path, &reversed).is_err());
+        let connection = Connection::open(&path).unwrap();
+        let created: i64 = connection
+            .query_row("SELECT COUNT(*) FROM sqlite_master", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(created, 0);
+        drop(connection);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,128 @@
+/*
+ULID generators
+48 bit unix milliseconds & 80 random bits, written as 26 characters
+of Crockford's base32 that sort by time
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeZone, Utc};
+use rand::Rng;
+
+use super::{decode_base, encode_base};
+
+type Error = String;
+
+const CROCKFORD_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// ## Example
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use random::database::Ulid;
+///
+/// let time = Utc.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap();
+/// let ulid = Ulid::with_time(&time).unwrap();
+/// assert_eq!(ulid.timestamp(), time);
+/// assert_eq!(ulid.to_string().parse::<Ulid>(), Ok(ulid));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    pub fn generate() -> Self {
+        //! New ULID for the current time
+        Ulid::with_time_rng(&mut rand::thread_rng(), &Utc::now()).unwrap_or(Ulid(0))
+    }
+
+    pub fn with_time(time: &DateTime<Utc>) -> Result<Self, Error> {
+        //! New ULID for the given time (1970 to 10889)
+        Ulid::with_time_rng(&mut rand::thread_rng(), time)
+    }
+
+    pub(crate) fn with_time_rng<R: Rng + ?Sized>(
+        rng: &mut R,
+        time: &DateTime<Utc>,
+    ) -> Result<Self, Error> {
+        let millis = time.timestamp_millis();
+        if !(0..1 << 48).contains(&millis) {
+            return Err(format!("Error: {} is out of range of a ULID", time));
+        }
+        let random = rng.gen::<u128>() & ((1 << 80) - 1);
+        Ok(Ulid(((millis as u128) << 80) | random))
+    }
+
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Ulid(u128::from_be_bytes(bytes))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        //! Creation time, to the millisecond
+        Utc.timestamp_millis_opt((self.0 >> 80) as i64)
+            .single()
+            .unwrap_or_default()
+    }
+
+    pub fn random(&self) -> u128 {
+        //! The 80 random bits
+        self.0 & ((1 << 80) - 1)
+    }
+}
+
+impl fmt::Display for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = encode_base(&self.to_bytes(), CROCKFORD_ALPHABET);
+        write!(f, "{:0>26}", encoded)
+    }
+}
+
+impl FromStr for Ulid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // crockford's base32 is case insensitive and forgives I, L & O
+        if s.len() != 26 {
+            return Err(format!("Error: invalid ULID '{}'", s));
+        }
+        let normalized: String = s
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'I' | 'L' => '1',
+                'O' => '0',
+                c => c,
+            })
+            .collect();
+        let bytes = decode_base(&normalized, CROCKFORD_ALPHABET, 16)
+            .map_err(|_| format!("Error: invalid ULID '{}'", s))?;
+        let bytes = <[u8; 16]>::try_from(bytes.as_slice())
+            .map_err(|_| format!("Error: invalid ULID '{}'", s))?;
+        Ok(Ulid::from_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ulid() {
+        let ulid: Ulid = "01ARZ3NDEKTSV4RRFFQ69G5FAV".parse().unwrap();
+        assert_eq!(ulid.timestamp().timestamp_millis(), 1469922850259);
+        assert_eq!(ulid.to_string(), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+        assert_eq!("01arz3ndektsv4rrffq69g5fav".parse::<Ulid>(), Ok(ulid));
+        assert!("81ARZ3NDEKTSV4RRFFQ69G5FAV".parse::<Ulid>().is_err());
+        assert!("01ARZ3NDEKTSV4RRFFQ69G5FA".parse::<Ulid>().is_err());
+
+        let earlier = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let later = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 1).unwrap();
+        let a = Ulid::with_time(&earlier).unwrap();
+        let b = Ulid::with_time(&later).unwrap();
+        assert!(a < b && a.to_string() < b.to_string());
+        assert_eq!(Ulid::generate().to_string().len(), 26);
+    }
+}
@@ -0,0 +1,126 @@
+/*
+Daylight saving time policies
+What a local (wall clock) time becomes when it does not exist,
+in the gap of a change to summer time, or exists twice, in the
+overlap of a change back to winter time
+*/
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone};
+
+type Error = String;
+
+/// longest stretch of local times that can be skipped by a change of offset
+const MAX_GAP_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DstPolicy {
+    /// overlaps: the first of the two times; gaps: moved
+    /// forward by the length of the gap, eg: 02:30 to 03:30
+    #[default]
+    Earliest,
+    /// overlaps: the second of the two times; gaps: moved
+    /// forward by the length of the gap, eg: 02:30 to 03:30
+    Latest,
+    /// times in gaps & overlaps are left out
+    Skip,
+    /// times in gaps & overlaps are errors
+    Strict,
+}
+
+impl DstPolicy {
+    pub fn resolve<Tz: TimeZone>(
+        &self,
+        timezone: &Tz,
+        local: &NaiveDateTime,
+    ) -> Result<Option<DateTime<Tz>>, Error> {
+        //! The time of a local time in a time zone; None when
+        //! the policy leaves it out
+        match timezone.from_local_datetime(local) {
+            LocalResult::Single(time) => Ok(Some(time)),
+            LocalResult::Ambiguous(earliest, latest) => match self {
+                DstPolicy::Earliest => Ok(Some(earliest)),
+                DstPolicy::Latest => Ok(Some(latest)),
+                DstPolicy::Skip => Ok(None),
+                DstPolicy::Strict => Err(format!("Error: {} is ambiguous in the time zone", local)),
+            },
+            LocalResult::None => match self {
+                DstPolicy::Earliest | DstPolicy::Latest => shift_forward(timezone, local).map(Some),
+                DstPolicy::Skip => Ok(None),
+                DstPolicy::Strict => {
+                    Err(format!("Error: {} does not exist in the time zone", local))
+                }
+            },
+        }
+    }
+}
+
+fn shift_forward<Tz: TimeZone>(
+    timezone: &Tz,
+    local: &NaiveDateTime,
+) -> Result<DateTime<Tz>, Error> {
+    // read with the offset from before the gap, a local time in the gap
+    // is as far past its end as it is past its start
+    let mut before = *local;
+    for _ in 0..MAX_GAP_HOURS * 4 {
+        before -= Duration::minutes(15);
+        let offset = match timezone.offset_from_local_datetime(&before) {
+            LocalResult::Single(offset) => offset,
+            LocalResult::Ambiguous(_, latest) => latest,
+            LocalResult::None => continue,
+        };
+        let utc = *local - offset.fix();
+        return Ok(timezone.from_utc_datetime(&utc));
+    }
+    Err(format!("Error: {} does not exist in the time zone", local))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use chrono_tz::Europe::Berlin;
+
+    #[test]
+    fn test_policies() {
+        // summer time in Berlin: 02:00 to 02:59 on 2023-03-26 do not exist
+        let gap = NaiveDate::from_ymd_opt(2023, 3, 26)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        let shifted = DstPolicy::Earliest.resolve(&Berlin, &gap).unwrap().unwrap();
+        assert_eq!(shifted.naive_local(), gap + Duration::hours(1));
+        assert_eq!(
+            shifted.with_timezone(&Utc).naive_utc(),
+            gap - Duration::hours(1)
+        );
+        assert_eq!(DstPolicy::Skip.resolve(&Berlin, &gap), Ok(None));
+        assert!(DstPolicy::Strict.resolve(&Berlin, &gap).is_err());
+
+        // winter time: 02:00 to 02:59 on 2023-10-29 happen twice
+        let overlap = NaiveDate::from_ymd_opt(2023, 10, 29)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        let earliest = DstPolicy::Earliest
+            .resolve(&Berlin, &overlap)
+            .unwrap()
+            .unwrap();
+        let latest = DstPolicy::Latest
+            .resolve(&Berlin, &overlap)
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest - earliest, Duration::hours(1));
+        assert_eq!(earliest.naive_local(), latest.naive_local());
+        assert_eq!(DstPolicy::Skip.resolve(&Berlin, &overlap), Ok(None));
+        assert!(DstPolicy::Strict.resolve(&Berlin, &overlap).is_err());
+
+        let regular = NaiveDate::from_ymd_opt(2023, 3, 15)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        assert!(DstPolicy::Strict
+            .resolve(&Berlin, &regular)
+            .unwrap()
+            .is_some());
+    }
+}
@@ -0,0 +1,318 @@
+/*
+Timestamp formats
+Times written the way logs & wire protocols write them, and
+deliberately malformed variants of those to fuzz parsers with
+*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Timelike, Utc};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+type Error = String;
+
+/// 100 ns intervals from 1601-01-01 to 1970-01-01
+const FILETIME_UNIX_EPOCH: i128 = 116_444_736_000_000_000;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimestampFormat {
+    /// 2023-10-18T07:35:01.123Z
+    Rfc3339,
+    /// Wed, 18 Oct 2023 07:35:01 +0000
+    Rfc2822,
+    /// syslog (RFC 3164): Oct 18 07:35:01
+    Syslog,
+    /// apache common log format: 18/Oct/2023:07:35:01 +0000
+    ApacheClf,
+    /// windows FILETIME: 100 ns intervals since 1601-01-01
+    FileTime,
+    /// unix epoch seconds
+    UnixSeconds,
+    /// unix epoch milliseconds
+    UnixMillis,
+    /// unix epoch microseconds
+    UnixMicros,
+    /// unix epoch nanoseconds
+    UnixNanos,
+    /// excel serial date (1900 date system): days since 1899-12-30
+    ExcelSerial,
+    /// SystemTime of windows events: 2023-10-18T07:35:01.1234567Z
+    WindowsSystemTime,
+}
+
+impl TimestampFormat {
+    pub const ALL: &'static [TimestampFormat] = &[
+        TimestampFormat::Rfc3339,
+        TimestampFormat::Rfc2822,
+        TimestampFormat::Syslog,
+        TimestampFormat::ApacheClf,
+        TimestampFormat::FileTime,
+        TimestampFormat::UnixSeconds,
+        TimestampFormat::UnixMillis,
+        TimestampFormat::UnixMicros,
+        TimestampFormat::UnixNanos,
+        TimestampFormat::ExcelSerial,
+        TimestampFormat::WindowsSystemTime,
+    ];
+
+    fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            TimestampFormat::FileTime
+                | TimestampFormat::UnixSeconds
+                | TimestampFormat::UnixMillis
+                | TimestampFormat::UnixMicros
+                | TimestampFormat::UnixNanos
+                | TimestampFormat::ExcelSerial
+        )
+    }
+
+    pub fn format<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        //! A time in the format; syslog & excel serials have no
+        //! time zone and are written in the local time of `time`,
+        //! windows SystemTimes are always UTC
+        let nanoseconds = i128::from(time.timestamp()) * 1_000_000_000
+            + i128::from(time.timestamp_subsec_nanos());
+        match self {
+            TimestampFormat::Rfc3339 => time.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            // to_rfc2822 panics outside the years 0 to 9999
+            TimestampFormat::Rfc2822 if (0..=9999).contains(&time.year()) => time.to_rfc2822(),
+            TimestampFormat::Rfc2822 => time.format("%a, %-d %b %Y %H:%M:%S %z").to_string(),
+            TimestampFormat::Syslog => time.format("%b %e %H:%M:%S").to_string(),
+            TimestampFormat::ApacheClf => time.format("%d/%b/%Y:%H:%M:%S %z").to_string(),
+            TimestampFormat::FileTime => {
+                (nanoseconds.div_euclid(100) + FILETIME_UNIX_EPOCH).to_string()
+            }
+            TimestampFormat::UnixSeconds => time.timestamp().to_string(),
+            TimestampFormat::UnixMillis => nanoseconds.div_euclid(1_000_000).to_string(),
+            TimestampFormat::UnixMicros => nanoseconds.div_euclid(1_000).to_string(),
+            TimestampFormat::UnixNanos => nanoseconds.to_string(),
+            TimestampFormat::ExcelSerial => {
+                let local = time.naive_local();
+                let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .unwrap_or_default();
+                let elapsed = local.signed_duration_since(epoch);
+                let days = elapsed.num_milliseconds() as f64 / 1000.0 / SECONDS_PER_DAY;
+                let serial = format!("{:.8}", days);
+                serial
+                    .trim_end_matches('0')
+                    .trim_end_matches('.')
+                    .to_string()
+            }
+            TimestampFormat::WindowsSystemTime => {
+                let utc = time.with_timezone(&Utc);
+                format!(
+                    "{}.{:07}Z",
+                    utc.format("%Y-%m-%dT%H:%M:%S"),
+                    utc.nanosecond() % 1_000_000_000 / 100
+                )
+            }
+        }
+    }
+
+    pub fn malformed<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        //! A deliberately broken variant of a time in the format:
+        //! truncated, out of range fields, wrong separators, stray
+        //! characters, stray whitespace or overflowing numbers
+        let valid = self.format(time);
+        let mut rng = rand::thread_rng();
+        let mut malformed = malform(&mut rng, &valid, self.is_numeric());
+        if malformed == valid {
+            // eg: a separator swapped for itself
+            malformed = valid[..valid.len() / 2].to_string();
+        }
+        malformed
+    }
+
+    pub fn fuzz<Tz: TimeZone>(&self, time: &DateTime<Tz>, malformed_probability: f64) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        //! A time in the format, malformed with the given probability
+        if rand::thread_rng().gen_bool(malformed_probability.clamp(0.0, 1.0)) {
+            self.malformed(time)
+        } else {
+            self.format(time)
+        }
+    }
+}
+
+fn malform<R: Rng + ?Sized>(rng: &mut R, valid: &str, numeric: bool) -> String {
+    let chars = valid.chars().collect::<Vec<char>>();
+    let position = rng.gen_range(0..=chars.len());
+    let (head, tail) = (
+        chars[..position].iter().collect::<String>(),
+        chars[position..].iter().collect::<String>(),
+    );
+    match rng.gen_range(0..6) {
+        // truncated
+        0 => chars[..rng.gen_range(0..chars.len().max(1))]
+            .iter()
+            .collect(),
+        // a stray character
+        1 => {
+            let stray = *['x', '?', '\u{fffd}', '\0', '#', 'é']
+                .choose(rng)
+                .unwrap_or(&'x');
+            format!("{}{}{}", head, stray, tail)
+        }
+        // stray whitespace
+        2 => match rng.gen_range(0..3) {
+            0 => format!(" {}", valid),
+            1 => format!("{}\t", valid),
+            _ => format!("{}  {}", head, tail),
+        },
+        // numbers: negative, overflowing or fractional
+        3 if numeric => match rng.gen_range(0..3) {
+            0 => format!("-{}", valid),
+            1 => format!("{}99999999999999999999", valid),
+            _ => format!("{}.5e", valid),
+        },
+        // fields: a run of digits turned out of range, eg: month 99
+        3 => {
+            let mut out = String::new();
+            let runs = digit_runs(&chars);
+            let (start, end) = runs.choose(rng).cloned().unwrap_or((0, 0));
+            for (i, c) in chars.iter().enumerate() {
+                out.push(if i >= start && i < end { '9' } else { *c });
+            }
+            out
+        }
+        // separators swapped
+        4 if !numeric => chars
+            .iter()
+            .map(|c| match c {
+                '-' => '/',
+                '/' => '-',
+                ':' => '.',
+                'T' => ' ',
+                ',' => ';',
+                c => *c,
+            })
+            .collect(),
+        // letters in the wrong case, eg: oct 18 07:35:01
+        _ if !numeric => valid.to_lowercase(),
+        _ => format!("0x{}", valid),
+    }
+}
+
+fn digit_runs(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, c) in chars.iter().enumerate() {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                runs.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        runs.push((s, chars.len()));
+    }
+    runs
+}
+
+impl fmt::Display for TimestampFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TimestampFormat::Rfc3339 => "rfc3339",
+            TimestampFormat::Rfc2822 => "rfc2822",
+            TimestampFormat::Syslog => "syslog",
+            TimestampFormat::ApacheClf => "clf",
+            TimestampFormat::FileTime => "filetime",
+            TimestampFormat::UnixSeconds => "unix",
+            TimestampFormat::UnixMillis => "unix_ms",
+            TimestampFormat::UnixMicros => "unix_us",
+            TimestampFormat::UnixNanos => "unix_ns",
+            TimestampFormat::ExcelSerial => "excel",
+            TimestampFormat::WindowsSystemTime => "systemtime",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for TimestampFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase().replace('-', "_");
+        TimestampFormat::ALL
+            .iter()
+            .find(|f| f.to_string() == name)
+            .copied()
+            .ok_or_else(|| format!("Error: unknown timestamp format '{}'", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_formats() {
+        let time = Utc
+            .with_ymd_and_hms(2023, 10, 8, 7, 35, 1)
+            .unwrap()
+            .with_nanosecond(123_456_789)
+            .unwrap();
+        let expected = [
+            "2023-10-08T07:35:01.123456789Z",
+            "Sun, 8 Oct 2023 07:35:01 +0000",
+            "Oct  8 07:35:01",
+            "08/Oct/2023:07:35:01 +0000",
+            "133412241011234567",
+            "1696750501",
+            "1696750501123",
+            "1696750501123456",
+            "1696750501123456789",
+            "45207.31598522",
+            "2023-10-08T07:35:01.1234567Z",
+        ];
+        for (format, expected) in TimestampFormat::ALL.iter().zip(expected.iter()) {
+            assert_eq!(format.format(&time), *expected, "{}", format);
+            assert_eq!(format.to_string().parse::<TimestampFormat>(), Ok(*format));
+        }
+
+        let tokyo = time.with_timezone(&FixedOffset::east_opt(9 * 3600).unwrap());
+        assert_eq!(
+            TimestampFormat::ApacheClf.format(&tokyo),
+            "08/Oct/2023:16:35:01 +0900"
+        );
+        assert_eq!(
+            TimestampFormat::WindowsSystemTime.format(&tokyo),
+            "2023-10-08T07:35:01.1234567Z"
+        );
+        let before_epoch = Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(TimestampFormat::UnixMillis.format(&before_epoch), "-1000");
+        let far_future = Utc.with_ymd_and_hms(12345, 6, 7, 8, 9, 10).unwrap();
+        assert_eq!(
+            TimestampFormat::Rfc2822.format(&far_future),
+            "Thu, 7 Jun +12345 08:09:10 +0000"
+        );
+        let before_year_zero = Utc.with_ymd_and_hms(-1, 6, 7, 8, 9, 10).unwrap();
+        assert!(TimestampFormat::Rfc2822
+            .format(&before_year_zero)
+            .contains(" Jun -0001 "));
+
+        for format in TimestampFormat::ALL {
+            for _ in 0..50 {
+                assert_ne!(format.malformed(&time), format.format(&time));
+            }
+            assert_eq!(format.fuzz(&time, 0.0), format.format(&time));
+        }
+    }
+}
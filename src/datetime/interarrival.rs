@@ -0,0 +1,175 @@
+/*
+Inter-arrival distributions
+The gaps between consecutive generated times: fixed intervals,
+uniform gaps, a poisson process, log-normal gaps & pareto bursts
+*/
+
+use chrono::Duration;
+use rand::Rng;
+use rand_distr::{Distribution, Exp, LogNormal, Pareto};
+
+type Error = String;
+
+/// How far apart consecutive times are
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterArrival {
+    /// always the same gap, eg: a metric scraped every 15 seconds
+    Fixed(Duration),
+    /// any gap from the first (included) to the second (excluded)
+    Uniform(Duration, Duration),
+    /// exponential gaps of the given mean: a poisson process,
+    /// eg: independent requests to a web server
+    Exponential(Duration),
+    /// log-normal gaps: mostly around the median with a long tail,
+    /// eg: think times of users
+    LogNormal { median: Duration, sigma: f64 },
+    /// pareto gaps of at least `min`: bursts of close events
+    /// separated by long quiet periods; a smaller `alpha` is burstier
+    Pareto { min: Duration, alpha: f64 },
+}
+
+impl Default for InterArrival {
+    fn default() -> Self {
+        InterArrival::Uniform(Duration::zero(), Duration::seconds(15))
+    }
+}
+
+fn nanoseconds(duration: &Duration) -> f64 {
+    duration.num_nanoseconds().unwrap_or(i64::MAX) as f64
+}
+
+impl InterArrival {
+    pub fn validate(&self) -> Result<(), Error> {
+        //! Check the parameters: gaps can never be negative
+        //! and the distribution has to move time forward
+        let positive = |duration: &Duration| *duration > Duration::zero();
+        let valid = match self {
+            InterArrival::Fixed(gap) => positive(gap),
+            InterArrival::Uniform(min, max) => *min >= Duration::zero() && min < max,
+            InterArrival::Exponential(mean) => positive(mean),
+            InterArrival::LogNormal { median, sigma } => {
+                positive(median) && sigma.is_finite() && *sigma >= 0.0
+            }
+            InterArrival::Pareto { min, alpha } => {
+                positive(min) && alpha.is_finite() && *alpha > 0.0
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!(
+                "Error: invalid inter-arrival distribution {:?}",
+                self
+            ))
+        }
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        //! Average gap; None when it is infinite (pareto with `alpha` <= 1)
+        let mean = match self {
+            InterArrival::Fixed(gap) => nanoseconds(gap),
+            InterArrival::Uniform(min, max) => (nanoseconds(min) + nanoseconds(max)) / 2.0,
+            InterArrival::Exponential(mean) => nanoseconds(mean),
+            InterArrival::LogNormal { median, sigma } => {
+                nanoseconds(median) * (sigma * sigma / 2.0).exp()
+            }
+            InterArrival::Pareto { min, alpha } if *alpha > 1.0 => {
+                nanoseconds(min) * alpha / (alpha - 1.0)
+            }
+            InterArrival::Pareto { .. } => return None,
+        };
+        if mean.is_finite() && mean < i64::MAX as f64 {
+            Some(Duration::nanoseconds(mean as i64))
+        } else {
+            None
+        }
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Duration, Error> {
+        //! Draw a gap
+        self.validate()?;
+        let gap = match self {
+            InterArrival::Fixed(gap) => return Ok(*gap),
+            InterArrival::Uniform(min, max) => {
+                let (min, max) = (
+                    min.num_nanoseconds().unwrap_or(i64::MAX),
+                    max.num_nanoseconds().unwrap_or(i64::MAX),
+                );
+                if min >= max {
+                    return Ok(Duration::nanoseconds(min));
+                }
+                return Ok(Duration::nanoseconds(rng.gen_range(min..max)));
+            }
+            InterArrival::Exponential(mean) => Exp::new(1.0 / nanoseconds(mean))
+                .map_err(|e| format!("Error: {}", e))?
+                .sample(rng),
+            InterArrival::LogNormal { median, sigma } => {
+                LogNormal::new(nanoseconds(median).ln(), *sigma)
+                    .map_err(|e| format!("Error: {}", e))?
+                    .sample(rng)
+            }
+            InterArrival::Pareto { min, alpha } => Pareto::new(nanoseconds(min), *alpha)
+                .map_err(|e| format!("Error: {}", e))?
+                .sample(rng),
+        };
+        // far out in the tail the gap is capped rather than overflowing
+        Ok(Duration::nanoseconds(gap.min(i64::MAX as f64) as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distributions() {
+        let mut rng = rand::thread_rng();
+        let distributions = [
+            InterArrival::Fixed(Duration::seconds(15)),
+            InterArrival::Uniform(Duration::seconds(1), Duration::seconds(3)),
+            InterArrival::Exponential(Duration::seconds(10)),
+            InterArrival::LogNormal {
+                median: Duration::seconds(10),
+                sigma: 0.5,
+            },
+            InterArrival::Pareto {
+                min: Duration::seconds(10),
+                alpha: 3.0,
+            },
+        ];
+        for distribution in &distributions {
+            let samples = (0..20_000)
+                .map(|_| distribution.sample(&mut rng).unwrap())
+                .collect::<Vec<Duration>>();
+            assert!(samples.iter().all(|gap| *gap >= Duration::zero()));
+            // the average of the samples is close to the mean
+            let mean = nanoseconds(&distribution.mean().unwrap());
+            let average = samples.iter().map(nanoseconds).sum::<f64>() / samples.len() as f64;
+            assert!((average - mean).abs() < mean * 0.05, "{:?}", distribution);
+        }
+        assert!(
+            InterArrival::Uniform(Duration::seconds(1), Duration::seconds(3))
+                .sample(&mut rng)
+                .map(|gap| gap >= Duration::seconds(1) && gap < Duration::seconds(3))
+                .unwrap()
+        );
+
+        assert_eq!(
+            InterArrival::Pareto {
+                min: Duration::seconds(1),
+                alpha: 1.0
+            }
+            .mean(),
+            None
+        );
+        assert!(InterArrival::Fixed(Duration::zero()).validate().is_err());
+        assert!(
+            InterArrival::Uniform(Duration::seconds(2), Duration::seconds(1))
+                .validate()
+                .is_err()
+        );
+        assert!(InterArrival::Exponential(Duration::seconds(-1))
+            .sample(&mut rng)
+            .is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::Rng;
+
+mod dst;
+mod formats;
+mod interarrival;
+mod primitives;
+mod schedule;
+mod seasonality;
+mod skew;
+mod stream;
+
+pub use dst::DstPolicy;
+pub use formats::TimestampFormat;
+pub use interarrival::InterArrival;
+pub use primitives::{
+    birthday, date_between, duration_between, future, iso_week, past, quarter, time_of_day, Quarter,
+};
+pub use schedule::{Jitter, Schedule};
+pub use seasonality::Seasonality;
+pub use skew::{ClockSkew, SkewedTime};
+pub use stream::{TimeStream, UniformTimeStream};
+
+type Error = String;
+
+/// Times generated between two times of any time zone
+/// (`Utc`, `Local`, `FixedOffset`, `chrono_tz::Tz`...), in the
+/// time zone of the start time
+pub trait GenerateTime: Sized {
+    fn generate_until(&self, end: &Self) -> Result<Vec<Self>, Error>;
+    fn generate_until_with(
+        &self,
+        end: &Self,
+        inter_arrival: &InterArrival,
+    ) -> Result<Vec<Self>, Error>;
+    fn generate_until_with_limit(&self, end: &Self, limit: usize) -> Result<Vec<Self>, Error>;
+    fn generate_until_with_seasonality(
+        &self,
+        end: &Self,
+        limit: usize,
+        seasonality: &Seasonality,
+    ) -> Result<Vec<Self>, Error>;
+    fn generate_local_until_with_limit(
+        &self,
+        end: &Self,
+        limit: usize,
+        dst_policy: DstPolicy,
+    ) -> Result<Vec<Self>, Error>;
+    fn generate_until_with_schedule(
+        &self,
+        end: &Self,
+        schedule: &Schedule,
+    ) -> Result<Vec<Self>, Error>;
+}
+
+impl<Tz: TimeZone> GenerateTime for DateTime<Tz> {
+    fn generate_until(&self, end: &Self) -> Result<Vec<Self>, Error> {
+        //! Generate times between `self` (start) and `end`;
+        //! consecutive times are 0 to 15 seconds apart, see
+        //! `generate_until_with` for other gaps
+        //!
+        //! ## Example
+        //! ```rust
+        //! use chrono::{Utc, DateTime, TimeZone};
+        //! use random::datetime::GenerateTime;
+        //!
+        //! fn main () {
+        //!     let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        //!     let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+        //!
+        //!     let date_times = start.generate_until(&end);
+        //!
+        //!     println!("{:#?}", date_times);
+        //!     println!("total: {}", date_times.unwrap().len());
+        //! }
+        //! ```
+        self.generate_until_with(end, &InterArrival::default())
+    }
+
+    fn generate_until_with(
+        &self,
+        end: &Self,
+        inter_arrival: &InterArrival,
+    ) -> Result<Vec<Self>, Error> {
+        //! Generate times between `self` (start) and `end`, the
+        //! gaps between them drawn from the `inter_arrival` distribution;
+        //! `TimeStream` generates them lazily instead
+        //!
+        //! ## Example
+        //! ```rust
+        //! use chrono::{Duration, TimeZone, Utc};
+        //! use random::datetime::{GenerateTime, InterArrival};
+        //!
+        //! let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        //! let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+        //!
+        //! // a poisson process of 2 events a minute on average
+        //! let events = start
+        //!     .generate_until_with(&end, &InterArrival::Exponential(Duration::seconds(30)))
+        //!     .unwrap();
+        //! println!("total: {}", events.len());
+        //! ```
+        Ok(TimeStream::new(self, end, *inter_arrival)?.collect())
+    }
+
+    fn generate_until_with_limit(&self, end: &Self, limit: usize) -> Result<Vec<Self>, Error> {
+        //! Generate times between `self` (start) and `end`
+        //! with a range limit; `UniformTimeStream` generates
+        //! them lazily instead
+        //!
+        //! ## Example
+        //! ```rust
+        //! use chrono::{Utc, DateTime, TimeZone};
+        //! use random::datetime::GenerateTime;
+        //!
+        //! fn main () {
+        //!     let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        //!     let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+        //!
+        //!     let date_times = start.generate_until_with_limit(&end, 100);
+        //!
+        //!     println!("{:#?}", date_times);
+        //!     println!("total: {}", date_times.unwrap().len());
+        //! }
+        //! ```
+        Ok(UniformTimeStream::new(self, end, limit)?.collect())
+    }
+
+    fn generate_until_with_seasonality(
+        &self,
+        end: &Self,
+        limit: usize,
+        seasonality: &Seasonality,
+    ) -> Result<Vec<Self>, Error> {
+        //! Generate `limit` times between `self` (start) and `end`,
+        //! more of them at the busy hours & days of the `seasonality`
+        //!
+        //! ## Example
+        //! ```rust
+        //! use chrono::{TimeZone, Utc};
+        //! use random::datetime::{GenerateTime, Seasonality};
+        //!
+        //! let start = Utc.with_ymd_and_hms(2023, 3, 1, 0, 0, 0).unwrap();
+        //! let end = Utc.with_ymd_and_hms(2023, 4, 1, 0, 0, 0).unwrap();
+        //!
+        //! // peaks at the business hours of Tokyo
+        //! let tokyo = Seasonality::office_hours(chrono_tz::Asia::Tokyo);
+        //! let date_times = start
+        //!     .generate_until_with_seasonality(&end, 1000, &tokyo)
+        //!     .unwrap();
+        //! assert_eq!(date_times.len(), 1000);
+        //! ```
+        if self.ge(end) {
+            return Err(String::from(
+                "start date/time is greater than end date/time",
+            ));
+        }
+        let times = seasonality.sample_with(
+            &mut rand::thread_rng(),
+            &self.with_timezone(&Utc),
+            &end.with_timezone(&Utc),
+            limit,
+        )?;
+        Ok(times
+            .iter()
+            .map(|t| t.with_timezone(&self.timezone()))
+            .collect())
+    }
+
+    fn generate_local_until_with_limit(
+        &self,
+        end: &Self,
+        limit: usize,
+        dst_policy: DstPolicy,
+    ) -> Result<Vec<Self>, Error> {
+        //! Generate up to `limit` times between `self` (start) and `end`,
+        //! evenly spread over the local (wall clock) times rather than
+        //! over the elapsed time. Local times that fall in the gap or
+        //! the overlap of a daylight saving time change are resolved by
+        //! the `dst_policy`; with `DstPolicy::Skip` those are left out,
+        //! as are times moved past `end` out of a gap
+        //!
+        //! ## Example
+        //! ```rust
+        //! use chrono::TimeZone;
+        //! use chrono_tz::Europe::Berlin;
+        //! use random::datetime::{DstPolicy, GenerateTime};
+        //!
+        //! // the night summer time starts: 02:00 to 02:59 do not exist
+        //! let start = Berlin.with_ymd_and_hms(2023, 3, 26, 0, 0, 0).unwrap();
+        //! let end = Berlin.with_ymd_and_hms(2023, 3, 26, 6, 0, 0).unwrap();
+        //!
+        //! let date_times = start
+        //!     .generate_local_until_with_limit(&end, 100, DstPolicy::Earliest)
+        //!     .unwrap();
+        //! assert_eq!(date_times.len(), 100);
+        //! ```
+        if self.ge(end) {
+            return Err(String::from(
+                "start date/time is greater than end date/time",
+            ));
+        }
+        let (start_local, end_local) = (self.naive_local(), end.naive_local());
+        let nanoseconds = end_local
+            .signed_duration_since(start_local)
+            .num_nanoseconds()
+            .unwrap_or(i64::MAX);
+        if nanoseconds <= 0 {
+            return Err(String::from(
+                "start local time is not before end local time",
+            ));
+        }
+        let timezone = self.timezone();
+        let mut rng = rand::thread_rng();
+        let mut result = vec![];
+
+        for _ in 0..limit {
+            let local = start_local + Duration::nanoseconds(rng.gen_range(0..nanoseconds));
+            match dst_policy.resolve(&timezone, &local)? {
+                Some(time) if time >= *self && time < *end => result.push(time),
+                _ => {}
+            }
+        }
+
+        result.sort();
+        Ok(result)
+    }
+
+    fn generate_until_with_schedule(
+        &self,
+        end: &Self,
+        schedule: &Schedule,
+    ) -> Result<Vec<Self>, Error> {
+        //! Generate the times a recurring `schedule` (cron, RRULE or
+        //! fixed interval, with jitter & missed runs) runs at between
+        //! `self` (start) and `end`
+        //!
+        //! ## Example
+        //! ```rust
+        //! use chrono::{Duration, TimeZone, Utc};
+        //! use random::datetime::{GenerateTime, Jitter, Schedule};
+        //!
+        //! let start = Utc.with_ymd_and_hms(2023, 10, 18, 0, 0, 0).unwrap();
+        //! let end = Utc.with_ymd_and_hms(2023, 10, 19, 0, 0, 0).unwrap();
+        //!
+        //! // a beacon every 5 minutes, each sleep up to 30% shorter
+        //! let beacon = Schedule::every(Duration::minutes(5))
+        //!     .unwrap()
+        //!     .jitter(Jitter::Sleep(0.3));
+        //! let calls = start.generate_until_with_schedule(&end, &beacon).unwrap();
+        //! assert!(calls.len() >= 288);
+        //! ```
+        schedule.occurrences(self, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_gen_with_limit() {
+        let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+
+        let date_times = start.generate_until_with_limit(&end, 100);
+
+        assert!(date_times.is_ok());
+        assert_eq!(date_times.unwrap().len(), 100)
+    }
+
+    #[test]
+    fn test_gen() {
+        let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+
+        let date_times = start.generate_until(&end);
+
+        assert!(date_times.is_ok());
+    }
+
+    #[test]
+    fn test_gen_with_inter_arrival() {
+        let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+
+        let date_times = start
+            .generate_until_with(&end, &InterArrival::Fixed(Duration::minutes(15)))
+            .unwrap();
+        assert_eq!(date_times.len(), 24 * 4 + 1);
+        assert!(date_times
+            .windows(2)
+            .all(|w| w[1] - w[0] == Duration::minutes(15)));
+
+        assert!(start
+            .generate_until_with(&end, &InterArrival::Fixed(Duration::zero()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_gen_in_timezones() {
+        use chrono::{FixedOffset, Local, Timelike};
+        use chrono_tz::Europe::Berlin;
+
+        let start = FixedOffset::east_opt(5 * 3600 + 1800)
+            .unwrap()
+            .with_ymd_and_hms(2001, 9, 9, 0, 0, 0)
+            .unwrap();
+        let date_times = start
+            .generate_until_with_limit(&(start + Duration::hours(1)), 10)
+            .unwrap();
+        assert!(date_times.iter().all(|t| t.offset() == start.offset()));
+        let now = Local::now();
+        assert!(now.generate_until(&(now + Duration::minutes(1))).is_ok());
+
+        // elapsed time: the overlap hour of 2023-10-29 in Berlin lasts 2 hours
+        let start = Berlin.with_ymd_and_hms(2023, 10, 29, 0, 0, 0).unwrap();
+        let end = Berlin.with_ymd_and_hms(2023, 10, 29, 4, 0, 0).unwrap();
+        assert_eq!(end - start, Duration::hours(5));
+        let date_times = start
+            .generate_until_with(&end, &InterArrival::Fixed(Duration::minutes(30)))
+            .unwrap();
+        assert_eq!(date_times.len(), 11);
+        assert_eq!(date_times.iter().filter(|t| t.hour() == 2).count(), 4);
+
+        // local times: none in the gap hour of 2023-03-26
+        let start = Berlin.with_ymd_and_hms(2023, 3, 26, 0, 0, 0).unwrap();
+        let end = Berlin.with_ymd_and_hms(2023, 3, 26, 6, 0, 0).unwrap();
+        for policy in &[DstPolicy::Earliest, DstPolicy::Latest, DstPolicy::Skip] {
+            let date_times = start
+                .generate_local_until_with_limit(&end, 500, *policy)
+                .unwrap();
+            assert!(date_times.iter().all(|t| t.hour() != 2));
+        }
+        assert!(start
+            .generate_local_until_with_limit(&end, 500, DstPolicy::Strict)
+            .is_err());
+        // ending right after the gap: times moved out of it would be past the end
+        let end = Berlin.with_ymd_and_hms(2023, 3, 26, 3, 0, 0).unwrap();
+        for policy in &[DstPolicy::Earliest, DstPolicy::Latest] {
+            let date_times = start
+                .generate_local_until_with_limit(&end, 1000, *policy)
+                .unwrap();
+            assert!(date_times.len() < 1000);
+            assert!(date_times.iter().all(|t| *t >= start && *t < end));
+        }
+    }
+
+    #[test]
+    fn test_start_greater_than_end() {
+        let start = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+
+        let date_times = start.generate_until(&end);
+        assert!(date_times.is_err());
+
+        let date_times = start.generate_until_with_limit(&end, 100);
+        assert!(date_times.is_err());
+    }
+}
@@ -0,0 +1,257 @@
+/*
+Single date & time generators
+Dates in a range, birthdays, times of day, durations, times
+relative to now, ISO weeks & quarters
+*/
+
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Duration, IsoWeek, Months, NaiveDate, NaiveTime, Utc, Weekday};
+use rand::Rng;
+
+type Error = String;
+
+/// A quarter of a year, eg: 2023-Q2
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quarter {
+    pub year: i32,
+    /// 1 to 4
+    pub quarter: u32,
+}
+
+impl Quarter {
+    pub fn of(date: &NaiveDate) -> Self {
+        //! The quarter a date is in
+        Quarter {
+            year: date.year(),
+            quarter: date.month0() / 3 + 1,
+        }
+    }
+
+    pub fn first_day(&self) -> Option<NaiveDate> {
+        //! None for a quarter outside 1 to 4
+        let index = self.quarter.checked_sub(1).filter(|q| *q < 4)?;
+        NaiveDate::from_ymd_opt(self.year, index * 3 + 1, 1)
+    }
+
+    pub fn last_day(&self) -> Option<NaiveDate> {
+        self.first_day()?
+            .checked_add_months(Months::new(3))?
+            .pred_opt()
+    }
+}
+
+impl fmt::Display for Quarter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-Q{}", self.year, self.quarter)
+    }
+}
+
+pub(crate) fn date_between_with<R: Rng + ?Sized>(
+    rng: &mut R,
+    start: &NaiveDate,
+    end: &NaiveDate,
+) -> Result<NaiveDate, Error> {
+    if start > end {
+        return Err(format!("Error: {} is after {}", start, end));
+    }
+    let days = end.signed_duration_since(*start).num_days();
+    Ok(*start + Duration::days(rng.gen_range(0..=days)))
+}
+
+pub fn date_between(start: &NaiveDate, end: &NaiveDate) -> Result<NaiveDate, Error> {
+    /*
+    Generate a date in a range
+    :param start: the first possible date
+    :param end: the last possible date
+    :return: a date from start to end, both included
+    */
+    date_between_with(&mut rand::thread_rng(), start, end)
+}
+
+pub fn birthday(min_age: u32, max_age: u32, today: Option<NaiveDate>) -> Result<NaiveDate, Error> {
+    /*
+    Generate the birthday of someone of a given age
+    :param min_age: youngest age, in years
+    :param max_age: oldest age, in years
+    :param today: the day the age is as of - today if not given
+    :return: date of birth, eg: birthday(18, 65, None)
+    */
+    if min_age > max_age {
+        return Err(format!("Error: ages {} to {}", min_age, max_age));
+    }
+    let today = today.unwrap_or_else(|| Utc::now().date_naive());
+    let years_before = |years: u32| {
+        today
+            .checked_sub_months(Months::new(years.saturating_mul(12)))
+            .ok_or_else(|| format!("Error: {} years before {} is out of range", years, today))
+    };
+    // max_age until the day before turning max_age + 1
+    let earliest = years_before(max_age.saturating_add(1))? + Duration::days(1);
+    let latest = years_before(min_age)?;
+    date_between(&earliest, &latest)
+}
+
+pub fn time_of_day(start: Option<NaiveTime>, end: Option<NaiveTime>) -> NaiveTime {
+    /*
+    Generate a time of day, to the second
+    :param start: earliest time - 09:00 if not given
+    :param end: latest time - 17:00 if not given; before start for
+        ranges over midnight, eg: a night shift of 22:00 to 06:00
+    :return: a time from start to end, both included
+    */
+    let start = start.unwrap_or_else(|| NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default());
+    let end = end.unwrap_or_else(|| NaiveTime::from_hms_opt(17, 0, 0).unwrap_or_default());
+    let mut seconds = end.signed_duration_since(start).num_seconds();
+    if seconds < 0 {
+        seconds += 24 * 3600;
+    }
+    let (time, _) =
+        start.overflowing_add_signed(Duration::seconds(rand::thread_rng().gen_range(0..=seconds)));
+    time
+}
+
+pub fn duration_between(min: &Duration, max: &Duration) -> Result<Duration, Error> {
+    /*
+    Generate a duration, to the millisecond
+    :param min: shortest duration
+    :param max: longest duration
+    :return: a duration from min to max, both included, eg: duration_between(&Duration::minutes(5), &Duration::hours(2))
+    */
+    if min > max {
+        return Err(String::from(
+            "Error: min duration is greater than max duration",
+        ));
+    }
+    let range = min.num_milliseconds()..=max.num_milliseconds();
+    Ok(Duration::milliseconds(rand::thread_rng().gen_range(range)))
+}
+
+pub fn past(within: &Duration) -> Result<DateTime<Utc>, Error> {
+    /*
+    Generate a time in the past
+    :param within: how far back at most, eg: past(&Duration::days(30)) is within the last 30 days
+    :return: a time from now - within to now
+    */
+    let now = Utc::now();
+    let offset = duration_between(&Duration::zero(), within)
+        .map_err(|_| String::from("Error: a past range cannot be negative"))?;
+    now.checked_sub_signed(offset)
+        .ok_or_else(|| String::from("Error: past time is out of range"))
+}
+
+pub fn future(within: &Duration) -> Result<DateTime<Utc>, Error> {
+    /*
+    Generate a time in the future
+    :param within: how far ahead at most, eg: future(&Duration::hours(12)) is within the next 12 hours
+    :return: a time from now to now + within
+    */
+    let now = Utc::now();
+    let offset = duration_between(&Duration::zero(), within)
+        .map_err(|_| String::from("Error: a future range cannot be negative"))?;
+    now.checked_add_signed(offset)
+        .ok_or_else(|| String::from("Error: future time is out of range"))
+}
+
+pub fn iso_week(year: Option<i32>) -> Result<IsoWeek, Error> {
+    /*
+    Generate an ISO 8601 week
+    :param year: ISO week based year - the current one if not given
+    :return: an ISO week, eg: 2023-W07; years have 52 or 53 of them
+    */
+    let year = year.unwrap_or_else(|| Utc::now().iso_week().year());
+    let first = NaiveDate::from_isoywd_opt(year, 1, Weekday::Mon);
+    let next = NaiveDate::from_isoywd_opt(year + 1, 1, Weekday::Mon).and_then(|d| d.pred_opt());
+    match (first, next) {
+        (Some(first), Some(last)) => Ok(date_between(&first, &last)?.iso_week()),
+        _ => Err(format!("Error: year {} is out of range", year)),
+    }
+}
+
+pub fn quarter(year: Option<i32>) -> Quarter {
+    /*
+    Generate a quarter of a year
+    :param year: year - the current one if not given
+    :return: a quarter, eg: 2023-Q2
+    */
+    Quarter {
+        year: year.unwrap_or_else(|| Utc::now().year()),
+        quarter: rand::thread_rng().gen_range(1..=4),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitives() {
+        let (start, end) = (
+            NaiveDate::from_ymd_opt(2020, 2, 28).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 3, 1).unwrap(),
+        );
+        for _ in 0..50 {
+            let date = date_between(&start, &end).unwrap();
+            assert!(date >= start && date <= end);
+        }
+        assert!(date_between(&end, &start).is_err());
+
+        let today = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        for _ in 0..200 {
+            let born = birthday(18, 20, Some(today)).unwrap();
+            assert!(born > NaiveDate::from_ymd_opt(2003, 2, 28).unwrap());
+            assert!(born <= NaiveDate::from_ymd_opt(2006, 2, 28).unwrap());
+        }
+
+        let night = time_of_day(
+            NaiveTime::from_hms_opt(22, 0, 0),
+            NaiveTime::from_hms_opt(6, 0, 0),
+        );
+        assert!(
+            night >= NaiveTime::from_hms_opt(22, 0, 0).unwrap()
+                || night <= NaiveTime::from_hms_opt(6, 0, 0).unwrap()
+        );
+        let work = time_of_day(None, None);
+        assert!(work >= NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        assert!(work <= NaiveTime::from_hms_opt(17, 0, 0).unwrap());
+
+        let duration = duration_between(&Duration::seconds(1), &Duration::seconds(2)).unwrap();
+        assert!(duration >= Duration::seconds(1) && duration <= Duration::seconds(2));
+        let now = Utc::now();
+        assert!(past(&Duration::days(30)).unwrap() <= Utc::now());
+        assert!(past(&Duration::days(30)).unwrap() >= now - Duration::days(30));
+        assert!(future(&Duration::days(-1)).is_err());
+
+        // 2020 has 53 ISO weeks
+        let week = iso_week(Some(2020)).unwrap();
+        assert_eq!(week.year(), 2020);
+        assert!((1..=53).contains(&week.week()));
+
+        let quarter = quarter(Some(2023));
+        assert_eq!(Quarter::of(&quarter.first_day().unwrap()), quarter);
+        assert_eq!(
+            Quarter {
+                year: 2023,
+                quarter: 1
+            }
+            .last_day(),
+            NaiveDate::from_ymd_opt(2023, 3, 31)
+        );
+        for invalid in [0, 5, u32::MAX] {
+            let quarter = Quarter {
+                year: 2023,
+                quarter: invalid,
+            };
+            assert_eq!(quarter.first_day(), None);
+            assert_eq!(quarter.last_day(), None);
+        }
+        assert_eq!(
+            Quarter {
+                year: 2023,
+                quarter: 4
+            }
+            .to_string(),
+            "2023-Q4"
+        );
+    }
+}
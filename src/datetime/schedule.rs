@@ -0,0 +1,901 @@
+/*
+Recurring schedules
+Occurrences of cron expressions, iCalendar recurrence rules
+(RFC 5545 RRULEs) & fixed intervals between two times, with
+random jitter & missed runs, eg: scheduled tasks or the
+beaconing of malware
+*/
+
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc,
+    Weekday,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::DstPolicy;
+
+type Error = String;
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// How the occurrences stray from the schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Jitter {
+    /// each occurrence delayed by up to this much, eg: RandomizedDelaySec of systemd timers
+    Delay(Duration),
+    /// each sleep since the last occurrence shortened by up to this share
+    /// of it (0 to 1, excluded), so the occurrences drift from the schedule,
+    /// eg: a beacon sleeping 60s with a jitter of 0.2 sleeps 48s to 60s
+    Sleep(f64),
+}
+
+impl Jitter {
+    fn validate(&self) -> Result<(), Error> {
+        match self {
+            Jitter::Delay(max) if *max >= Duration::zero() => Ok(()),
+            Jitter::Sleep(ratio) if (0.0..1.0).contains(ratio) => Ok(()),
+            _ => Err(format!("Error: invalid jitter {:?}", self)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A cron expression: minute hour day-of-month month day-of-week
+#[derive(Debug, Clone, PartialEq)]
+struct Cron {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    month_days: Vec<u32>,
+    months: Vec<u32>,
+    /// 0 is sunday
+    weekdays: Vec<u32>,
+    /// day of month & day of week both restricted: a day matching either runs
+    either_day: bool,
+}
+
+/// An iCalendar recurrence rule
+#[derive(Debug, Clone, PartialEq)]
+struct RRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    /// the last possible occurrence; UTC or else local
+    until: Option<(NaiveDateTime, bool)>,
+    by_month: Vec<u32>,
+    by_month_day: Vec<i32>,
+    /// weekdays, with their ordinal in the month or the year, eg: -1FR
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_hour: Vec<u32>,
+    by_minute: Vec<u32>,
+    by_second: Vec<u32>,
+    by_set_pos: Vec<i32>,
+    week_start: Weekday,
+    /// the first possible occurrence; UTC or else local
+    dtstart: Option<(NaiveDateTime, bool)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Rule {
+    Cron(Cron),
+    RRule(RRule),
+    Every(Duration),
+}
+
+/// Occurrences of a recurring schedule between two times
+///
+/// ## Example
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use random::datetime::{Jitter, Schedule};
+///
+/// let start = Utc.with_ymd_and_hms(2023, 10, 2, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2023, 11, 1, 0, 0, 0).unwrap();
+///
+/// // a backup at 02:30 on weekdays, up to 10 minutes late & missed 1 night in 20
+/// let backup = Schedule::cron("30 2 * * MON-FRI")
+///     .unwrap()
+///     .jitter(Jitter::Delay(Duration::minutes(10)))
+///     .missed(0.05);
+/// assert!(backup.occurrences(&start, &end).unwrap().len() <= 22);
+///
+/// // the last friday of every month at 17:00
+/// let report = Schedule::rrule("FREQ=MONTHLY;BYDAY=-1FR;BYHOUR=17;BYMINUTE=0;BYSECOND=0").unwrap();
+/// let october = Utc.with_ymd_and_hms(2023, 10, 1, 0, 0, 0).unwrap();
+/// assert_eq!(
+///     report.occurrences(&october, &end).unwrap(),
+///     vec![Utc.with_ymd_and_hms(2023, 10, 27, 17, 0, 0).unwrap()]
+/// );
+///
+/// // a beacon calling home every 60 seconds with a 20% jitter
+/// let beacon = Schedule::every(Duration::seconds(60))
+///     .unwrap()
+///     .jitter(Jitter::Sleep(0.2));
+/// let calls = beacon.occurrences(&start, &end).unwrap();
+/// assert!(calls.windows(2).all(|w| w[1] - w[0] >= Duration::seconds(48)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    rule: Rule,
+    jitter: Option<Jitter>,
+    missed_probability: f64,
+    dst_policy: DstPolicy,
+    seed: Option<u64>,
+}
+
+impl Schedule {
+    fn new(rule: Rule) -> Self {
+        Schedule {
+            rule,
+            jitter: None,
+            missed_probability: 0.0,
+            dst_policy: DstPolicy::default(),
+            seed: None,
+        }
+    }
+
+    pub fn cron(expression: &str) -> Result<Self, Error> {
+        //! A cron schedule of 5 fields (minute, hour, day of month,
+        //! month, day of week) with `*`, ranges, lists, steps & names,
+        //! eg: `*/15 9-17 * * MON-FRI`, or a macro such as `@daily`
+        Ok(Schedule::new(Rule::Cron(Cron::parse(expression)?)))
+    }
+
+    pub fn rrule(rule: &str) -> Result<Self, Error> {
+        //! A schedule of an iCalendar (RFC 5545) recurrence rule,
+        //! eg: `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE`, optionally
+        //! preceded by a `DTSTART:20230101T090000` line; without a
+        //! DTSTART the rule starts at the start of the range.
+        //! BYYEARDAY, BYWEEKNO & TZID are not supported
+        Ok(Schedule::new(Rule::RRule(RRule::parse(rule)?)))
+    }
+
+    pub fn every(interval: Duration) -> Result<Self, Error> {
+        //! A fixed interval from the start of the range, eg: a beacon
+        if interval <= Duration::zero() {
+            return Err(String::from("Error: interval must be positive"));
+        }
+        Ok(Schedule::new(Rule::Every(interval)))
+    }
+
+    pub fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    pub fn missed(mut self, probability: f64) -> Self {
+        //! Share of the occurrences that do not happen, eg: the host was off
+        self.missed_probability = probability;
+        self
+    }
+
+    pub fn dst_policy(mut self, dst_policy: DstPolicy) -> Self {
+        //! What cron & RRULE occurrences in the gap or the overlap of a
+        //! daylight saving time change become; `Earliest` by default
+        self.dst_policy = dst_policy;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        //! Same seed, same jitter & missed runs
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn occurrences<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        end: &DateTime<Tz>,
+    ) -> Result<Vec<DateTime<Tz>>, Error> {
+        //! The times the schedule runs at from `start` (included) to
+        //! `end` (excluded), in the time zone of `start`; cron & RRULE
+        //! times are local (wall clock) times of that time zone
+        if start >= end {
+            return Err(String::from(
+                "start date/time is greater than end date/time",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.missed_probability) {
+            return Err(format!(
+                "Error: invalid missed probability {}",
+                self.missed_probability
+            ));
+        }
+        if let Some(jitter) = &self.jitter {
+            jitter.validate()?;
+        }
+        // sleeps shortened by up to a ratio fit more occurrences in the range
+        let nominal_end = match self.jitter {
+            Some(Jitter::Sleep(ratio)) => {
+                let stretched = nanoseconds(&(end.clone() - start.clone())) as f64 / (1.0 - ratio);
+                start
+                    .clone()
+                    .checked_add_signed(Duration::nanoseconds(stretched as i64))
+                    .unwrap_or_else(|| end.clone())
+            }
+            _ => end.clone(),
+        };
+        let nominal = match &self.rule {
+            Rule::Cron(cron) => cron.occurrences(start, &nominal_end, self.dst_policy)?,
+            Rule::RRule(rrule) => rrule.occurrences(start, &nominal_end, self.dst_policy)?,
+            Rule::Every(interval) => {
+                let mut times = vec![];
+                let mut time = start.clone();
+                while time < nominal_end {
+                    times.push(time.clone());
+                    time = match time.checked_add_signed(*interval) {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+                times
+            }
+        };
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut times = Vec::with_capacity(nominal.len());
+        let mut previous: Option<(DateTime<Tz>, DateTime<Tz>)> = None;
+        for time in nominal {
+            let actual = match self.jitter {
+                None => time.clone(),
+                Some(Jitter::Delay(max)) => {
+                    let delay = rng.gen_range(0..=nanoseconds(&max));
+                    time.clone() + Duration::nanoseconds(delay)
+                }
+                Some(Jitter::Sleep(ratio)) => match &previous {
+                    None => time.clone(),
+                    Some((last_nominal, last_actual)) => {
+                        let sleep = nanoseconds(&(time.clone() - last_nominal.clone())) as f64
+                            * (1.0 - rng.gen::<f64>() * ratio);
+                        last_actual.clone() + Duration::nanoseconds(sleep as i64)
+                    }
+                },
+            };
+            previous = Some((time, actual.clone()));
+            if !rng.gen_bool(self.missed_probability) {
+                times.push(actual);
+            }
+        }
+        times.retain(|t| t >= start && t < end);
+        times.sort();
+        Ok(times)
+    }
+}
+
+impl Cron {
+    fn parse(expression: &str) -> Result<Self, Error> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+        let fields = expression.split_whitespace().collect::<Vec<&str>>();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Error: cron expression '{}' does not have 5 fields",
+                expression
+            ));
+        }
+        let weekdays = cron_field(fields[4], 0, 7, WEEKDAY_NAMES)?
+            .into_iter()
+            .map(|d| d % 7)
+            .collect::<BTreeSet<u32>>();
+        Ok(Cron {
+            minutes: cron_field(fields[0], 0, 59, &[])?,
+            hours: cron_field(fields[1], 0, 23, &[])?,
+            month_days: cron_field(fields[2], 1, 31, &[])?,
+            months: cron_field(fields[3], 1, 12, MONTH_NAMES)?,
+            weekdays: weekdays.into_iter().collect(),
+            either_day: !fields[2].starts_with('*') && !fields[4].starts_with('*'),
+        })
+    }
+
+    fn matches(&self, date: &NaiveDate) -> bool {
+        let month_day = self.month_days.contains(&date.day());
+        let weekday = self
+            .weekdays
+            .contains(&date.weekday().num_days_from_sunday());
+        self.months.contains(&date.month())
+            && if self.either_day {
+                month_day || weekday
+            } else {
+                month_day && weekday
+            }
+    }
+
+    fn occurrences<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        end: &DateTime<Tz>,
+        dst_policy: DstPolicy,
+    ) -> Result<Vec<DateTime<Tz>>, Error> {
+        let timezone = start.timezone();
+        let last = end.naive_local().date();
+        let mut date = start.naive_local().date();
+        let mut times = vec![];
+        while date <= last {
+            if self.matches(&date) {
+                for hour in &self.hours {
+                    for minute in &self.minutes {
+                        let local = match date.and_hms_opt(*hour, *minute, 0) {
+                            Some(local) => local,
+                            None => continue,
+                        };
+                        match dst_policy.resolve(&timezone, &local)? {
+                            Some(time) if time >= *start && time < *end => times.push(time),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            date = match date.succ_opt() {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        // runs in a gap moved onto runs after it
+        times.sort();
+        times.dedup();
+        Ok(times)
+    }
+}
+
+fn cron_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<Vec<u32>, Error> {
+    let invalid = || format!("Error: invalid cron field '{}'", field);
+    let value = |s: &str| -> Result<u32, Error> {
+        match names.iter().position(|n| n.eq_ignore_ascii_case(s)) {
+            Some(i) => Ok(min + i as u32),
+            None => s.parse::<u32>().map_err(|_| invalid()),
+        }
+    };
+    let mut values = BTreeSet::new();
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<usize>().map_err(|_| invalid())?)),
+            None => (item, None),
+        };
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (value(low)?, value(high)?)
+        } else {
+            let value = value(range)?;
+            // a/n is a to the end, every n
+            (value, if step.is_some() { max } else { value })
+        };
+        if step == Some(0) || low > high || low < min || high > max {
+            return Err(invalid());
+        }
+        values.extend((low..=high).step_by(step.unwrap_or(1)));
+    }
+    Ok(values.into_iter().collect())
+}
+
+impl RRule {
+    fn parse(rule: &str) -> Result<Self, Error> {
+        let mut rrule = RRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_month: vec![],
+            by_month_day: vec![],
+            by_day: vec![],
+            by_hour: vec![],
+            by_minute: vec![],
+            by_second: vec![],
+            by_set_pos: vec![],
+            week_start: Weekday::Mon,
+            dtstart: None,
+        };
+        let mut frequency = None;
+        for line in rule.split_whitespace() {
+            let upper = line.to_ascii_uppercase();
+            if let Some(dtstart) = upper.strip_prefix("DTSTART") {
+                if dtstart.contains("TZID") {
+                    return Err(String::from(
+                        "Error: TZID is not supported, give the range in that time zone",
+                    ));
+                }
+                let value = dtstart.rsplit(':').next().unwrap_or_default();
+                rrule.dtstart = Some(ical_datetime(value, false)?);
+                continue;
+            }
+            let parts = upper.strip_prefix("RRULE:").unwrap_or(&upper);
+            for part in parts.split(';').filter(|p| !p.is_empty()) {
+                let (key, value) = part
+                    .split_once('=')
+                    .ok_or_else(|| format!("Error: invalid rule part '{}'", part))?;
+                match key {
+                    "FREQ" => frequency = Some(frequency_of(value)?),
+                    "INTERVAL" => rrule.interval = number(key, value, 1, i32::MAX)? as u32,
+                    "COUNT" => rrule.count = Some(number(key, value, 1, i32::MAX)? as usize),
+                    "UNTIL" => rrule.until = Some(ical_datetime(value, true)?),
+                    "BYMONTH" => rrule.by_month = numbers(key, value, 1, 12)?,
+                    "BYMONTHDAY" => rrule.by_month_day = signed_numbers(key, value, 31)?,
+                    "BYDAY" => {
+                        rrule.by_day = value
+                            .split(',')
+                            .map(|day| {
+                                // the weekday is the last 2 characters, bytes once ascii
+                                if !day.is_ascii() {
+                                    return Err(format!("Error: invalid weekday '{}'", day));
+                                }
+                                let split = day.len().saturating_sub(2);
+                                let ordinal = match &day[..split] {
+                                    "" => None,
+                                    n => Some(signed_number(key, n.trim_start_matches('+'), 53)?),
+                                };
+                                Ok((ordinal, weekday_of(&day[split..])?))
+                            })
+                            .collect::<Result<_, Error>>()?
+                    }
+                    "BYHOUR" => rrule.by_hour = numbers(key, value, 0, 23)?,
+                    "BYMINUTE" => rrule.by_minute = numbers(key, value, 0, 59)?,
+                    "BYSECOND" => rrule.by_second = numbers(key, value, 0, 59)?,
+                    "BYSETPOS" => rrule.by_set_pos = signed_numbers(key, value, 366)?,
+                    "WKST" => rrule.week_start = weekday_of(value)?,
+                    _ => return Err(format!("Error: {} is not supported", key)),
+                }
+            }
+        }
+        rrule.frequency = frequency.ok_or_else(|| String::from("Error: FREQ is missing"))?;
+        if rrule.count.is_some() && rrule.until.is_some() {
+            return Err(String::from("Error: COUNT & UNTIL are exclusive"));
+        }
+        let ordinals = rrule.by_day.iter().any(|(n, _)| n.is_some());
+        if ordinals && !matches!(rrule.frequency, Frequency::Monthly | Frequency::Yearly) {
+            return Err(String::from(
+                "Error: BYDAY ordinals are for MONTHLY & YEARLY rules only",
+            ));
+        }
+        Ok(rrule)
+    }
+
+    fn date_matches(&self, date: &NaiveDate, dtstart: &NaiveDateTime) -> bool {
+        // parts not given default to those of the start
+        let days_given = !self.by_month_day.is_empty() || !self.by_day.is_empty();
+        let same_month =
+            self.frequency == Frequency::Yearly && self.by_month.is_empty() && !days_given;
+        let same_day =
+            matches!(self.frequency, Frequency::Yearly | Frequency::Monthly) && !days_given;
+        let same_weekday = self.frequency == Frequency::Weekly && self.by_day.is_empty();
+
+        (self.by_month.is_empty() || self.by_month.contains(&date.month()))
+            && (!same_month || date.month() == dtstart.month())
+            && (!same_day || date.day() == dtstart.day())
+            && (!same_weekday || date.weekday() == dtstart.weekday())
+            && (self.by_month_day.is_empty()
+                || self.by_month_day.iter().any(|d| {
+                    let last = month_bounds(date).1.day() as i32;
+                    *d == date.day() as i32 || *d == date.day() as i32 - last - 1
+                }))
+            && (self.by_day.is_empty()
+                || self
+                    .by_day
+                    .iter()
+                    .any(|(n, w)| *w == date.weekday() && self.ordinal_matches(date, *n)))
+    }
+
+    fn ordinal_matches(&self, date: &NaiveDate, ordinal: Option<i32>) -> bool {
+        // counted in the year for yearly rules without months, else in the month
+        let (first, last) = if self.frequency == Frequency::Yearly && self.by_month.is_empty() {
+            (
+                date.with_ordinal(1).unwrap_or(*date),
+                NaiveDate::from_ymd_opt(date.year(), 12, 31).unwrap_or(*date),
+            )
+        } else {
+            month_bounds(date)
+        };
+        match ordinal {
+            None => true,
+            Some(n) if n > 0 => (*date - first).num_days() / 7 + 1 == i64::from(n),
+            Some(n) => -((last - *date).num_days() / 7 + 1) == i64::from(n),
+        }
+    }
+
+    fn period(&self, dtstart: &NaiveDateTime, k: i64) -> Option<(NaiveDateTime, Vec<NaiveDate>)> {
+        // the start of the k-th period & the dates in it
+        let step = k.checked_mul(i64::from(self.interval))?;
+        let date = dtstart.date();
+        let whole_minute = dtstart.with_nanosecond(0)?.with_second(0)?;
+        let (period_start, days) = match self.frequency {
+            Frequency::Yearly => {
+                let first = NaiveDate::from_ymd_opt(date.year() + i32::try_from(step).ok()?, 1, 1)?;
+                let days = NaiveDate::from_ymd_opt(first.year(), 12, 31)?.ordinal();
+                (first, days)
+            }
+            Frequency::Monthly => {
+                let first = date
+                    .with_day(1)?
+                    .checked_add_months(Months::new(u32::try_from(step).ok()?))?;
+                (first, month_bounds(&first).1.day())
+            }
+            Frequency::Weekly => {
+                let back = (7 + date.weekday().num_days_from_monday()
+                    - self.week_start.num_days_from_monday())
+                    % 7;
+                let first = date - Duration::days(i64::from(back)) + Duration::weeks(step);
+                (first, 7)
+            }
+            Frequency::Daily => (date + Duration::days(step), 1),
+            Frequency::Hourly => {
+                let time = whole_minute.with_minute(0)? + Duration::hours(step);
+                return Some((time, vec![time.date()]));
+            }
+            Frequency::Minutely => {
+                let time = whole_minute + Duration::minutes(step);
+                return Some((time, vec![time.date()]));
+            }
+            Frequency::Secondly => {
+                let time = dtstart.with_nanosecond(0)? + Duration::seconds(step);
+                return Some((time, vec![time.date()]));
+            }
+        };
+        let dates = (0..days)
+            .filter_map(|d| period_start.checked_add_signed(Duration::days(i64::from(d))))
+            .collect();
+        Some((period_start.and_hms_opt(0, 0, 0)?, dates))
+    }
+
+    fn candidates(
+        &self,
+        dtstart: &NaiveDateTime,
+        period: &NaiveDateTime,
+        dates: &[NaiveDate],
+    ) -> Vec<NaiveDateTime> {
+        let or_start = |given: &Vec<u32>, start: u32| {
+            if given.is_empty() {
+                vec![start]
+            } else {
+                given.clone()
+            }
+        };
+        // parts finer than the frequency expand, coarser ones only filter
+        let hours = match self.frequency {
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly => vec![period.hour()],
+            _ => or_start(&self.by_hour, dtstart.hour()),
+        };
+        let minutes = match self.frequency {
+            Frequency::Minutely | Frequency::Secondly => vec![period.minute()],
+            _ => or_start(&self.by_minute, dtstart.minute()),
+        };
+        let seconds = match self.frequency {
+            Frequency::Secondly => vec![period.second()],
+            _ => or_start(&self.by_second, dtstart.second()),
+        };
+        let allowed = |given: &Vec<u32>, value: &u32| given.is_empty() || given.contains(value);
+
+        let mut candidates = vec![];
+        for date in dates.iter().filter(|d| self.date_matches(d, dtstart)) {
+            for hour in hours.iter().filter(|h| allowed(&self.by_hour, h)) {
+                for minute in minutes.iter().filter(|m| allowed(&self.by_minute, m)) {
+                    for second in seconds.iter().filter(|s| allowed(&self.by_second, s)) {
+                        if let Some(time) = date.and_hms_opt(*hour, *minute, *second) {
+                            candidates.push(time);
+                        }
+                    }
+                }
+            }
+        }
+        candidates.sort();
+        candidates.dedup();
+        if self.by_set_pos.is_empty() {
+            return candidates;
+        }
+        let count = candidates.len() as i32;
+        let mut selected = self
+            .by_set_pos
+            .iter()
+            .filter_map(|p| {
+                let index = if *p > 0 { p - 1 } else { count + p };
+                candidates.get(usize::try_from(index).ok()?).copied()
+            })
+            .collect::<Vec<NaiveDateTime>>();
+        selected.sort();
+        selected.dedup();
+        selected
+    }
+
+    fn occurrences<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        end: &DateTime<Tz>,
+        dst_policy: DstPolicy,
+    ) -> Result<Vec<DateTime<Tz>>, Error> {
+        let timezone = start.timezone();
+        let local = |(time, utc): (NaiveDateTime, bool)| {
+            if utc {
+                Utc.from_utc_datetime(&time)
+                    .with_timezone(&timezone)
+                    .naive_local()
+            } else {
+                time
+            }
+        };
+        let dtstart = self
+            .dtstart
+            .map(local)
+            .unwrap_or_else(|| start.naive_local());
+        let until = self.until.map(local);
+        // local times a day past either end cover any change of offset
+        let (first, last) = (
+            start.naive_local() - Duration::days(1),
+            end.naive_local() + Duration::days(1),
+        );
+
+        // without a count, periods before the range can be skipped
+        let elapsed = first - dtstart;
+        let elapsed = match self.frequency {
+            Frequency::Daily => elapsed.num_days(),
+            Frequency::Hourly => elapsed.num_hours(),
+            Frequency::Minutely => elapsed.num_minutes(),
+            Frequency::Secondly => elapsed.num_seconds(),
+            _ => 0,
+        };
+        let mut k = match self.count {
+            None => (elapsed / i64::from(self.interval) - 1).max(0),
+            Some(_) => 0,
+        };
+
+        let mut times = vec![];
+        let mut count = 0;
+        'periods: while let Some((period, dates)) = self.period(&dtstart, k) {
+            if period > last {
+                break;
+            }
+            for time in self.candidates(&dtstart, &period, &dates) {
+                if time < dtstart {
+                    continue;
+                }
+                if until.is_some_and(|until| time > until) || self.count == Some(count) {
+                    break 'periods;
+                }
+                count += 1;
+                match dst_policy.resolve(&timezone, &time)? {
+                    Some(time) if time >= *start && time < *end => times.push(time),
+                    _ => {}
+                }
+            }
+            k += 1;
+        }
+        times.sort();
+        times.dedup();
+        Ok(times)
+    }
+}
+
+fn month_bounds(date: &NaiveDate) -> (NaiveDate, NaiveDate) {
+    let first = date.with_day(1).unwrap_or(*date);
+    let last = first
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(*date);
+    (first, last)
+}
+
+fn frequency_of(value: &str) -> Result<Frequency, Error> {
+    match value {
+        "SECONDLY" => Ok(Frequency::Secondly),
+        "MINUTELY" => Ok(Frequency::Minutely),
+        "HOURLY" => Ok(Frequency::Hourly),
+        "DAILY" => Ok(Frequency::Daily),
+        "WEEKLY" => Ok(Frequency::Weekly),
+        "MONTHLY" => Ok(Frequency::Monthly),
+        "YEARLY" => Ok(Frequency::Yearly),
+        _ => Err(format!("Error: invalid FREQ '{}'", value)),
+    }
+}
+
+fn weekday_of(value: &str) -> Result<Weekday, Error> {
+    match value {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(format!("Error: invalid weekday '{}'", value)),
+    }
+}
+
+fn number(key: &str, value: &str, min: i32, max: i32) -> Result<i32, Error> {
+    match value.parse::<i32>() {
+        Ok(n) if n >= min && n <= max => Ok(n),
+        _ => Err(format!("Error: invalid {} '{}'", key, value)),
+    }
+}
+
+fn numbers(key: &str, value: &str, min: i32, max: i32) -> Result<Vec<u32>, Error> {
+    value
+        .split(',')
+        .map(|n| number(key, n, min, max).map(|n| n as u32))
+        .collect()
+}
+
+fn signed_number(key: &str, value: &str, max: i32) -> Result<i32, Error> {
+    // 1 to max, or counted from the end: -1 to -max
+    let n = number(key, value, -max, max)?;
+    if n == 0 {
+        return Err(format!("Error: invalid {} '{}'", key, value));
+    }
+    Ok(n)
+}
+
+fn signed_numbers(key: &str, value: &str, max: i32) -> Result<Vec<i32>, Error> {
+    value
+        .split(',')
+        .map(|n| signed_number(key, n.trim_start_matches('+'), max))
+        .collect()
+}
+
+fn ical_datetime(value: &str, end_of_day: bool) -> Result<(NaiveDateTime, bool), Error> {
+    // 20230101T090000, 20230101T090000Z (UTC) or a date: 20230101
+    let (value, utc) = match value.strip_suffix('Z') {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let time = match NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        Ok(time) => Some(time),
+        Err(_) => NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .and_then(|d| {
+                if end_of_day {
+                    d.and_hms_opt(23, 59, 59)
+                } else {
+                    d.and_hms_opt(0, 0, 0)
+                }
+            }),
+    };
+    time.map(|t| (t, utc))
+        .ok_or_else(|| format!("Error: invalid date/time '{}'", value))
+}
+
+fn nanoseconds(duration: &Duration) -> i64 {
+    duration.num_nanoseconds().unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Berlin;
+
+    #[test]
+    fn test_schedules() {
+        let start = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        // cron
+        let quarter_hours = Schedule::cron("*/15 9-17 * * MON-FRI").unwrap();
+        let times = quarter_hours
+            .occurrences(&start, &(start + Duration::days(7)))
+            .unwrap();
+        assert_eq!(times.len(), 5 * 9 * 4);
+        assert!(times
+            .iter()
+            .all(|t| t.weekday() != Weekday::Sun && t.minute() % 15 == 0));
+        // day of month or day of week: the 1st & 15th plus every monday
+        let either = Schedule::cron("0 0 1,15 * 1").unwrap();
+        assert_eq!(either.occurrences(&start, &end).unwrap().len(), 24 + 52 - 2);
+        assert_eq!(
+            Schedule::cron("@monthly")
+                .unwrap()
+                .occurrences(&start, &end)
+                .unwrap()
+                .len(),
+            12
+        );
+        for invalid in &["* * * *", "61 * * * *", "*/0 * * * *", "* * * FOO *"] {
+            assert!(Schedule::cron(invalid).is_err(), "{}", invalid);
+        }
+
+        // rrule
+        let last_fridays =
+            Schedule::rrule("RRULE:FREQ=MONTHLY;BYDAY=-1FR;BYHOUR=17;BYMINUTE=0;BYSECOND=0")
+                .unwrap()
+                .occurrences(&start, &end)
+                .unwrap();
+        assert_eq!(last_fridays.len(), 12);
+        assert_eq!(
+            last_fridays[0],
+            Utc.with_ymd_and_hms(2023, 1, 27, 17, 0, 0).unwrap()
+        );
+        let last_workdays = Schedule::rrule(
+            "DTSTART:20230101T180000\nRRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;COUNT=3",
+        )
+        .unwrap()
+        .occurrences(&start, &end)
+        .unwrap();
+        assert_eq!(
+            last_workdays,
+            vec![
+                Utc.with_ymd_and_hms(2023, 1, 31, 18, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2023, 2, 28, 18, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2023, 3, 31, 18, 0, 0).unwrap(),
+            ]
+        );
+        let fortnightly = Schedule::rrule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20230131")
+            .unwrap()
+            .occurrences(&start, &end)
+            .unwrap();
+        // mondays & wednesdays of every other week from that of the start, dec 26
+        assert_eq!(
+            fortnightly.iter().map(|t| t.day()).collect::<Vec<u32>>(),
+            vec![9, 11, 23, 25]
+        );
+        for invalid in &[
+            "INTERVAL=2",
+            "FREQ=DAILY;BYWEEKNO=1",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=MONTHLY;BYDAY=éa",
+            "FREQ=MONTHLY;BYDAY=1é",
+        ] {
+            assert!(Schedule::rrule(invalid).is_err(), "{}", invalid);
+        }
+
+        // local times: 02:30 does not exist on 2023-03-26 in Berlin
+        let berlin = (
+            Berlin.with_ymd_and_hms(2023, 3, 20, 0, 0, 0).unwrap(),
+            Berlin.with_ymd_and_hms(2023, 4, 1, 0, 0, 0).unwrap(),
+        );
+        let nightly = Schedule::cron("30 2 * * *").unwrap();
+        assert_eq!(nightly.occurrences(&berlin.0, &berlin.1).unwrap().len(), 12);
+        let skipped = nightly.dst_policy(DstPolicy::Skip);
+        assert_eq!(skipped.occurrences(&berlin.0, &berlin.1).unwrap().len(), 11);
+        // 02:00 & 02:30 move onto the 03:00 & 03:30 runs, which run once
+        let half_hourly = Schedule::cron("*/30 * * * *").unwrap().occurrences(
+            &Berlin.with_ymd_and_hms(2023, 3, 26, 0, 0, 0).unwrap(),
+            &Berlin.with_ymd_and_hms(2023, 3, 26, 6, 0, 0).unwrap(),
+        );
+        let half_hourly = half_hourly.unwrap();
+        assert_eq!(half_hourly.len(), 10);
+        assert!(half_hourly.windows(2).all(|w| w[0] < w[1]));
+
+        // beacons with jitter & missed calls
+        let day = start + Duration::days(1);
+        let beacon = Schedule::every(Duration::seconds(60)).unwrap();
+        assert_eq!(beacon.occurrences(&start, &day).unwrap().len(), 1440);
+        let jittered = beacon.jitter(Jitter::Sleep(0.2)).seed(1);
+        let calls = jittered.occurrences(&start, &day).unwrap();
+        assert_eq!(calls, jittered.occurrences(&start, &day).unwrap());
+        assert!(calls.len() > 1440);
+        assert!(calls.windows(2).all(|w| {
+            w[1] - w[0] >= Duration::seconds(48) && w[1] - w[0] <= Duration::seconds(60)
+        }));
+        let missed = Schedule::every(Duration::seconds(60))
+            .unwrap()
+            .missed(0.5)
+            .occurrences(&start, &day)
+            .unwrap();
+        assert!(missed.len() > 500 && missed.len() < 940);
+        assert!(Schedule::every(Duration::zero()).is_err());
+        assert!(Schedule::every(Duration::seconds(1))
+            .unwrap()
+            .jitter(Jitter::Sleep(1.0))
+            .occurrences(&start, &day)
+            .is_err());
+    }
+}
@@ -0,0 +1,248 @@
+/*
+Seasonality profiles
+How busy each hour of the day & day of the week is, in a given
+time zone, with holidays when nothing happens: eg, an office that
+is busy during its business hours, dips at lunch & is quiet on
+weekends
+*/
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Offset, TimeZone, Timelike, Utc};
+use rand::Rng;
+
+type Error = String;
+
+/// business hours: quiet nights, a lunch dip & an evening tail
+const OFFICE_HOUR_WEIGHTS: [f64; 24] = [
+    0.05, 0.03, 0.02, 0.02, 0.02, 0.03, 0.08, 0.3, 0.8, 1.0, 1.0, 0.9, 0.5, 0.7, 1.0, 1.0, 0.9,
+    0.6, 0.3, 0.2, 0.15, 0.12, 0.1, 0.07,
+];
+/// monday first: quiet weekends
+const OFFICE_WEEKDAY_WEIGHTS: [f64; 7] = [1.0, 1.0, 1.0, 1.0, 0.9, 0.15, 0.1];
+
+/// Weights of the hours of the day & days of the week in a time zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct Seasonality {
+    pub timezone: chrono_tz::Tz,
+    /// local hours, from midnight
+    pub hour_weights: [f64; 24],
+    /// local days of the week, from monday
+    pub weekday_weights: [f64; 7],
+    /// local dates with no activity at all
+    pub holidays: HashSet<NaiveDate>,
+}
+
+impl Seasonality {
+    pub fn new(timezone: chrono_tz::Tz) -> Self {
+        //! Flat profile: every hour of every day weighs the same
+        Seasonality {
+            timezone,
+            hour_weights: [1.0; 24],
+            weekday_weights: [1.0; 7],
+            holidays: HashSet::new(),
+        }
+    }
+
+    pub fn office_hours(timezone: chrono_tz::Tz) -> Self {
+        //! Profile of an office: busy working hours with a dip
+        //! at lunch, quiet nights & quiet weekends
+        Seasonality {
+            hour_weights: OFFICE_HOUR_WEIGHTS,
+            weekday_weights: OFFICE_WEEKDAY_WEIGHTS,
+            ..Seasonality::new(timezone)
+        }
+    }
+
+    #[cfg(feature = "tz")]
+    pub fn office_hours_in(tz: &crate::tz::Tz) -> Result<Self, Error> {
+        //! Profile of an office in a country, in its (first) time zone
+        let timezone = tz
+            .timezones()
+            .iter()
+            .find_map(|name| name.trim().parse::<chrono_tz::Tz>().ok())
+            .ok_or_else(|| format!("Error: no known time zone for {}", tz.name()))?;
+        Ok(Seasonality::office_hours(timezone))
+    }
+
+    pub fn hour_weights(mut self, weights: [f64; 24]) -> Self {
+        self.hour_weights = weights;
+        self
+    }
+
+    pub fn weekday_weights(mut self, weights: [f64; 7]) -> Self {
+        self.weekday_weights = weights;
+        self
+    }
+
+    pub fn holidays(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(dates);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        //! Check the weights: none negative & some of each positive
+        let valid = |weights: &[f64]| {
+            weights.iter().all(|w| w.is_finite() && *w >= 0.0) && weights.iter().any(|w| *w > 0.0)
+        };
+        if valid(&self.hour_weights) && valid(&self.weekday_weights) {
+            Ok(())
+        } else {
+            Err(String::from(
+                "Error: seasonality weights must be positive or zero, and not all zero",
+            ))
+        }
+    }
+
+    pub fn weight(&self, time: &DateTime<Utc>) -> f64 {
+        //! Relative activity at a given time, in the local time of the profile
+        let local = time.with_timezone(&self.timezone);
+        if self.holidays.contains(&local.date_naive()) {
+            return 0.0;
+        }
+        self.hour_weights[local.hour() as usize]
+            * self.weekday_weights[local.weekday().num_days_from_monday() as usize]
+    }
+
+    fn next_hour_change(&self, time: &DateTime<Utc>) -> DateTime<Utc> {
+        // the next local hour boundary at the current offset, or the
+        // transition to another offset if there is one before it; offsets
+        // need not be whole quarters of an hour, eg: -0:44:30 of Monrovia
+        let local = time.with_timezone(&self.timezone);
+        let offset = local.offset().fix();
+        let into_hour = Duration::seconds(i64::from(local.minute() * 60 + local.second()))
+            + Duration::nanoseconds(i64::from(local.nanosecond()));
+        let boundary = *time - into_hour + Duration::hours(1);
+        let offset_at = |seconds: i64| match Utc.timestamp_opt(seconds, 0).single() {
+            Some(t) => self.timezone.offset_from_utc_datetime(&t.naive_utc()).fix(),
+            None => offset,
+        };
+        let (mut before, mut after) = (time.timestamp(), boundary.timestamp() - 1);
+        if offset_at(after) == offset {
+            return boundary;
+        }
+        // transitions are at whole seconds: the first one with the new offset
+        while after - before > 1 {
+            let middle = before + (after - before) / 2;
+            if offset_at(middle) == offset {
+                before = middle;
+            } else {
+                after = middle;
+            }
+        }
+        Utc.timestamp_opt(after, 0).single().unwrap_or(boundary)
+    }
+
+    pub(crate) fn sample_with<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        start: &DateTime<Utc>,
+        end: &DateTime<Utc>,
+        count: usize,
+    ) -> Result<Vec<DateTime<Utc>>, Error> {
+        // times between start & end, as dense as the weights are:
+        // slots of one local hour are picked by weight, then a time in them
+        self.validate()?;
+        let mut slots = Vec::new();
+        let mut cumulative = Vec::new();
+        let mut total = 0.0;
+        let mut from = *start;
+        while from < *end {
+            let to = self.next_hour_change(&from).min(*end);
+            let weight = self.weight(&from) * (to - from).num_nanoseconds().unwrap_or(0) as f64;
+            if weight > 0.0 {
+                total += weight;
+                slots.push((from, to));
+                cumulative.push(total);
+            }
+            from = to;
+        }
+        if slots.is_empty() {
+            return Err(String::from(
+                "Error: nothing happens between start & end with this seasonality",
+            ));
+        }
+
+        let mut result = Vec::with_capacity(count);
+        for _ in 0..count {
+            let target = rng.gen_range(0.0..total);
+            let index = cumulative
+                .partition_point(|c| *c <= target)
+                .min(slots.len() - 1);
+            let (from, to) = slots[index];
+            let nanoseconds = (to - from).num_nanoseconds().unwrap_or(1).max(1);
+            result.push(from + Duration::nanoseconds(rng.gen_range(0..nanoseconds)));
+        }
+        result.sort();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_office_hours() {
+        let start = Utc.with_ymd_and_hms(2023, 3, 6, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2023, 3, 20, 0, 0, 0).unwrap();
+        let holiday = NaiveDate::from_ymd_opt(2023, 3, 8).unwrap();
+        let tokyo = Seasonality::office_hours(chrono_tz::Asia::Tokyo).holidays(vec![holiday]);
+
+        let times = tokyo
+            .sample_with(&mut rand::thread_rng(), &start, &end, 10_000)
+            .unwrap();
+        assert_eq!(times.len(), 10_000);
+        assert!(times.windows(2).all(|w| w[0] <= w[1]));
+
+        let local = times
+            .iter()
+            .map(|t| t.with_timezone(&chrono_tz::Asia::Tokyo))
+            .collect::<Vec<_>>();
+        assert!(local.iter().all(|t| t.date_naive() != holiday));
+        // 10:00 in Tokyo is far busier than 03:00, and weekdays than sundays
+        let at_hour = |hour: u32| local.iter().filter(|t| t.hour() == hour).count();
+        assert!(at_hour(10) > 5 * at_hour(3));
+        let on_day = |day: chrono::Weekday| local.iter().filter(|t| t.weekday() == day).count();
+        assert!(on_day(chrono::Weekday::Tue) > 3 * on_day(chrono::Weekday::Sun));
+
+        let closed = Seasonality::new(chrono_tz::UTC).weekday_weights([0.0; 7]);
+        assert!(closed.validate().is_err());
+        let all_holidays = Seasonality::new(chrono_tz::UTC)
+            .holidays(vec![NaiveDate::from_ymd_opt(2023, 3, 6).unwrap()]);
+        assert!(all_holidays
+            .sample_with(
+                &mut rand::thread_rng(),
+                &start,
+                &(start + Duration::hours(5)),
+                1
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_offsets_off_the_quarter_hour() {
+        // Monrovia was 0:44:30 behind UTC until it moved to UTC on 1972-01-07
+        let monrovia = chrono_tz::Africa::Monrovia;
+        let mut hour_weights = [0.0; 24];
+        hour_weights[0] = 1.0;
+        let midnights = Seasonality::new(monrovia).hour_weights(hour_weights);
+        let start = Utc.with_ymd_and_hms(1972, 1, 5, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(1972, 1, 10, 0, 0, 0).unwrap();
+        let times = midnights
+            .sample_with(&mut rand::thread_rng(), &start, &end, 10_000)
+            .unwrap();
+        assert!(times.iter().all(|t| t.with_timezone(&monrovia).hour() == 0));
+        assert!(times.iter().any(|t| t.minute() == 44));
+        assert!(times.iter().any(|t| t.hour() == 0 && t.minute() < 15));
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_office_hours_in_country() {
+        let tz = crate::tz::Tz::by_iso_code("JP").unwrap().unwrap();
+        let seasonality = Seasonality::office_hours_in(tz).unwrap();
+        assert_eq!(seasonality.timezone, chrono_tz::Asia::Tokyo);
+    }
+}
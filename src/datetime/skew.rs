@@ -0,0 +1,290 @@
+/*
+Clock skew & delivery imperfections
+What the logs of a host make of the times events happened at:
+its clock is off & drifts until NTP steps it back, some events
+arrive late (out of order) and some arrive twice
+*/
+
+use chrono::{DateTime, Duration, TimeZone};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+type Error = String;
+
+/// An event as a host logged it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkewedTime<Tz: TimeZone> {
+    /// position of the event in the given times
+    pub index: usize,
+    /// when it happened
+    pub actual: DateTime<Tz>,
+    /// when the clock of the host said it happened
+    pub recorded: DateTime<Tz>,
+    /// when it reached the log, which is in this order
+    pub delivered: DateTime<Tz>,
+    /// a second copy of an event
+    pub duplicate: bool,
+}
+
+/// Clock & delivery of a host; a perfect one by default
+///
+/// ## Example
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use random::datetime::{ClockSkew, GenerateTime};
+///
+/// let start = Utc.with_ymd_and_hms(2023, 10, 18, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2023, 10, 19, 0, 0, 0).unwrap();
+/// let times = start.generate_until_with_limit(&end, 1000).unwrap();
+///
+/// // 2 seconds fast, gaining 40 µs a second, stepped back by NTP every 17 minutes,
+/// // 1% of events up to 5 seconds late & 0.5% of them twice
+/// let host = ClockSkew::new()
+///     .offset(Duration::seconds(2))
+///     .drift_ppm(40.0)
+///     .ntp_interval(Duration::seconds(1024))
+///     .reordering(0.01, Duration::seconds(5))
+///     .duplicates(0.005);
+/// for event in host.apply(&times).unwrap().iter().take(5) {
+///     println!("{} (actually {})", event.recorded, event.actual);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClockSkew {
+    /// how far ahead (or behind, when negative) the clock starts
+    pub offset: Duration,
+    /// how much the clock gains (or loses, when negative), in parts per million
+    pub drift_ppm: f64,
+    /// NTP steps the clock to the right time this often
+    pub ntp_interval: Option<Duration>,
+    /// share of the events that are delivered late
+    pub reorder_probability: f64,
+    pub max_delay: Duration,
+    /// share of the events that are delivered twice
+    pub duplicate_probability: f64,
+    pub seed: Option<u64>,
+}
+
+impl Default for ClockSkew {
+    fn default() -> Self {
+        ClockSkew {
+            offset: Duration::zero(),
+            drift_ppm: 0.0,
+            ntp_interval: None,
+            reorder_probability: 0.0,
+            max_delay: Duration::zero(),
+            duplicate_probability: 0.0,
+            seed: None,
+        }
+    }
+}
+
+impl ClockSkew {
+    pub fn new() -> Self {
+        ClockSkew::default()
+    }
+
+    pub fn random() -> Self {
+        //! A host like many are: up to 2 seconds off, drifting up
+        //! to 50 ppm, synced by NTP every 1024 seconds, delivering
+        //! 1% of the events late & 0.1% of them twice
+        let mut rng = rand::thread_rng();
+        ClockSkew {
+            offset: Duration::milliseconds(rng.gen_range(-2000..=2000)),
+            drift_ppm: rng.gen_range(-50.0..=50.0),
+            ntp_interval: Some(Duration::seconds(1024)),
+            reorder_probability: 0.01,
+            max_delay: Duration::seconds(5),
+            duplicate_probability: 0.001,
+            seed: None,
+        }
+    }
+
+    pub fn offset(mut self, offset: Duration) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn drift_ppm(mut self, drift_ppm: f64) -> Self {
+        self.drift_ppm = drift_ppm;
+        self
+    }
+
+    pub fn ntp_interval(mut self, interval: Duration) -> Self {
+        self.ntp_interval = Some(interval);
+        self
+    }
+
+    pub fn reordering(mut self, probability: f64, max_delay: Duration) -> Self {
+        self.reorder_probability = probability;
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn duplicates(mut self, probability: f64) -> Self {
+        self.duplicate_probability = probability;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        //! Same seed, same late & duplicated events
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let probability = |p: f64| (0.0..=1.0).contains(&p);
+        let valid = self.drift_ppm.is_finite()
+            && self.drift_ppm.abs() < 1_000_000.0
+            && self.ntp_interval.is_none_or(|i| i > Duration::zero())
+            && probability(self.reorder_probability)
+            && probability(self.duplicate_probability)
+            && self.max_delay >= Duration::zero();
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Error: invalid clock skew {:?}", self))
+        }
+    }
+
+    pub fn recorded_time<Tz: TimeZone>(
+        &self,
+        actual: &DateTime<Tz>,
+        origin: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        //! What the clock says at `actual`, the clock having been
+        //! `offset` off at `origin`; after an NTP step it is right
+        //! again, & drifts away from there
+        let elapsed = actual.clone() - origin.clone();
+        let (synced, since_sync) = match self.ntp_interval {
+            Some(interval) if elapsed >= interval => {
+                let (elapsed_ns, interval_ns) = (nanoseconds(&elapsed), nanoseconds(&interval));
+                (true, Duration::nanoseconds(elapsed_ns % interval_ns))
+            }
+            _ => (false, elapsed),
+        };
+        let drift = nanoseconds(&since_sync) as f64 * self.drift_ppm / 1_000_000.0;
+        let error = if synced {
+            Duration::zero()
+        } else {
+            self.offset
+        } + Duration::nanoseconds(drift as i64);
+        actual.clone() + error
+    }
+
+    pub fn apply<Tz: TimeZone>(
+        &self,
+        times: &[DateTime<Tz>],
+    ) -> Result<Vec<SkewedTime<Tz>>, Error> {
+        //! The times events happened at as the host logs them, in the
+        //! order they are delivered in; the clock is `offset` off at
+        //! the first of the times
+        self.validate()?;
+        let origin = match times.iter().min() {
+            Some(origin) => origin.clone(),
+            None => return Ok(Vec::new()),
+        };
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let max_delay = nanoseconds(&self.max_delay);
+        let delay = |rng: &mut StdRng, late: bool| {
+            if late && max_delay > 0 {
+                Duration::nanoseconds(rng.gen_range(1..=max_delay))
+            } else {
+                Duration::zero()
+            }
+        };
+
+        let mut events = Vec::with_capacity(times.len());
+        for (index, actual) in times.iter().enumerate() {
+            let recorded = self.recorded_time(actual, &origin);
+            let late = rng.gen_bool(self.reorder_probability);
+            events.push(SkewedTime {
+                index,
+                actual: actual.clone(),
+                recorded: recorded.clone(),
+                delivered: actual.clone() + delay(&mut rng, late),
+                duplicate: false,
+            });
+            if rng.gen_bool(self.duplicate_probability) {
+                // a retry: the same record, delivered again a little later
+                events.push(SkewedTime {
+                    index,
+                    actual: actual.clone(),
+                    recorded,
+                    delivered: actual.clone() + delay(&mut rng, true),
+                    duplicate: true,
+                });
+            }
+        }
+        events.sort_by(|a, b| {
+            a.delivered
+                .cmp(&b.delivered)
+                .then(a.index.cmp(&b.index))
+                .then(a.duplicate.cmp(&b.duplicate))
+        });
+        Ok(events)
+    }
+}
+
+fn nanoseconds(duration: &Duration) -> i64 {
+    duration.num_nanoseconds().unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_skew() {
+        let origin = Utc.with_ymd_and_hms(2023, 10, 18, 0, 0, 0).unwrap();
+        let clock = ClockSkew::new()
+            .offset(Duration::seconds(2))
+            .drift_ppm(100.0)
+            .ntp_interval(Duration::seconds(1000));
+        assert_eq!(
+            clock.recorded_time(&origin, &origin),
+            origin + Duration::seconds(2)
+        );
+        // 100 ppm of 500 seconds: 50 ms
+        let actual = origin + Duration::seconds(500);
+        assert_eq!(
+            clock.recorded_time(&actual, &origin),
+            actual + Duration::milliseconds(2050)
+        );
+        // stepped at 1000 seconds, then drifting again
+        let actual = origin + Duration::seconds(1100);
+        assert_eq!(
+            clock.recorded_time(&actual, &origin),
+            actual + Duration::milliseconds(10)
+        );
+        assert!(ClockSkew::new().duplicates(1.5).validate().is_err());
+
+        let start = origin;
+        let times = (0..10_000)
+            .map(|i| start + Duration::seconds(i))
+            .collect::<Vec<_>>();
+
+        let perfect = ClockSkew::new().apply(&times).unwrap();
+        assert!(perfect
+            .iter()
+            .zip(times.iter())
+            .all(|(e, t)| e.recorded == *t && e.delivered == *t));
+
+        let host = ClockSkew::new()
+            .reordering(0.05, Duration::seconds(10))
+            .duplicates(0.01)
+            .seed(3);
+        let events = host.apply(&times).unwrap();
+        assert_eq!(events, host.apply(&times).unwrap());
+        let duplicates = events.iter().filter(|e| e.duplicate).count();
+        assert_eq!(events.len(), times.len() + duplicates);
+        assert!(duplicates > 0);
+        assert!(events.windows(2).all(|w| w[0].delivered <= w[1].delivered));
+        // late events leave the recorded times out of order
+        assert!(events.windows(2).any(|w| w[0].recorded > w[1].recorded));
+    }
+}
@@ -0,0 +1,207 @@
+/*
+Streaming time generators
+Iterators of ordered times that are generated as they are
+consumed, so any number of them takes constant memory
+*/
+
+use chrono::{DateTime, Duration, TimeZone};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::InterArrival;
+
+type Error = String;
+
+fn check_range<Tz: TimeZone>(start: &DateTime<Tz>, end: &DateTime<Tz>) -> Result<(), Error> {
+    if start >= end {
+        return Err(String::from(
+            "start date/time is greater than end date/time",
+        ));
+    }
+    Ok(())
+}
+
+/// Times from a start to an end, the gaps between them drawn
+/// from an inter-arrival distribution; the start & the end are
+/// the first & the last times
+///
+/// ## Example
+/// ```rust
+/// use chrono::{Duration, TimeZone, Utc};
+/// use random::datetime::{InterArrival, TimeStream};
+///
+/// let start = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2101, 1, 1, 0, 0, 0).unwrap();
+///
+/// // a century of events, a few at a time
+/// let stream = TimeStream::new(&start, &end, InterArrival::Exponential(Duration::seconds(1)))
+///     .unwrap()
+///     .seed(42);
+/// for time in stream.take(5) {
+///     println!("{}", time);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeStream<Tz: TimeZone> {
+    next: Option<DateTime<Tz>>,
+    end: DateTime<Tz>,
+    inter_arrival: InterArrival,
+    rng: StdRng,
+}
+
+impl<Tz: TimeZone> TimeStream<Tz> {
+    pub fn new(
+        start: &DateTime<Tz>,
+        end: &DateTime<Tz>,
+        inter_arrival: InterArrival,
+    ) -> Result<Self, Error> {
+        check_range(start, end)?;
+        inter_arrival.validate()?;
+        Ok(TimeStream {
+            next: Some(start.clone()),
+            end: end.clone(),
+            inter_arrival,
+            rng: StdRng::from_entropy(),
+        })
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        //! Same seed, same times
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl<Tz: TimeZone> Iterator for TimeStream<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        if current < self.end {
+            // the distribution was validated, so sampling cannot fail
+            let gap = self
+                .inter_arrival
+                .sample(&mut self.rng)
+                .unwrap_or_else(|_| Duration::zero());
+            self.next = match current.clone().checked_add_signed(gap) {
+                Some(next) if next < self.end => Some(next),
+                _ => Some(self.end.clone()),
+            };
+        }
+        Some(current)
+    }
+}
+
+/// A given number of times spread uniformly between a start
+/// (included) & an end (excluded), generated in order
+///
+/// ## Example
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use random::datetime::UniformTimeStream;
+///
+/// let start = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2002, 1, 1, 0, 0, 0).unwrap();
+///
+/// // a billion sorted times, without holding them
+/// let stream = UniformTimeStream::new(&start, &end, 1_000_000_000).unwrap();
+/// assert_eq!(stream.len(), 1_000_000_000);
+/// let first = stream.take(3).collect::<Vec<_>>();
+/// assert!(first[0] <= first[1] && first[1] <= first[2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniformTimeStream<Tz: TimeZone> {
+    start: DateTime<Tz>,
+    nanoseconds: i64,
+    remaining: usize,
+    /// where the last time was, from 0 (start) to 1 (end)
+    position: f64,
+    rng: StdRng,
+}
+
+impl<Tz: TimeZone> UniformTimeStream<Tz> {
+    pub fn new(start: &DateTime<Tz>, end: &DateTime<Tz>, count: usize) -> Result<Self, Error> {
+        check_range(start, end)?;
+        let nanoseconds = (end.clone() - start.clone())
+            .num_nanoseconds()
+            .ok_or_else(|| String::from("Error: date/time range is too long"))?;
+        Ok(UniformTimeStream {
+            start: start.clone(),
+            nanoseconds,
+            remaining: count,
+            position: 0.0,
+            rng: StdRng::from_entropy(),
+        })
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        //! Same seed, same times
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl<Tz: TimeZone> Iterator for UniformTimeStream<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        // the smallest of the n uniform times left past the last one
+        // is 1 - u^(1/n) of the way to the end, u uniform in (0, 1]
+        let u = 1.0 - self.rng.gen::<f64>();
+        let step = -(u.ln() / self.remaining as f64).exp_m1();
+        self.position += (1.0 - self.position) * step;
+        self.remaining -= 1;
+        let offset = ((self.position * self.nanoseconds as f64) as i64).min(self.nanoseconds - 1);
+        Some(self.start.clone() + Duration::nanoseconds(offset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Tz: TimeZone> ExactSizeIterator for UniformTimeStream<Tz> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_streams() {
+        let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+
+        let times = TimeStream::new(&start, &end, InterArrival::Fixed(Duration::hours(5)))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(times.len(), 6);
+        assert_eq!(times.first(), Some(&start));
+        assert_eq!(times.last(), Some(&end));
+
+        let stream = TimeStream::new(&start, &end, InterArrival::default()).unwrap();
+        assert_eq!(
+            stream.clone().seed(7).collect::<Vec<_>>(),
+            stream.seed(7).collect::<Vec<_>>()
+        );
+
+        let times = UniformTimeStream::new(&start, &end, 100_000)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(times.len(), 100_000);
+        assert!(times.windows(2).all(|w| w[0] <= w[1]));
+        assert!(times.iter().all(|t| *t >= start && *t < end));
+        // uniform: about half of them in the first half of the day
+        let first_half = times
+            .iter()
+            .filter(|t| **t < start + Duration::hours(12))
+            .count();
+        assert!((first_half as i64 - 50_000).abs() < 1_500);
+
+        assert!(TimeStream::new(&end, &start, InterArrival::default()).is_err());
+        assert!(UniformTimeStream::new(&end, &start, 1).is_err());
+    }
+}
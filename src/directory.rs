@@ -0,0 +1,959 @@
+/*
+Organisational directory (Active Directory / LDAP) generators
+A whole fake domain: OUs per site & department, users, nested groups,
+computers & service accounts, each with a DN, SID and objectGUID;
+exportable as LDIF
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::accounts::{netbios_name, AccountName, ServiceAccountStyle};
+use crate::core::constants::SERVICE_ACCOUNT_APPLICATIONS;
+use crate::core::utils::ascii_fold;
+use crate::operatingsystems::{localhost_names, servername};
+use crate::sids::{DomainRid, DomainSids, Sid};
+use crate::users::{User, UserGenerator};
+
+pub use crate::users::MAX_COMPUTER_NAME_LENGTH;
+
+type Error = String;
+
+/// userAccountControl: NORMAL_ACCOUNT
+const UAC_NORMAL_ACCOUNT: u32 = 0x0200;
+/// userAccountControl: NORMAL_ACCOUNT | DONT_EXPIRE_PASSWORD
+const UAC_SERVICE_ACCOUNT: u32 = 0x0200 | 0x1_0000;
+/// userAccountControl: WORKSTATION_TRUST_ACCOUNT
+const UAC_WORKSTATION_TRUST_ACCOUNT: u32 = 0x1000;
+/// groupType: global security group
+const GLOBAL_SECURITY_GROUP: i32 = 0x8000_0002_u32 as i32;
+/// max length of a folded LDIF line
+const LDIF_LINE_LENGTH: usize = 76;
+
+static WORKSTATION_OPERATING_SYSTEMS: &[(&str, &str)] = &[
+    ("Windows 10 Enterprise", "10.0 (19045)"),
+    ("Windows 11 Enterprise", "10.0 (22631)"),
+    ("Windows 11 Pro", "10.0 (26100)"),
+];
+static SERVER_OPERATING_SYSTEMS: &[(&str, &str)] = &[
+    ("Windows Server 2016 Standard", "10.0 (14393)"),
+    ("Windows Server 2019 Standard", "10.0 (17763)"),
+    ("Windows Server 2022 Datacenter", "10.0 (20348)"),
+];
+static LINUX_OPERATING_SYSTEMS: &[(&str, &str)] = &[
+    ("Ubuntu", "22.04"),
+    ("Red Hat Enterprise Linux", "9.4"),
+    ("SUSE Linux Enterprise Server", "15 SP5"),
+];
+static MAC_OPERATING_SYSTEMS: &[(&str, &str)] = &[
+    ("macOS Ventura", "13.6"),
+    ("macOS Sonoma", "14.7"),
+    ("macOS Sequoia", "15.1"),
+];
+static CHROME_OPERATING_SYSTEMS: &[(&str, &str)] = &[
+    ("ChromeOS", "126.0.6478.222"),
+    ("ChromeOS Flex", "128.0.6613.153"),
+];
+
+fn workstation_operating_systems(name: &str) -> &'static [(&'static str, &'static str)] {
+    // the suffix of a user's workstation tells its os, eg: JSMITH-MAC
+    match name.rsplit('-').next() {
+        Some("LINUX") => LINUX_OPERATING_SYSTEMS,
+        Some("MAC") | Some("OSX") => MAC_OPERATING_SYSTEMS,
+        Some("CHROMEBOOK") => CHROME_OPERATING_SYSTEMS,
+        _ => WORKSTATION_OPERATING_SYSTEMS,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrganizationalUnit {
+    pub name: String,
+    pub dn: String,
+    pub description: String,
+    pub guid: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryUser {
+    pub user: User,
+    pub dn: String,
+    pub sid: Sid,
+    pub guid: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub name: String,
+    pub dn: String,
+    pub description: String,
+    pub sid: Sid,
+    pub guid: Uuid,
+    /// DNs of the member users & groups
+    pub members: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Computer {
+    /// NetBIOS name, eg: DESKTOP-7KQ2M4X
+    pub name: String,
+    pub dn: String,
+    pub dns_host_name: String,
+    pub operating_system: String,
+    pub operating_system_version: String,
+    pub server: bool,
+    pub sid: Sid,
+    pub guid: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceAccount {
+    pub account: AccountName,
+    /// application the account runs, eg: sql
+    pub application: String,
+    pub dn: String,
+    pub sid: Sid,
+    pub guid: Uuid,
+}
+
+/// A generated domain with everything in it
+///
+/// ## Example
+/// ```rust
+/// use random::directory::Directory;
+///
+/// let directory = Directory::new(Some("Acme"), Some(2), Some(25)).unwrap();
+/// println!("{} ({})", directory.base_dn, directory.domain_sid);
+/// for group in &directory.groups {
+///     println!("{}: {} members", group.name, group.members.len());
+/// }
+/// std::fs::write("/tmp/acme.ldif", directory.ldif()).ok();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directory {
+    pub employer: String,
+    /// dns domain, eg: acme.com
+    pub domain: String,
+    /// eg: ACME
+    pub netbios_name: String,
+    /// eg: DC=acme,DC=com
+    pub base_dn: String,
+    /// eg: S-1-5-21-1004336348-1177238915-682003330
+    pub domain_sid: Sid,
+    /// parents always come before their children
+    pub organizational_units: Vec<OrganizationalUnit>,
+    pub users: Vec<DirectoryUser>,
+    pub groups: Vec<Group>,
+    pub computers: Vec<Computer>,
+    pub service_accounts: Vec<ServiceAccount>,
+}
+
+/// Keeps the RIDs, DNs & computer names unique while a directory is built
+struct Builder {
+    domain: String,
+    sids: DomainSids,
+    dns: HashSet<String>,
+    computer_names: HashSet<String>,
+    organizational_units: Vec<OrganizationalUnit>,
+}
+
+impl Builder {
+    fn sid(&mut self, account: &str) -> Sid {
+        self.sids.sid(account)
+    }
+
+    fn dn(&mut self, cn: &str, fallback: &str, parent: &str) -> String {
+        // two objects cannot share a DN; fall back to a name that is unique
+        let dn = format!("CN={},{}", escape_dn_value(cn), parent);
+        if self.dns.insert(dn.to_ascii_lowercase()) {
+            return dn;
+        }
+        let dn = format!(
+            "CN={} ({}),{}",
+            escape_dn_value(cn),
+            escape_dn_value(fallback),
+            parent
+        );
+        self.dns.insert(dn.to_ascii_lowercase());
+        dn
+    }
+
+    fn organizational_unit(&mut self, name: &str, parent: &str, description: &str) -> String {
+        let dn = format!("OU={},{}", escape_dn_value(name), parent);
+        if self.dns.insert(dn.to_ascii_lowercase()) {
+            self.organizational_units.push(OrganizationalUnit {
+                name: name.to_string(),
+                dn: dn.clone(),
+                description: description.to_string(),
+                guid: Uuid::new_v4(),
+            });
+        }
+        dn
+    }
+
+    fn computer(
+        &mut self,
+        name: &str,
+        parent: &str,
+        server: bool,
+        operating_systems: &[(&str, &str)],
+    ) -> Computer {
+        let name = self.computer_name(name);
+        let (operating_system, operating_system_version) = operating_systems
+            .choose(&mut rand::thread_rng())
+            .copied()
+            .unwrap_or(WORKSTATION_OPERATING_SYSTEMS[0]);
+        Computer {
+            dn: self.dn(&name, &name, parent),
+            dns_host_name: format!("{}.{}", name.to_ascii_lowercase(), self.domain),
+            operating_system: operating_system.to_string(),
+            operating_system_version: operating_system_version.to_string(),
+            server,
+            sid: self.sid(&format!("{}$", name)),
+            guid: Uuid::new_v4(),
+            name,
+        }
+    }
+
+    fn computer_name(&mut self, name: &str) -> String {
+        let base: String = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let candidate: String = base.chars().take(MAX_COMPUTER_NAME_LENGTH).collect();
+        if self.computer_names.insert(candidate.clone()) {
+            return candidate;
+        }
+        let mut n: u32 = 1;
+        loop {
+            let suffix = format!("{:02}", n);
+            let stem: String = base
+                .chars()
+                .take(MAX_COMPUTER_NAME_LENGTH - suffix.len())
+                .collect();
+            let candidate = format!("{}{}", stem, suffix);
+            if self.computer_names.insert(candidate.clone()) {
+                return candidate;
+            }
+            n += 1;
+        }
+    }
+}
+
+impl Directory {
+    pub fn new(
+        employer: Option<&str>,
+        offices: Option<usize>,
+        users: Option<usize>,
+    ) -> Result<Self, Error> {
+        //! Generate a whole directory
+        //! - `employer`: company owning the domain - random if not given
+        //! - `offices`: number of sites - 1 to 5 if not given
+        //! - `users`: number of employees - 10 to 200 if not given
+        let mut trng = rand::thread_rng();
+        let mut generator = UserGenerator::new(employer, offices, None)?;
+        let user_count = users.unwrap_or_else(|| trng.gen_range(10..=200));
+
+        let domain = generator.domain().to_string();
+        let base_dn = domain
+            .split('.')
+            .map(|label| format!("DC={}", escape_dn_value(label)))
+            .collect::<Vec<String>>()
+            .join(",");
+        let mut builder = Builder {
+            domain: domain.clone(),
+            sids: DomainSids::new(None),
+            dns: HashSet::new(),
+            computer_names: HashSet::new(),
+            organizational_units: Vec::new(),
+        };
+
+        // sites, each with their users & computers
+        let mut sites = HashMap::new();
+        for office in generator.offices().to_vec() {
+            let site = builder.organizational_unit(
+                &office.city,
+                &base_dn,
+                &format!("{} office", office.country),
+            );
+            let site_users =
+                builder.organizational_unit("Users", &site, &format!("{} users", office.city));
+            let site_computers = builder.organizational_unit(
+                "Computers",
+                &site,
+                &format!("{} workstations", office.city),
+            );
+            sites.insert(office.city.clone(), (site_users, site_computers));
+        }
+        let groups_ou = builder.organizational_unit("Groups", &base_dn, "Security groups");
+        let servers_ou = builder.organizational_unit("Servers", &base_dn, "Member servers");
+        let service_accounts_ou =
+            builder.organizational_unit("Service Accounts", &base_dn, "Application accounts");
+
+        let mut directory_users = Vec::new();
+        let mut computers = Vec::new();
+        for _ in 0..user_count {
+            let user = generator.user()?;
+            let (site_users, site_computers) = &sites[&user.office.city];
+            let department_ou = builder.organizational_unit(
+                &user.department,
+                site_users,
+                &format!("{} - {}", user.department, user.office.city),
+            );
+            let dn = builder.dn(
+                &user.name.full_name(),
+                &user.account.username,
+                &department_ou,
+            );
+            // users come first, so their workstations keep their (unique) names
+            computers.push(builder.computer(
+                &user.workstation,
+                site_computers,
+                false,
+                workstation_operating_systems(&user.workstation),
+            ));
+            directory_users.push(DirectoryUser {
+                sid: builder.sid(&user.account.username),
+                guid: Uuid::new_v4(),
+                user,
+                dn,
+            });
+        }
+
+        // member servers & a few spare (unassigned) workstations
+        let server_count = (user_count / 20).clamp(2, 50);
+        for _ in 0..server_count {
+            let (name, operating_systems) = if trng.gen_bool(0.3) {
+                (servername(None, Some(true)), LINUX_OPERATING_SYSTEMS)
+            } else {
+                (servername(Some(true), None), SERVER_OPERATING_SYSTEMS)
+            };
+            computers.push(builder.computer(&name, &servers_ou, true, operating_systems));
+        }
+        let spare_count = (user_count / 10).max(1);
+        for (_, (_, site_computers)) in sites.iter() {
+            for name in localhost_names(Some(true), None, None, None).take(spare_count) {
+                computers.push(builder.computer(
+                    &name,
+                    site_computers,
+                    false,
+                    WORKSTATION_OPERATING_SYSTEMS,
+                ));
+            }
+        }
+
+        // service accounts follow one naming convention per company
+        let style = *[
+            ServiceAccountStyle::SvcUnderscore,
+            ServiceAccountStyle::SvcDash,
+            ServiceAccountStyle::SaDash,
+            ServiceAccountStyle::AppSuffix,
+        ]
+        .choose(&mut trng)
+        .unwrap_or(&ServiceAccountStyle::SvcUnderscore);
+        let application_count = trng.gen_range(3..=8);
+        let applications = SERVICE_ACCOUNT_APPLICATIONS
+            .choose_multiple(&mut trng, application_count)
+            .cloned()
+            .collect::<Vec<&str>>();
+        let mut service_accounts = Vec::new();
+        for application in applications {
+            let account = generator.service_account(Some(application), Some(style));
+            service_accounts.push(ServiceAccount {
+                dn: builder.dn(&account.username, &account.username, &service_accounts_ou),
+                sid: builder.sid(&account.username),
+                guid: Uuid::new_v4(),
+                application: application.to_string(),
+                account,
+            });
+        }
+
+        let groups = groups(
+            &mut builder,
+            &groups_ou,
+            &directory_users,
+            &service_accounts,
+        );
+
+        Ok(Directory {
+            employer: generator.employer().to_string(),
+            netbios_name: netbios_name(&domain),
+            organizational_units: builder.organizational_units,
+            users: directory_users,
+            service_accounts,
+            computers,
+            groups,
+            domain_sid: builder.sids.domain_sid().clone(),
+            base_dn,
+            domain,
+        })
+    }
+
+    pub fn member_of(&self, dn: &str) -> Vec<&Group> {
+        //! Every group an object is a member of, directly or through nesting
+        let mut found: Vec<&Group> = Vec::new();
+        let mut pending = vec![dn.to_string()];
+        while let Some(member) = pending.pop() {
+            for group in &self.groups {
+                if group.members.contains(&member) && !found.iter().any(|g| g.dn == group.dn) {
+                    found.push(group);
+                    pending.push(group.dn.clone());
+                }
+            }
+        }
+        found
+    }
+
+    pub fn ldif(&self) -> String {
+        //! Export as LDIF with the Active Directory schema, ready for
+        //! `ldbadd` (Samba); the domain root itself is left out as it
+        //! exists already. Entries are ordered so that parents and group
+        //! members are added before whatever refers to them
+        let mut ldif = String::from("version: 1\n");
+        for ou in &self.organizational_units {
+            let mut entry = Entry::new(&ou.dn);
+            entry.values("objectClass", &["top", "organizationalUnit"]);
+            entry.value("ou", &ou.name);
+            entry.value("description", &ou.description);
+            entry.binary("objectGUID", &guid_bytes(&ou.guid));
+            ldif.push_str(&entry.finish());
+        }
+        for user in &self.users {
+            let person = &user.user;
+            let mut entry = Entry::new(&user.dn);
+            entry.values(
+                "objectClass",
+                &["top", "person", "organizationalPerson", "user"],
+            );
+            entry.value("cn", &rdn_value(&user.dn));
+            entry.value("sn", &person.name.last_name);
+            entry.value("givenName", &person.name.first_name);
+            entry.value("displayName", &person.name.full_name());
+            entry.value("initials", &person.name.initials());
+            entry.value("sAMAccountName", &person.account.username);
+            entry.value("userPrincipalName", &person.account.user_principal_name);
+            entry.value("mail", &person.email);
+            entry.value("telephoneNumber", &person.phone);
+            entry.value("title", &person.job_title);
+            entry.value("department", &person.department);
+            entry.value("company", &person.employer);
+            entry.value("physicalDeliveryOfficeName", &person.office.city);
+            entry.value("l", &person.office.city);
+            entry.value("c", &person.office.country_code);
+            entry.value("co", &person.office.country);
+            entry.value("userAccountControl", &UAC_NORMAL_ACCOUNT.to_string());
+            entry.binary("objectSid", &user.sid.to_bytes());
+            entry.binary("objectGUID", &guid_bytes(&user.guid));
+            ldif.push_str(&entry.finish());
+        }
+        for account in &self.service_accounts {
+            let mut entry = Entry::new(&account.dn);
+            entry.values(
+                "objectClass",
+                &["top", "person", "organizationalPerson", "user"],
+            );
+            entry.value("cn", &rdn_value(&account.dn));
+            entry.value("sAMAccountName", &account.account.username);
+            entry.value("userPrincipalName", &account.account.user_principal_name);
+            entry.value(
+                "description",
+                &format!("Service account for {}", account.application),
+            );
+            entry.value("userAccountControl", &UAC_SERVICE_ACCOUNT.to_string());
+            entry.binary("objectSid", &account.sid.to_bytes());
+            entry.binary("objectGUID", &guid_bytes(&account.guid));
+            ldif.push_str(&entry.finish());
+        }
+        for computer in &self.computers {
+            let mut entry = Entry::new(&computer.dn);
+            entry.values(
+                "objectClass",
+                &["top", "person", "organizationalPerson", "user", "computer"],
+            );
+            entry.value("cn", &computer.name);
+            entry.value("sAMAccountName", &format!("{}$", computer.name));
+            entry.value("dNSHostName", &computer.dns_host_name);
+            entry.value("operatingSystem", &computer.operating_system);
+            entry.value("operatingSystemVersion", &computer.operating_system_version);
+            entry.value(
+                "userAccountControl",
+                &UAC_WORKSTATION_TRUST_ACCOUNT.to_string(),
+            );
+            entry.value(
+                "primaryGroupID",
+                &DomainRid::DomainComputers.rid().to_string(),
+            );
+            entry.binary("objectSid", &computer.sid.to_bytes());
+            entry.binary("objectGUID", &guid_bytes(&computer.guid));
+            ldif.push_str(&entry.finish());
+        }
+        for group in &self.groups {
+            let mut entry = Entry::new(&group.dn);
+            entry.values("objectClass", &["top", "group"]);
+            entry.value("cn", &group.name);
+            entry.value("sAMAccountName", &group.name);
+            entry.value("description", &group.description);
+            entry.value("groupType", &GLOBAL_SECURITY_GROUP.to_string());
+            for member in &group.members {
+                entry.value("member", member);
+            }
+            entry.binary("objectSid", &group.sid.to_bytes());
+            entry.binary("objectGUID", &guid_bytes(&group.guid));
+            ldif.push_str(&entry.finish());
+        }
+        ldif
+    }
+
+    pub fn openldap_ldif(&self) -> String {
+        //! Export as LDIF with the standard schemas of OpenLDAP (core,
+        //! cosine & inetorgperson), ready for `ldapadd`: users and service
+        //! accounts are inetOrgPersons, groups groupOfNames & computers
+        //! devices; SIDs & GUIDs have no place there and are left out
+        let mut ldif = String::from("version: 1\n");
+        for ou in &self.organizational_units {
+            let mut entry = Entry::new(&ou.dn);
+            entry.values("objectClass", &["top", "organizationalUnit"]);
+            entry.value("ou", &ou.name);
+            entry.value("description", &ou.description);
+            ldif.push_str(&entry.finish());
+        }
+        for user in &self.users {
+            let person = &user.user;
+            let mut entry = Entry::new(&user.dn);
+            entry.values(
+                "objectClass",
+                &["top", "person", "organizationalPerson", "inetOrgPerson"],
+            );
+            entry.value("cn", &rdn_value(&user.dn));
+            entry.value("sn", &person.name.last_name);
+            entry.value("givenName", &person.name.first_name);
+            entry.value("displayName", &person.name.full_name());
+            entry.value("initials", &person.name.initials());
+            entry.value("uid", &person.account.username);
+            entry.value("mail", &person.email);
+            entry.value("telephoneNumber", &person.phone);
+            entry.value("title", &person.job_title);
+            entry.value("ou", &person.department);
+            entry.value("o", &person.employer);
+            entry.value("physicalDeliveryOfficeName", &person.office.city);
+            entry.value("l", &person.office.city);
+            ldif.push_str(&entry.finish());
+        }
+        for account in &self.service_accounts {
+            let mut entry = Entry::new(&account.dn);
+            entry.values(
+                "objectClass",
+                &["top", "person", "organizationalPerson", "inetOrgPerson"],
+            );
+            entry.value("cn", &rdn_value(&account.dn));
+            entry.value("sn", &account.account.username);
+            entry.value("uid", &account.account.username);
+            entry.value(
+                "description",
+                &format!("Service account for {}", account.application),
+            );
+            ldif.push_str(&entry.finish());
+        }
+        for computer in &self.computers {
+            let mut entry = Entry::new(&computer.dn);
+            entry.values("objectClass", &["top", "device"]);
+            entry.value("cn", &computer.name);
+            entry.value(
+                "description",
+                &format!(
+                    "{} - {} {}",
+                    computer.dns_host_name,
+                    computer.operating_system,
+                    computer.operating_system_version
+                ),
+            );
+            for user in self
+                .users
+                .iter()
+                .filter(|u| u.user.workstation == computer.name)
+            {
+                entry.value("owner", &user.dn);
+            }
+            ldif.push_str(&entry.finish());
+        }
+        for group in &self.groups {
+            let mut entry = Entry::new(&group.dn);
+            entry.values("objectClass", &["top", "groupOfNames"]);
+            entry.value("cn", &group.name);
+            entry.value("description", &group.description);
+            // member is mandatory: an empty group has the empty DN as its member
+            if group.members.is_empty() {
+                entry.value("member", "");
+            }
+            for member in &group.members {
+                entry.value("member", member);
+            }
+            ldif.push_str(&entry.finish());
+        }
+        ldif
+    }
+}
+
+fn groups(
+    builder: &mut Builder,
+    groups_ou: &str,
+    users: &[DirectoryUser],
+    service_accounts: &[ServiceAccount],
+) -> Vec<Group> {
+    // leaf groups come first so that every member exists
+    // by the time the group referring to it is added
+    let mut groups: Vec<Group> = Vec::new();
+    let mut group = |builder: &mut Builder, name: String, description: String, members| {
+        let group = Group {
+            dn: builder.dn(&name, &name, groups_ou),
+            sid: builder.sid(&name),
+            guid: Uuid::new_v4(),
+            name,
+            description,
+            members,
+        };
+        groups.push(group.clone());
+        group.dn
+    };
+
+    let mut departments: Vec<&str> = Vec::new();
+    let mut sites: Vec<&str> = Vec::new();
+    for user in users {
+        if !departments.contains(&user.user.department.as_str()) {
+            departments.push(&user.user.department);
+        }
+        if !sites.contains(&user.user.office.city.as_str()) {
+            sites.push(&user.user.office.city);
+        }
+    }
+
+    let mut department_groups = Vec::new();
+    let mut it_admins = Vec::new();
+    for department in departments {
+        let members = users
+            .iter()
+            .filter(|u| u.user.department == department)
+            .map(|u| u.dn.clone())
+            .collect::<Vec<String>>();
+        if department == "Information Technology" {
+            it_admins = members.clone();
+        }
+        department_groups.push(group(
+            builder,
+            format!("GG-{}", group_name(department)),
+            format!("All members of {}", department),
+            members,
+        ));
+    }
+    for site in sites {
+        let members = users
+            .iter()
+            .filter(|u| u.user.office.city == site)
+            .map(|u| u.dn.clone())
+            .collect::<Vec<String>>();
+        group(
+            builder,
+            format!("GG-Site-{}", group_name(site)),
+            format!("Everyone working in {}", site),
+            members,
+        );
+    }
+    let service_accounts_group = group(
+        builder,
+        "GG-Service-Accounts".to_string(),
+        "Application service accounts".to_string(),
+        service_accounts.iter().map(|a| a.dn.clone()).collect(),
+    );
+    let it_admins = group(
+        builder,
+        "IT Admins".to_string(),
+        "Administrators of workstations".to_string(),
+        it_admins,
+    );
+    group(
+        builder,
+        "Server Admins".to_string(),
+        "Administrators of member servers".to_string(),
+        vec![it_admins, service_accounts_group],
+    );
+    group(
+        builder,
+        "All Staff".to_string(),
+        "Every employee".to_string(),
+        department_groups,
+    );
+    groups
+}
+
+fn group_name(text: &str) -> String {
+    ascii_fold(text)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+pub fn escape_dn_value(value: &str) -> String {
+    /*
+    Escape an attribute value for use in a DN (RFC 4514)
+    :param value: eg: Smith, John
+    :return: eg: Smith\, John
+    */
+    let last = value.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        let special = matches!(c, ',' | '+' | '"' | '\\' | '<' | '>' | ';' | '=')
+            || (i == 0 && (c == '#' || c == ' '))
+            || (i == last && c == ' ');
+        if special {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn rdn_value(dn: &str) -> String {
+    // value of the first RDN, unescaped
+    let mut value = String::new();
+    let mut chars = dn
+        .split_once('=')
+        .map(|(_, rest)| rest)
+        .unwrap_or("")
+        .chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next()),
+            ',' => break,
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
+pub fn guid_bytes(guid: &Uuid) -> [u8; 16] {
+    /*
+    Binary form of a GUID as windows stores it (objectGUID);
+    the first three fields are little endian
+    :param guid: the guid
+    :return: 16 bytes
+    */
+    guid.to_bytes_le()
+}
+
+/// One LDIF record; values that are not safe strings are base64 encoded
+struct Entry {
+    lines: Vec<String>,
+}
+
+impl Entry {
+    fn new(dn: &str) -> Self {
+        let mut entry = Entry { lines: Vec::new() };
+        entry.value("dn", dn);
+        entry
+    }
+
+    fn value(&mut self, attribute: &str, value: &str) {
+        if is_safe_string(value) {
+            self.lines.push(format!("{}: {}", attribute, value));
+        } else {
+            self.binary(attribute, value.as_bytes());
+        }
+    }
+
+    fn values(&mut self, attribute: &str, values: &[&str]) {
+        for value in values {
+            self.value(attribute, value);
+        }
+    }
+
+    fn binary(&mut self, attribute: &str, value: &[u8]) {
+        self.lines
+            .push(format!("{}:: {}", attribute, BASE64.encode(value)));
+    }
+
+    fn finish(self) -> String {
+        let mut record = String::from("\n");
+        for line in self.lines {
+            record.push_str(&fold(&line));
+        }
+        record
+    }
+}
+
+fn is_safe_string(value: &str) -> bool {
+    // SAFE-STRING of RFC 2849
+    !value.starts_with([' ', ':', '<'])
+        && !value.ends_with(' ')
+        && value
+            .bytes()
+            .all(|b| b.is_ascii() && b != b'\0' && b != b'\n' && b != b'\r')
+}
+
+fn fold(line: &str) -> String {
+    // lines are ascii (safe strings or base64), so splitting by bytes is fine
+    let mut folded = String::with_capacity(line.len() + line.len() / LDIF_LINE_LENGTH * 2 + 1);
+    let (first, mut rest) = line.split_at(line.len().min(LDIF_LINE_LENGTH));
+    folded.push_str(first);
+    folded.push('\n');
+    while !rest.is_empty() {
+        let (chunk, tail) = rest.split_at(rest.len().min(LDIF_LINE_LENGTH - 1));
+        folded.push(' ');
+        folded.push_str(chunk);
+        folded.push('\n');
+        rest = tail;
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_is_consistent() {
+        let directory = Directory::new(Some("Acme"), Some(2), Some(40)).unwrap();
+        assert_eq!(directory.users.len(), 40);
+        assert!(directory.base_dn.starts_with("DC=acme,DC="));
+        assert_eq!(directory.netbios_name, "ACME");
+
+        let mut sids = HashSet::new();
+        let mut guids = HashSet::new();
+        let mut dns = HashSet::new();
+        let objects = directory
+            .users
+            .iter()
+            .map(|u| (&u.dn, &u.sid, &u.guid))
+            .chain(directory.groups.iter().map(|g| (&g.dn, &g.sid, &g.guid)))
+            .chain(directory.computers.iter().map(|c| (&c.dn, &c.sid, &c.guid)))
+            .chain(
+                directory
+                    .service_accounts
+                    .iter()
+                    .map(|a| (&a.dn, &a.sid, &a.guid)),
+            );
+        for (dn, sid, guid) in objects {
+            assert!(dn.ends_with(&directory.base_dn));
+            assert_eq!(sid.domain().as_ref(), Some(&directory.domain_sid));
+            assert!(sids.insert(sid.clone()));
+            assert!(guids.insert(*guid));
+            assert!(dns.insert(dn.to_ascii_lowercase()));
+        }
+
+        for computer in &directory.computers {
+            assert!(computer.name.len() <= MAX_COMPUTER_NAME_LENGTH);
+        }
+        for user in &directory.users {
+            let computer = directory
+                .computers
+                .iter()
+                .find(|c| c.name == user.user.workstation)
+                .unwrap();
+            let os = &computer.operating_system;
+            match user.user.workstation.rsplit('-').next().unwrap() {
+                "LINUX" => assert!(LINUX_OPERATING_SYSTEMS.iter().any(|(o, _)| o == os)),
+                "MAC" | "OSX" => assert!(os.starts_with("macOS")),
+                "CHROMEBOOK" => assert!(os.starts_with("ChromeOS")),
+                _ => assert!(os.starts_with("Windows")),
+            }
+        }
+        for group in &directory.groups {
+            for member in &group.members {
+                assert!(dns.contains(&member.to_ascii_lowercase()));
+            }
+        }
+        for ou in &directory.organizational_units {
+            let (_, parent) = ou.dn.split_once(',').unwrap();
+            assert!(
+                parent == directory.base_dn
+                    || directory
+                        .organizational_units
+                        .iter()
+                        .any(|o| o.dn == parent)
+            );
+        }
+    }
+
+    #[test]
+    fn test_nested_membership() {
+        let directory = Directory::new(None, Some(1), Some(30)).unwrap();
+        let all_staff = directory
+            .groups
+            .iter()
+            .find(|g| g.name == "All Staff")
+            .unwrap();
+        for user in &directory.users {
+            let groups = directory.member_of(&user.dn);
+            assert!(groups.iter().any(|g| g.dn == all_staff.dn));
+            if user.user.department == "Information Technology" {
+                assert!(groups.iter().any(|g| g.name == "Server Admins"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ldif() {
+        assert_eq!(escape_dn_value("Smith, John"), "Smith\\, John");
+        assert_eq!(escape_dn_value("#1 "), "\\#1\\ ");
+        assert_eq!(
+            rdn_value("CN=Smith\\, John,OU=Users,DC=acme,DC=com"),
+            "Smith, John"
+        );
+
+        let guid = Uuid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+        assert_eq!(
+            guid_bytes(&guid),
+            [
+                0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff
+            ]
+        );
+
+        let mut entry = Entry::new("CN=Zoë Müller,OU=Users,DC=acme,DC=com");
+        entry.value("description", &"x".repeat(200));
+        let ldif = entry.finish();
+        assert!(ldif.starts_with("\ndn:: "));
+        assert!(ldif.lines().all(|l| l.len() <= LDIF_LINE_LENGTH));
+        assert_eq!(ldif.lines().filter(|l| l.starts_with(' ')).count(), 2);
+
+        let directory = Directory::new(Some("Acme"), Some(1), Some(10)).unwrap();
+        let ldif = directory.ldif();
+        assert!(ldif.starts_with("version: 1\n"));
+        assert_eq!(
+            ldif.matches("\ndn").count(),
+            directory.organizational_units.len()
+                + directory.users.len()
+                + directory.service_accounts.len()
+                + directory.computers.len()
+                + directory.groups.len()
+        );
+
+        let ldif = directory.openldap_ldif();
+        assert_eq!(
+            ldif.matches("\ndn").count(),
+            ldif.matches("\nobjectClass: top\n").count()
+        );
+        for ad_only in &[
+            "objectSid",
+            "objectGUID",
+            "sAMAccountName",
+            ": user\n",
+            ": group\n",
+        ] {
+            assert!(!ldif.contains(ad_only));
+        }
+        assert_eq!(
+            ldif.matches("\nobjectClass: inetOrgPerson\n").count(),
+            directory.users.len() + directory.service_accounts.len()
+        );
+        assert_eq!(
+            ldif.matches("\nobjectClass: device\n").count(),
+            directory.computers.len()
+        );
+    }
+}
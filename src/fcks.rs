@@ -32,3 +32,23 @@ pub fn fortune_cookie() -> &'static str {
         .unwrap_or(&COOKIES[0]);
     r
 }
+
+pub fn fortune_cookies() -> impl Iterator<Item = &'static str> {
+    /*
+    Endless stream of fortune cookies, see fortune_cookie
+    :return: iterator of fortune cookies
+    */
+    std::iter::repeat_with(fortune_cookie)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iterators() {
+        assert!(fortune_cookies()
+            .take(10)
+            .all(|cookie| COOKIES.contains(&cookie)));
+    }
+}
@@ -59,11 +59,12 @@ pub fn dga_domain(
         None => trng.gen_range(10..25),
     };
 
+    // the algorithm relies on 32 bit wraparound
     for _ in 0..selected_length {
-        selected_year =
-            ((selected_year ^ 8 * selected_year) >> 11) ^ ((selected_year & 0xFFFFFFF0) << 17);
-        selected_month =
-            ((selected_month ^ 4 * selected_month) >> 25) ^ 16 * (selected_month & 0xFFFFFFF8);
+        selected_year = ((selected_year ^ selected_year.wrapping_mul(8)) >> 11)
+            ^ ((selected_year & 0xFFFFFFF0) << 17);
+        selected_month = ((selected_month ^ selected_month.wrapping_mul(4)) >> 25)
+            ^ (selected_month & 0xFFFFFFF8).wrapping_mul(16);
         selected_day =
             ((selected_day ^ (selected_day << 13)) >> 19) ^ ((selected_day & 0xFFFFFFFE) << 12);
 
@@ -76,3 +77,35 @@ pub fn dga_domain(
     domain += selected_tld;
     domain
 }
+
+pub fn dga_domains<'a>(
+    year: Option<u32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    length: Option<u32>,
+    tld: Option<&'a str>,
+) -> impl Iterator<Item = String> + 'a {
+    /*
+    Endless stream of DGA domains, see dga_domain; the same for
+    every item when the date and length are all given
+    :return: iterator of domains
+    */
+    std::iter::repeat_with(move || dga_domain(year, month, day, length, tld))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iterators() {
+        assert!(dga_domains(None, None, None, Some(12), Some("com"))
+            .take(10)
+            .all(|d| d.len() == 16 && d.ends_with(".com")));
+        // the same seed date gives the same domain
+        let seeded = dga_domains(Some(2021), Some(3), Some(4), Some(12), Some("net"))
+            .take(3)
+            .collect::<Vec<String>>();
+        assert!(seeded.iter().all(|d| *d == seeded[0]));
+    }
+}
@@ -1,13 +1,28 @@
+#[cfg(feature = "credentials")]
+pub mod credentials;
+
 #[cfg(feature = "timegenerate")]
 pub mod datetime;
 
+#[cfg(feature = "directory")]
+pub mod directory;
+
 #[cfg(feature = "tz")]
 pub mod tz;
 
+#[cfg(feature = "tz")]
+pub mod users;
+
+#[cfg(feature = "timegenerate")]
+pub mod uuids;
+
+pub mod accounts;
 pub mod core;
 pub mod database;
 pub mod fcks;
 pub mod internet;
+pub mod names;
 pub mod networking;
 pub mod operatingsystems;
-
+pub mod sids;
+pub mod useragents;
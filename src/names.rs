@@ -0,0 +1,435 @@
+/*
+Person name generators
+First names, last names, full names, initials & usernames
+from the bundled per-locale datasets in `core::names`
+*/
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core::names::*;
+use crate::core::utils::ascii_fold;
+
+type Error = String;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gender {
+    Male,
+    Female,
+    Neutral,
+}
+
+impl Gender {
+    pub(crate) fn random_with<R: Rng + ?Sized>(rng: &mut R) -> Gender {
+        // gender-neutral names are a minority in every locale
+        match rng.gen_range(0..20) {
+            0..=8 => Gender::Male,
+            9..=17 => Gender::Female,
+            _ => Gender::Neutral,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    EnUs,
+    EnGb,
+    DeDe,
+    FrFr,
+    EsEs,
+    ItIt,
+    PtBr,
+    NlNl,
+    SvSe,
+    PlPl,
+    JaJp,
+    ZhCn,
+    HiIn,
+    RuRu,
+}
+
+impl Locale {
+    pub const ALL: &'static [Locale] = &[
+        Locale::EnUs,
+        Locale::EnGb,
+        Locale::DeDe,
+        Locale::FrFr,
+        Locale::EsEs,
+        Locale::ItIt,
+        Locale::PtBr,
+        Locale::NlNl,
+        Locale::SvSe,
+        Locale::PlPl,
+        Locale::JaJp,
+        Locale::ZhCn,
+        Locale::HiIn,
+        Locale::RuRu,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        //! Locale code, eg: `en_US`
+        match self {
+            Locale::EnUs => "en_US",
+            Locale::EnGb => "en_GB",
+            Locale::DeDe => "de_DE",
+            Locale::FrFr => "fr_FR",
+            Locale::EsEs => "es_ES",
+            Locale::ItIt => "it_IT",
+            Locale::PtBr => "pt_BR",
+            Locale::NlNl => "nl_NL",
+            Locale::SvSe => "sv_SE",
+            Locale::PlPl => "pl_PL",
+            Locale::JaJp => "ja_JP",
+            Locale::ZhCn => "zh_CN",
+            Locale::HiIn => "hi_IN",
+            Locale::RuRu => "ru_RU",
+        }
+    }
+
+    pub fn country_code(&self) -> &'static str {
+        //! 2 letter iso country code of the locale, eg: `US`
+        &self.code()[3..]
+    }
+
+    pub fn from_country_code(iso_code: &str) -> Option<Locale> {
+        //! Locale used for a 2 letter iso country code, if there is one
+        Locale::ALL
+            .iter()
+            .find(|l| l.country_code().eq_ignore_ascii_case(iso_code))
+            .copied()
+    }
+
+    pub fn random() -> Locale {
+        //! Gets a random locale
+        Self::random_with(&mut rand::thread_rng())
+    }
+
+    pub(crate) fn random_with<R: Rng + ?Sized>(rng: &mut R) -> Locale {
+        *Locale::ALL.choose(rng).unwrap_or(&Locale::EnUs)
+    }
+
+    pub(crate) fn first_names(&self, gender: Gender) -> &'static [&'static str] {
+        let (male, female, neutral) = match self {
+            Locale::EnUs => (EN_US_MALE, EN_US_FEMALE, EN_US_NEUTRAL),
+            Locale::EnGb => (EN_GB_MALE, EN_GB_FEMALE, EN_GB_NEUTRAL),
+            Locale::DeDe => (DE_DE_MALE, DE_DE_FEMALE, DE_DE_NEUTRAL),
+            Locale::FrFr => (FR_FR_MALE, FR_FR_FEMALE, FR_FR_NEUTRAL),
+            Locale::EsEs => (ES_ES_MALE, ES_ES_FEMALE, ES_ES_NEUTRAL),
+            Locale::ItIt => (IT_IT_MALE, IT_IT_FEMALE, IT_IT_NEUTRAL),
+            Locale::PtBr => (PT_BR_MALE, PT_BR_FEMALE, PT_BR_NEUTRAL),
+            Locale::NlNl => (NL_NL_MALE, NL_NL_FEMALE, NL_NL_NEUTRAL),
+            Locale::SvSe => (SV_SE_MALE, SV_SE_FEMALE, SV_SE_NEUTRAL),
+            Locale::PlPl => (PL_PL_MALE, PL_PL_FEMALE, PL_PL_NEUTRAL),
+            Locale::JaJp => (JA_JP_MALE, JA_JP_FEMALE, JA_JP_NEUTRAL),
+            Locale::ZhCn => (ZH_CN_MALE, ZH_CN_FEMALE, ZH_CN_NEUTRAL),
+            Locale::HiIn => (HI_IN_MALE, HI_IN_FEMALE, HI_IN_NEUTRAL),
+            Locale::RuRu => (RU_RU_MALE, RU_RU_FEMALE, RU_RU_NEUTRAL),
+        };
+        match gender {
+            Gender::Male => male,
+            Gender::Female => female,
+            Gender::Neutral => neutral,
+        }
+    }
+
+    pub(crate) fn last_names(&self) -> &'static [&'static str] {
+        match self {
+            Locale::EnUs => EN_US_LAST,
+            Locale::EnGb => EN_GB_LAST,
+            Locale::DeDe => DE_DE_LAST,
+            Locale::FrFr => FR_FR_LAST,
+            Locale::EsEs => ES_ES_LAST,
+            Locale::ItIt => IT_IT_LAST,
+            Locale::PtBr => PT_BR_LAST,
+            Locale::NlNl => NL_NL_LAST,
+            Locale::SvSe => SV_SE_LAST,
+            Locale::PlPl => PL_PL_LAST,
+            Locale::JaJp => JA_JP_LAST,
+            Locale::ZhCn => ZH_CN_LAST,
+            Locale::HiIn => HI_IN_LAST,
+            Locale::RuRu => RU_RU_LAST,
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.replace('-', "_");
+        Locale::ALL
+            .iter()
+            .find(|l| l.code().eq_ignore_ascii_case(&s))
+            .copied()
+            .ok_or_else(|| format!("Error: unknown locale '{}'", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PersonName {
+    pub first_name: String,
+    pub last_name: String,
+    pub gender: Gender,
+    pub locale: Locale,
+}
+
+impl PersonName {
+    pub fn full_name(&self) -> String {
+        //! First name followed by the last name
+        format!("{} {}", self.first_name, self.last_name)
+    }
+
+    pub fn initials(&self) -> String {
+        //! Upper case initials; name particles such as
+        //! `de` / `van den` are skipped, eg: `Jan de Jong` -> `JJ`
+        let last = self
+            .last_name
+            .split_whitespace()
+            .find(|part| part.starts_with(char::is_uppercase))
+            .unwrap_or(&self.last_name);
+        [self.first_name.as_str(), last]
+            .iter()
+            .filter_map(|part| part.chars().next())
+            .flat_map(char::to_uppercase)
+            .collect()
+    }
+
+    pub fn username(&self) -> String {
+        //! Plain ascii lower case username: first initial + last name,
+        //! eg: `Jürgen Müller` -> `jmuller`
+        let first = ascii_fold(&self.first_name);
+        let last = ascii_fold(&self.last_name);
+        format!(
+            "{}{}",
+            first.chars().next().map(String::from).unwrap_or_default(),
+            last
+        )
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase()
+    }
+}
+
+pub fn usernames(gender: Option<Gender>, locale: Option<Locale>) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of usernames, see username
+    :return: iterator of usernames
+    */
+    std::iter::repeat_with(move || username(gender, locale))
+}
+
+pub fn full_names(gender: Option<Gender>, locale: Option<Locale>) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of full names, see full_name
+    :return: iterator of full names
+    */
+    std::iter::repeat_with(move || full_name(gender, locale))
+}
+
+impl fmt::Display for PersonName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.first_name, self.last_name)
+    }
+}
+
+fn gendered_last_name(last_name: &str, gender: Gender, locale: Locale) -> String {
+    // polish & russian family names take a feminine form
+    if gender != Gender::Female {
+        return last_name.to_string();
+    }
+    match locale {
+        Locale::PlPl => {
+            for (masculine, feminine) in [("ski", "ska"), ("cki", "cka"), ("dzki", "dzka")] {
+                if let Some(stem) = last_name.strip_suffix(masculine) {
+                    return format!("{}{}", stem, feminine);
+                }
+            }
+            last_name.to_string()
+        }
+        Locale::RuRu => {
+            if let Some(stem) = last_name.strip_suffix("sky") {
+                format!("{}skaya", stem)
+            } else if ["ov", "ev", "in"].iter().any(|s| last_name.ends_with(s)) {
+                format!("{}a", last_name)
+            } else {
+                last_name.to_string()
+            }
+        }
+        _ => last_name.to_string(),
+    }
+}
+
+pub(crate) fn person_name_with<R: Rng + ?Sized>(
+    rng: &mut R,
+    gender: Option<Gender>,
+    locale: Option<Locale>,
+) -> PersonName {
+    let gender = gender.unwrap_or_else(|| Gender::random_with(rng));
+    let locale = locale.unwrap_or_else(|| Locale::random_with(rng));
+
+    let first_name = locale.first_names(gender).choose(rng).unwrap_or(&"Alex");
+    let last_name = locale.last_names().choose(rng).unwrap_or(&"Smith");
+
+    PersonName {
+        first_name: first_name.to_string(),
+        last_name: gendered_last_name(last_name, gender, locale),
+        gender,
+        locale,
+    }
+}
+
+pub fn person_name(gender: Option<Gender>, locale: Option<Locale>) -> PersonName {
+    /*
+    Generate a person's name
+    :param gender: Male, Female or Neutral first names - random if not given
+    :param locale: Locale of the names - random if not given
+    :return: PersonName with first name, last name, gender & locale
+    */
+    person_name_with(&mut rand::thread_rng(), gender, locale)
+}
+
+pub fn first_name(gender: Option<Gender>, locale: Option<Locale>) -> String {
+    /*
+    Generate a first name
+    :param gender: Male, Female or Neutral - random if not given
+    :param locale: Locale of the name - random if not given
+    :return: first name as str
+    */
+    person_name(gender, locale).first_name
+}
+
+pub fn first_names(gender: Option<Gender>, locale: Option<Locale>) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of first names, see first_name
+    :return: iterator of first names
+    */
+    std::iter::repeat_with(move || first_name(gender, locale))
+}
+
+pub fn last_name(gender: Option<Gender>, locale: Option<Locale>) -> String {
+    /*
+    Generate a last name
+    :param gender: used for locales with gendered family names (eg: Kowalski / Kowalska)
+    :param locale: Locale of the name - random if not given
+    :return: last name as str
+    */
+    person_name(gender, locale).last_name
+}
+
+pub fn last_names(gender: Option<Gender>, locale: Option<Locale>) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of last names, see last_name
+    :return: iterator of last names
+    */
+    std::iter::repeat_with(move || last_name(gender, locale))
+}
+
+pub fn full_name(gender: Option<Gender>, locale: Option<Locale>) -> String {
+    /*
+    Generate a full name
+    :param gender: Male, Female or Neutral - random if not given
+    :param locale: Locale of the name - random if not given
+    :return: full name as str, eg: John Smith
+    */
+    person_name(gender, locale).full_name()
+}
+
+pub fn initials(gender: Option<Gender>, locale: Option<Locale>) -> String {
+    /*
+    Generate initials of a random person
+    :param gender: Male, Female or Neutral - random if not given
+    :param locale: Locale of the name - random if not given
+    :return: initials as str, eg: JS
+    */
+    person_name(gender, locale).initials()
+}
+
+pub fn username(gender: Option<Gender>, locale: Option<Locale>) -> String {
+    /*
+    Generate a username of a random person
+    :param gender: Male, Female or Neutral - random if not given
+    :param locale: Locale of the name - random if not given
+    :return: ascii username as str, eg: jsmith
+    */
+    person_name(gender, locale).username()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_locale_has_names() {
+        for locale in Locale::ALL {
+            for gender in [Gender::Male, Gender::Female, Gender::Neutral] {
+                assert!(!locale.first_names(gender).is_empty());
+            }
+            assert!(!locale.last_names().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_locale_lookup() {
+        assert_eq!("en_US".parse::<Locale>(), Ok(Locale::EnUs));
+        assert_eq!("pt-br".parse::<Locale>(), Ok(Locale::PtBr));
+        assert!("xx_XX".parse::<Locale>().is_err());
+        assert_eq!(Locale::from_country_code("de"), Some(Locale::DeDe));
+        assert_eq!(Locale::from_country_code("ZZ"), None);
+    }
+
+    #[test]
+    fn test_initials_and_username() {
+        let name = PersonName {
+            first_name: "Jürgen".to_string(),
+            last_name: "Müller".to_string(),
+            gender: Gender::Male,
+            locale: Locale::DeDe,
+        };
+        assert_eq!(name.initials(), "JM");
+        assert_eq!(name.username(), "jmuller");
+
+        let name = PersonName {
+            first_name: "Jan".to_string(),
+            last_name: "de Jong".to_string(),
+            gender: Gender::Male,
+            locale: Locale::NlNl,
+        };
+        assert_eq!(name.initials(), "JJ");
+        assert_eq!(name.username(), "jdejong");
+    }
+
+    #[test]
+    fn test_gendered_last_name() {
+        assert_eq!(
+            gendered_last_name("Kowalski", Gender::Female, Locale::PlPl),
+            "Kowalska"
+        );
+        assert_eq!(
+            gendered_last_name("Ivanov", Gender::Female, Locale::RuRu),
+            "Ivanova"
+        );
+        assert_eq!(
+            gendered_last_name("Ivanov", Gender::Male, Locale::RuRu),
+            "Ivanov"
+        );
+    }
+
+    #[test]
+    fn test_iterators() {
+        assert!(first_names(None, Some(Locale::EnUs))
+            .take(10)
+            .all(|n| !n.is_empty()));
+        assert_eq!(last_names(None, None).take(10).count(), 10);
+        assert!(full_names(None, None).take(10).all(|n| n.contains(' ')));
+        assert!(usernames(None, None).take(10).all(|u| u.is_ascii()));
+    }
+}
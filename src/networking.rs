@@ -145,10 +145,10 @@ fn __ipaddr(
     // define the return value
     let mut ip: Option<String> = None;
 
-    if from_subnet != None {
-        let from_subnet = from_subnet.unwrap_or_else(|| "".to_string());
+    if from_subnet.is_some() {
+        let from_subnet = from_subnet.unwrap_or_default();
         ip = _ip_from_subnet(from_subnet, 4);
-        if ip != None {
+        if ip.is_some() {
             if let Some(x) = ip {
                 return x;
             }
@@ -157,7 +157,7 @@ fn __ipaddr(
 
     let mut network_classes;
     let mut private_networks;
-    let mut excluded_networks;
+    let excluded_networks;
 
     let mut trng = rand::thread_rng();
 
@@ -172,38 +172,40 @@ fn __ipaddr(
 
         //# Three common private networks from class A, B and CIDR
         //# to generate private addresses from.
-        private_networks = Vec::<IpNetwork>::new();
-        private_networks.push("10.0.0.0/8".parse().unwrap());
-        private_networks.push("172.16.0.0/12".parse().unwrap());
-        private_networks.push("192.168.0.0/16".parse().unwrap());
+        private_networks = vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "172.16.0.0/12".parse().unwrap(),
+            "192.168.0.0/16".parse().unwrap(),
+        ];
 
         // List of networks from which IP addresses will never be generated,
         // includes other private IANA and reserved networks from
         // https://www.iana.org/assignments/iana-ipv4-special-registry/iana-ipv4-special-registry.xhtml
-        excluded_networks = Vec::<IpNetwork>::new();
-        excluded_networks.push("0.0.0.0/8".parse().unwrap());
-        excluded_networks.push("100.64.0.0/10".parse().unwrap());
-        excluded_networks.push("127.0.0.0/8".parse().unwrap()); // loopback network
-        excluded_networks.push("169.254.0.0/16".parse().unwrap()); // link-local network
-        excluded_networks.push("192.0.0.0/24".parse().unwrap());
-        excluded_networks.push("192.0.2.0/24".parse().unwrap());
-        excluded_networks.push("192.31.196.0/24".parse().unwrap());
-        excluded_networks.push("192.52.193.0/24".parse().unwrap());
-        excluded_networks.push("192.88.99.0/24".parse().unwrap()); // 6to4 anycast relay
-        excluded_networks.push("192.175.48.0/24".parse().unwrap());
-        excluded_networks.push("198.18.0.0/15".parse().unwrap());
-        excluded_networks.push("198.51.100.0/24".parse().unwrap());
-        excluded_networks.push("203.0.113.0/24".parse().unwrap());
-        excluded_networks.push("224.0.0.0/4".parse().unwrap()); // multicast network
-        excluded_networks.push("240.0.0.0/4".parse().unwrap());
-        excluded_networks.push("255.255.255.255/32".parse().unwrap());
+        excluded_networks = vec![
+            "0.0.0.0/8".parse().unwrap(),
+            "100.64.0.0/10".parse().unwrap(),
+            "127.0.0.0/8".parse().unwrap(),    // loopback network
+            "169.254.0.0/16".parse().unwrap(), // link-local network
+            "192.0.0.0/24".parse().unwrap(),
+            "192.0.2.0/24".parse().unwrap(),
+            "192.31.196.0/24".parse().unwrap(),
+            "192.52.193.0/24".parse().unwrap(),
+            "192.88.99.0/24".parse().unwrap(), // 6to4 anycast relay
+            "192.175.48.0/24".parse().unwrap(),
+            "198.18.0.0/15".parse().unwrap(),
+            "198.51.100.0/24".parse().unwrap(),
+            "203.0.113.0/24".parse().unwrap(),
+            "224.0.0.0/4".parse().unwrap(), // multicast network
+            "240.0.0.0/4".parse().unwrap(),
+            "255.255.255.255/32".parse().unwrap(),
+        ];
 
         //if address_class not in network_classes.keys(){
         if true {
             address_class = *(['a', 'b', 'c'].choose(&mut trng).unwrap_or(&'c'));
         }
 
-        let supernet = network_classes[&address_class];
+        let _supernet = network_classes[&address_class];
 
         let public_networks = network_classes[&address_class];
         let mut sclasses = private_networks.to_vec();
@@ -229,26 +231,29 @@ fn __ipaddr(
         }
     } else if version == 6 {
         //# IPv6
-        private_networks = Vec::<IpNetwork>::new();
-        private_networks.push("fc00::/7".parse().unwrap()); //# Unique Local Addresses (ULAs - RFC 4193)
-        private_networks.push("fc00::/8".parse().unwrap()); //# Unique Local Addresses (ULAs - RFC 4193)
-        private_networks.push("fd00::/8".parse().unwrap()); //# Unique Local Addresses (ULAs - RFC 4193)
-
-        let mut public_networks = Vec::<IpNetwork>::new();
-        public_networks.push("2001::/16".parse().unwrap());
-        public_networks.push("2001::/32".parse().unwrap());
-        public_networks.push("2001::/48".parse().unwrap());
-        public_networks.push("2001::/56".parse().unwrap());
-        public_networks.push("2001::/64".parse().unwrap());
-
-        let mut excluded_networks = Vec::<IpNetwork>::new();
-        excluded_networks.push("::/128".parse().unwrap()); //# unspecified address
-        excluded_networks.push("::1/128".parse().unwrap()); //# loopback address
-        excluded_networks.push("fe80::/10".parse().unwrap()); //# link-local network
-        excluded_networks.push("ff00::/8".parse().unwrap()); //# multicast address (RFC 4038)
-        excluded_networks.push("2001:db8::/32".parse().unwrap()); //# reserved for use in documentation
-        excluded_networks.push("2002::/16".parse().unwrap()); //# 6to4 public router anycast (RFC 3068)
-        excluded_networks.push("2000::/3".parse().unwrap()); //# global unicast
+        private_networks = vec![
+            "fc00::/7".parse().unwrap(), //# Unique Local Addresses (ULAs - RFC 4193)
+            "fc00::/8".parse().unwrap(), //# Unique Local Addresses (ULAs - RFC 4193)
+            "fd00::/8".parse().unwrap(), //# Unique Local Addresses (ULAs - RFC 4193)
+        ];
+
+        let public_networks: Vec<IpNetwork> = vec![
+            "2001::/16".parse().unwrap(),
+            "2001::/32".parse().unwrap(),
+            "2001::/48".parse().unwrap(),
+            "2001::/56".parse().unwrap(),
+            "2001::/64".parse().unwrap(),
+        ];
+
+        let excluded_networks: Vec<IpNetwork> = vec![
+            "::/128".parse().unwrap(),        //# unspecified address
+            "::1/128".parse().unwrap(),       //# loopback address
+            "fe80::/10".parse().unwrap(),     //# link-local network
+            "ff00::/8".parse().unwrap(),      //# multicast address (RFC 4038)
+            "2001:db8::/32".parse().unwrap(), //# reserved for use in documentation
+            "2002::/16".parse().unwrap(),     //# 6to4 public router anycast (RFC 3068)
+            "2000::/3".parse().unwrap(),      //# global unicast
+        ];
 
         private_networks = __exclude_ip_networks(private_networks, excluded_networks);
 
@@ -277,6 +282,18 @@ pub fn public_ip(
     __ipaddr(Some(version), Some(address_class), None, from_subnet)
 }
 
+pub fn public_ips(
+    version: Option<u32>,
+    address_class: Option<char>,
+    from_subnet: Option<String>,
+) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of public IP addresses, see public_ip
+    :return: iterator of ip addresses
+    */
+    std::iter::repeat_with(move || public_ip(version, address_class, from_subnet.clone()))
+}
+
 pub fn private_ip(
     version: Option<u32>,
     address_class: Option<char>,
@@ -290,6 +307,18 @@ pub fn private_ip(
     __ipaddr(version, address_class, Some(true), from_subnet)
 }
 
+pub fn private_ips(
+    version: Option<u32>,
+    address_class: Option<char>,
+    from_subnet: Option<String>,
+) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of private IP addresses, see private_ip
+    :return: iterator of ip addresses
+    */
+    std::iter::repeat_with(move || private_ip(version, address_class, from_subnet.clone()))
+}
+
 pub fn port_number(
     is_system: Option<bool>,
     is_user: Option<bool>,
@@ -321,12 +350,24 @@ pub fn port_number(
     trng.gen_range(0..65535)
 }
 
+pub fn port_numbers(
+    is_system: Option<bool>,
+    is_user: Option<bool>,
+    is_dynamic: Option<bool>,
+) -> impl Iterator<Item = u32> {
+    /*
+    Endless stream of network port numbers, see port_number
+    :return: iterator of port numbers
+    */
+    std::iter::repeat_with(move || port_number(is_system, is_user, is_dynamic))
+}
+
 pub fn mac_address(upper_case: Option<bool>, oui: Option<String>) -> String {
     /*
     Generates a random MAC Address
     */
     //let upper_case = upper_case.unwrap_or(false);
-    let oui = oui.unwrap_or_else(|| "".to_string());
+    let oui = oui.unwrap_or_default();
 
     if oui.is_empty() {
         //return hex_pattern(pattern='^^:^^:^^:^^:^^:^^', upper=upper_case)
@@ -341,6 +382,17 @@ pub fn mac_address(upper_case: Option<bool>, oui: Option<String>) -> String {
     // /mac_adr
 }
 
+pub fn mac_addresses(
+    upper_case: Option<bool>,
+    oui: Option<String>,
+) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of MAC addresses, see mac_address
+    :return: iterator of mac addresses
+    */
+    std::iter::repeat_with(move || mac_address(upper_case, oui.clone()))
+}
+
 pub struct FQDN {
     pub domain: String,
     pub sub_domain: String,
@@ -385,3 +437,28 @@ pub fn fqdn(company_name: &str) -> Option<FQDN> {
         fqdn: _fqdn.to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iterators() {
+        assert!(public_ips(Some(4), None, None)
+            .take(10)
+            .all(|ip| ip.parse::<std::net::Ipv4Addr>().is_ok()));
+        let private = ["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"]
+            .iter()
+            .map(|n| n.parse::<IpNetwork>().unwrap())
+            .collect::<Vec<IpNetwork>>();
+        assert!(private_ips(Some(4), None, None)
+            .take(10)
+            .all(|ip| private.iter().any(|n| n.contains(ip.parse().unwrap()))));
+        assert!(port_numbers(None, None, Some(true))
+            .take(10)
+            .all(|p| (49152..65535).contains(&p)));
+        assert!(mac_addresses(None, None)
+            .take(10)
+            .all(|m| m.len() == 17 && m.split(':').count() == 6));
+    }
+}
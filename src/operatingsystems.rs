@@ -15,7 +15,6 @@ from curious.core.decorators import uppercase, lowercase
 */
 use rand::seq::SliceRandom;
 use rand::Rng;
-use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::core::constants::{
@@ -29,41 +28,37 @@ use crate::core::constants::{
     WIN_HOSTNAMES,
 };
 
-pub fn logon_id(batch_size: Option<u32>) -> Vec<String> {
+pub fn logon_id() -> String {
     /*
     Generates a hex logon id for windows os
-    :param batch_size: how many to generate
-    :return: string of hex logon id if batch size is 1 else a list of login id's
+    :return: string of hex logon id, eg: 0x3e7a9f1
     */
+    crate::core::utils::hex_pattern(Some("0x^^^^^^^"), None)
+}
 
-    let batch_size = batch_size.unwrap_or(1);
-
-    let mut logon_ids = Vec::<String>::new();
-    for _ in 1..batch_size {
-        logon_ids.push(crate::core::utils::hex_pattern(Some("0x^^^^^^^"), None));
-    }
-
-    //return ''.join(logon_ids) if batch_size == 1 else logon_ids
-    logon_ids
+pub fn logon_ids() -> impl Iterator<Item = String> {
+    /*
+    Endless stream of hex logon ids
+    :return: iterator of logon ids, eg: logon_ids().take(1000)
+    */
+    std::iter::repeat_with(logon_id)
 }
 
-pub fn new_uuid(batch_size: Option<u32>) -> Vec<String> {
+pub fn new_uuid() -> String {
     /*
     Generate a UUID/GUID
-    :param batch_size: how many to generate
-    :return: str of generated UUID or list of uuid's ig batch size is more than 1
+    :return: str of generated UUID
     */
+    Uuid::new_v4().to_string()
+}
 
-    let batch_size = batch_size.unwrap_or(1);
-
-    let mut uuids = Vec::<String>::new();
-
-    for _ in 1..batch_size {
-        uuids.push(Uuid::new_v4().to_string());
-    }
-
-    //return ''.join(uuids) if batch_size == 1 else uuids
-    uuids
+pub fn uuid_strings() -> impl Iterator<Item = String> {
+    /*
+    Endless stream of UUIDs/GUIDs as strings, see new_uuid;
+    uuids::uuids streams typed UUIDs of any version
+    :return: iterator of uuid's, eg: uuid_strings().take(1000)
+    */
+    std::iter::repeat_with(new_uuid)
 }
 
 //@uppercase
@@ -72,8 +67,7 @@ pub fn localhost_name(
     linux: Option<bool>,
     name: Option<String>,
     suffix_local: Option<bool>,
-    batch_size: Option<u32>,
-) -> Vec<String> {
+) -> String {
     /*
     Generate a random localhost Name: Same as hostname, but this will not take a prefix, instead just
     generate a random name and return
@@ -81,87 +75,67 @@ pub fn localhost_name(
     :param linux: generates linux machine names
     :param name: if given this name will be used as suffix instead of a random generated id
     :param suffix_local: eg: hostname.local
-    :param batch_size: how many to generate
-    :return: returns a hostname as str if batch size is 1, else returns a list
+    :return: returns a hostname
     */
 
     let windows = windows.unwrap_or(false);
     let linux = linux.unwrap_or(false);
-    let name: String = match name {
-        Some(x) => x,
-        None => "".to_owned(),
-    };
-
     let suffix_local = suffix_local.unwrap_or(false);
-    let batch_size = batch_size.unwrap_or(1);
-
-    let mut hostname = Vec::<String>::new();
 
     let mut trng = rand::thread_rng();
 
-    for _ in 1..batch_size {
-        let n = trng.gen_range(8..12);
-
-        let prefix: String;
-
-        let suffix = if !name.is_empty() {
-            name.clone()
-        } else {
-            let s: String = rand::thread_rng()
+    let suffix = match name {
+        Some(x) if !x.is_empty() => x,
+        _ => {
+            let n = trng.gen_range(8..12);
+            let s: String = (&mut trng)
                 .sample_iter(&rand::distributions::Alphanumeric)
                 .take(n)
                 .map(char::from)
                 .collect();
             s.to_ascii_uppercase()
-        };
-
-        if windows {
-            let mut winhbrands = Vec::new();
-            for i in WIN_HOSTNAMES.iter() {
-                winhbrands.push(i);
-            }
-            for i in BRANDS.iter() {
-                winhbrands.push(i);
-            }
-
-            let x = winhbrands.choose(&mut rand::thread_rng());
-            prefix = x.unwrap().to_string();
-        } else if linux {
-            let mut nixnames = Vec::new();
-            for i in NIX_HOSTNAMES.iter() {
-                nixnames.push(i);
-            }
-
-            let x = nixnames.choose(&mut rand::thread_rng());
-
-            prefix = x.unwrap().to_string();
-        } else {
-            let mut mixnames = Vec::new();
-            for i in NIX_HOSTNAMES.iter() {
-                mixnames.push(i);
-            }
-            for i in WIN_HOSTNAMES.iter() {
-                mixnames.push(i);
-            }
-
-            let x = mixnames.choose(&mut rand::thread_rng());
-
-            prefix = x.unwrap().to_string();
         }
+    };
 
-        let hname = format!(
-            "{}-{}",
-            prefix,
-            if suffix_local { suffix } else { "".to_string() }
-        );
-
-        hostname.push(hname);
-        //hostname.push(r"{prefix}-{suffix}{'.local' if suffix_local else ''}")
+    let prefix = if windows {
+        WIN_HOSTNAMES
+            .iter()
+            .chain(BRANDS.iter())
+            .collect::<Vec<_>>()
+            .choose(&mut trng)
+            .map(|x| x.to_string())
+    } else if linux {
+        NIX_HOSTNAMES.choose(&mut trng).map(|x| x.to_string())
+    } else {
+        NIX_HOSTNAMES
+            .iter()
+            .chain(WIN_HOSTNAMES.iter())
+            .collect::<Vec<_>>()
+            .choose(&mut trng)
+            .map(|x| x.to_string())
     }
+    .unwrap_or_default();
+
+    //f"{prefix}-{suffix}{'.local' if suffix_local else ''}"
+    format!(
+        "{}-{}{}",
+        prefix,
+        suffix,
+        if suffix_local { ".local" } else { "" }
+    )
+}
 
-    hostname
-
-    //return ''.join(hostname) if batch_size == 1 else hostname
+pub fn localhost_names(
+    windows: Option<bool>,
+    linux: Option<bool>,
+    name: Option<String>,
+    suffix_local: Option<bool>,
+) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of localhost names, see localhost_name
+    :return: iterator of hostnames, eg: localhost_names(Some(true), None, None, None).take(10)
+    */
+    std::iter::repeat_with(move || localhost_name(windows, linux, name.clone(), suffix_local))
 }
 
 //@lowercase
@@ -184,28 +158,8 @@ pub fn hostname(prefix_str: &str, suffix_str: Option<&str>, mac_suffix: Option<b
 
     let hostnum: String = format!("{:02}", x);
 
-    println!("{}", hostnum);
-
-    let digits = "0123456789";
-
     //let mut prefix_str_name = prefix_str.translate(str.maketrans('', '', digits));
-    let mut hashmap: HashMap<char, Option<char>> = HashMap::new();
-    // for c in prefix_str.chars() {
-    //     hashmap.insert(c, c);
-    // }
-    for c in digits.chars() {
-        hashmap.insert(c, None);
-    }
-
-    let mut prefix_str_name = String::with_capacity(prefix_str.len());
-    for c in prefix_str.chars() {
-        match hashmap.get(&c) {
-            Some(None) => {
-                // do nothing in case of digits mapped to None
-            }
-            _ => prefix_str_name.push(c),
-        }
-    }
+    let prefix_str_name = crate::core::utils::strip_digits(prefix_str);
 
     //prefix_str = [''.join(g) for k, g in groupby(prefix_str, ' ._'.__contains__) if not k]
 
@@ -241,6 +195,18 @@ pub fn hostname(prefix_str: &str, suffix_str: Option<&str>, mac_suffix: Option<b
     host
 }
 
+pub fn hostnames<'a>(
+    prefix_str: &'a str,
+    suffix_str: Option<&'a str>,
+    mac_suffix: Option<bool>,
+) -> impl Iterator<Item = String> + 'a {
+    /*
+    Endless stream of hostnames based on a given prefix str, see hostname
+    :return: iterator of hostnames
+    */
+    std::iter::repeat_with(move || hostname(prefix_str, suffix_str, mac_suffix))
+}
+
 //@lowercase
 pub enum OsType {
     Windows,
@@ -301,3 +267,54 @@ pub fn servername(windows: Option<bool>, linux: Option<bool>) -> String {
         )
     }
 }
+
+pub fn servernames(windows: Option<bool>, linux: Option<bool>) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of server names, see servername
+    :return: iterator of server names
+    */
+    std::iter::repeat_with(move || servername(windows, linux))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_localhost_name() {
+        // the suffix is kept with or without .local
+        for name in localhost_names(None, Some(true), None, None).take(10) {
+            let (_, suffix) = name.split_once('-').unwrap();
+            assert!((8..12).contains(&suffix.len()));
+        }
+        let name = Some("FINANCE".to_string());
+        assert!(localhost_name(Some(true), None, name.clone(), Some(false)).ends_with("-FINANCE"));
+        assert!(localhost_name(Some(true), None, name, Some(true)).ends_with("-FINANCE.local"));
+    }
+
+    #[test]
+    fn test_iterators() {
+        assert!(logon_id().starts_with("0x"));
+        assert_eq!(logon_ids().take(5).count(), 5);
+
+        let uuids = uuid_strings()
+            .take(100)
+            .collect::<std::collections::HashSet<String>>();
+        assert_eq!(uuids.len(), 100);
+        assert!(uuids.iter().all(|u| Uuid::parse_str(u).is_ok()));
+
+        let name = localhost_name(Some(true), None, Some("FINANCE".to_string()), Some(true));
+        assert!(name.ends_with("-FINANCE.local"));
+        assert!(localhost_names(None, Some(true), None, None)
+            .take(10)
+            .all(|n| NIX_HOSTNAMES
+                .iter()
+                .any(|p| n.starts_with(&format!("{}-", p)))));
+        assert!(hostnames("jsmith", Some("-PC"), None)
+            .take(10)
+            .all(|h| h.starts_with("jsmith") && h.ends_with("-PC")));
+        assert!(servernames(Some(true), None)
+            .take(10)
+            .all(|s| s.ends_with("-SRV")));
+    }
+}
@@ -0,0 +1,622 @@
+/*
+Windows security identifier (SID) generators
+Well-known SIDs, domain SIDs (S-1-5-21-x-y-z) and account SIDs with
+RIDs that stay consistent within a domain; string & binary encodings
+*/
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+type Error = String;
+
+/// NT authority (S-1-5)
+pub const NT_AUTHORITY: u64 = 5;
+/// first sub authority of domain & machine SIDs (S-1-5-21)
+pub const NT_NON_UNIQUE: u32 = 21;
+/// first RID handed out to accounts created after the domain itself
+pub const FIRST_RID: u32 = 1103;
+pub const MAX_SUB_AUTHORITIES: usize = 15;
+const MAX_AUTHORITY: u64 = (1 << 48) - 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sid {
+    authority: u64,
+    sub_authorities: Vec<u32>,
+}
+
+impl Sid {
+    pub const REVISION: u8 = 1;
+
+    pub fn new(authority: u64, sub_authorities: &[u32]) -> Result<Self, Error> {
+        //! New SID from its identifier authority (48 bits) and
+        //! up to 15 sub authorities, eg: `Sid::new(5, &[32, 544])`
+        if authority > MAX_AUTHORITY {
+            return Err(format!("Error: authority {} is over 48 bits", authority));
+        }
+        if sub_authorities.len() > MAX_SUB_AUTHORITIES {
+            return Err(format!(
+                "Error: a SID has at most {} sub authorities",
+                MAX_SUB_AUTHORITIES
+            ));
+        }
+        Ok(Sid {
+            authority,
+            sub_authorities: sub_authorities.to_vec(),
+        })
+    }
+
+    pub fn authority(&self) -> u64 {
+        self.authority
+    }
+
+    pub fn sub_authorities(&self) -> &[u32] {
+        &self.sub_authorities
+    }
+
+    pub fn rid(&self) -> Option<u32> {
+        //! Relative identifier: the last sub authority
+        self.sub_authorities.last().copied()
+    }
+
+    pub fn is_domain(&self) -> bool {
+        //! true for a domain (or machine) SID, eg: S-1-5-21-x-y-z
+        self.authority == NT_AUTHORITY
+            && self.sub_authorities.len() == 4
+            && self.sub_authorities[0] == NT_NON_UNIQUE
+    }
+
+    pub fn domain(&self) -> Option<Sid> {
+        //! Domain SID of an account SID, eg: S-1-5-21-x-y-z-1104 -> S-1-5-21-x-y-z
+        if self.authority == NT_AUTHORITY
+            && self.sub_authorities.len() == 5
+            && self.sub_authorities[0] == NT_NON_UNIQUE
+        {
+            Some(Sid {
+                authority: self.authority,
+                sub_authorities: self.sub_authorities[..4].to_vec(),
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn with_rid(&self, rid: u32) -> Result<Sid, Error> {
+        //! SID of an account relative to this (domain) SID
+        let mut sub_authorities = self.sub_authorities.clone();
+        sub_authorities.push(rid);
+        Sid::new(self.authority, &sub_authorities)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        //! Binary encoding (as in objectSid & event records): revision, sub authority
+        //! count, 48 bit big endian authority, little endian sub authorities
+        let mut bytes = Vec::with_capacity(8 + 4 * self.sub_authorities.len());
+        bytes.push(Sid::REVISION);
+        bytes.push(self.sub_authorities.len() as u8);
+        bytes.extend_from_slice(&self.authority.to_be_bytes()[2..]);
+        for sub_authority in &self.sub_authorities {
+            bytes.extend_from_slice(&sub_authority.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        //! Decode the binary encoding, see `to_bytes`
+        if bytes.len() < 8 {
+            return Err(String::from("Error: a binary SID is at least 8 bytes"));
+        }
+        if bytes[0] != Sid::REVISION {
+            return Err(format!("Error: unknown SID revision {}", bytes[0]));
+        }
+        let count = bytes[1] as usize;
+        if bytes.len() != 8 + 4 * count {
+            return Err(format!(
+                "Error: a binary SID with {} sub authorities is {} bytes, not {}",
+                count,
+                8 + 4 * count,
+                bytes.len()
+            ));
+        }
+        let authority = bytes[2..8]
+            .iter()
+            .fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+        let sub_authorities = bytes[8..]
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect::<Vec<u32>>();
+        Sid::new(authority, &sub_authorities)
+    }
+}
+
+impl fmt::Display for Sid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // authorities that do not fit 32 bits are written in hex
+        if self.authority >> 32 == 0 {
+            write!(f, "S-{}-{}", Sid::REVISION, self.authority)?;
+        } else {
+            write!(f, "S-{}-0x{:012X}", Sid::REVISION, self.authority)?;
+        }
+        for sub_authority in &self.sub_authorities {
+            write!(f, "-{}", sub_authority)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Sid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Error: invalid SID '{}'", s);
+        let mut parts = s.trim().split('-');
+        if !parts
+            .next()
+            .map(|p| p.eq_ignore_ascii_case("S"))
+            .unwrap_or(false)
+            || parts.next() != Some("1")
+        {
+            return Err(invalid());
+        }
+        // digits only: parse and from_str_radix alone would take a '+' sign
+        let digits =
+            |text: &str, radix: u32| !text.is_empty() && text.chars().all(|c| c.is_digit(radix));
+        let authority = parts.next().ok_or_else(invalid)?;
+        let authority = match authority
+            .strip_prefix("0x")
+            .or_else(|| authority.strip_prefix("0X"))
+        {
+            Some(hex) if digits(hex, 16) => u64::from_str_radix(hex, 16).ok(),
+            None if digits(authority, 10) => authority.parse::<u64>().ok(),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+        let sub_authorities = parts
+            .map(|p| Some(p).filter(|p| digits(p, 10))?.parse::<u32>().ok())
+            .collect::<Option<Vec<u32>>>()
+            .ok_or_else(invalid)?;
+        Sid::new(authority, &sub_authorities)
+    }
+}
+
+impl TryFrom<&[u8]> for Sid {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Sid::from_bytes(bytes)
+    }
+}
+
+/// Well-known SIDs that are the same on every windows machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WellKnownSid {
+    Null,
+    Everyone,
+    CreatorOwner,
+    Dialup,
+    Network,
+    Batch,
+    Interactive,
+    Service,
+    Anonymous,
+    EnterpriseDomainControllers,
+    AuthenticatedUsers,
+    LocalSystem,
+    LocalService,
+    NetworkService,
+    Administrators,
+    Users,
+    Guests,
+    PowerUsers,
+    BackupOperators,
+    RemoteDesktopUsers,
+    NtServiceTrustedInstaller,
+    HighMandatoryLevel,
+    SystemMandatoryLevel,
+}
+
+impl WellKnownSid {
+    pub const ALL: &'static [WellKnownSid] = &[
+        WellKnownSid::Null,
+        WellKnownSid::Everyone,
+        WellKnownSid::CreatorOwner,
+        WellKnownSid::Dialup,
+        WellKnownSid::Network,
+        WellKnownSid::Batch,
+        WellKnownSid::Interactive,
+        WellKnownSid::Service,
+        WellKnownSid::Anonymous,
+        WellKnownSid::EnterpriseDomainControllers,
+        WellKnownSid::AuthenticatedUsers,
+        WellKnownSid::LocalSystem,
+        WellKnownSid::LocalService,
+        WellKnownSid::NetworkService,
+        WellKnownSid::Administrators,
+        WellKnownSid::Users,
+        WellKnownSid::Guests,
+        WellKnownSid::PowerUsers,
+        WellKnownSid::BackupOperators,
+        WellKnownSid::RemoteDesktopUsers,
+        WellKnownSid::NtServiceTrustedInstaller,
+        WellKnownSid::HighMandatoryLevel,
+        WellKnownSid::SystemMandatoryLevel,
+    ];
+
+    pub fn random() -> WellKnownSid {
+        *WellKnownSid::ALL
+            .choose(&mut rand::thread_rng())
+            .unwrap_or(&WellKnownSid::Everyone)
+    }
+
+    fn parts(&self) -> (u64, &'static [u32]) {
+        match self {
+            WellKnownSid::Null => (0, &[0]),
+            WellKnownSid::Everyone => (1, &[0]),
+            WellKnownSid::CreatorOwner => (3, &[0]),
+            WellKnownSid::Dialup => (5, &[1]),
+            WellKnownSid::Network => (5, &[2]),
+            WellKnownSid::Batch => (5, &[3]),
+            WellKnownSid::Interactive => (5, &[4]),
+            WellKnownSid::Service => (5, &[6]),
+            WellKnownSid::Anonymous => (5, &[7]),
+            WellKnownSid::EnterpriseDomainControllers => (5, &[9]),
+            WellKnownSid::AuthenticatedUsers => (5, &[11]),
+            WellKnownSid::LocalSystem => (5, &[18]),
+            WellKnownSid::LocalService => (5, &[19]),
+            WellKnownSid::NetworkService => (5, &[20]),
+            WellKnownSid::Administrators => (5, &[32, 544]),
+            WellKnownSid::Users => (5, &[32, 545]),
+            WellKnownSid::Guests => (5, &[32, 546]),
+            WellKnownSid::PowerUsers => (5, &[32, 547]),
+            WellKnownSid::BackupOperators => (5, &[32, 551]),
+            WellKnownSid::RemoteDesktopUsers => (5, &[32, 555]),
+            WellKnownSid::NtServiceTrustedInstaller => (
+                5,
+                &[
+                    80, 956008885, 3418522649, 1831038044, 1853292631, 2271478464,
+                ],
+            ),
+            WellKnownSid::HighMandatoryLevel => (16, &[12288]),
+            WellKnownSid::SystemMandatoryLevel => (16, &[16384]),
+        }
+    }
+
+    pub fn sid(&self) -> Sid {
+        let (authority, sub_authorities) = self.parts();
+        Sid {
+            authority,
+            sub_authorities: sub_authorities.to_vec(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        //! Account name as shown in event logs, eg: NT AUTHORITY\SYSTEM
+        match self {
+            WellKnownSid::Null => "NULL SID",
+            WellKnownSid::Everyone => "Everyone",
+            WellKnownSid::CreatorOwner => "CREATOR OWNER",
+            WellKnownSid::Dialup => "NT AUTHORITY\\DIALUP",
+            WellKnownSid::Network => "NT AUTHORITY\\NETWORK",
+            WellKnownSid::Batch => "NT AUTHORITY\\BATCH",
+            WellKnownSid::Interactive => "NT AUTHORITY\\INTERACTIVE",
+            WellKnownSid::Service => "NT AUTHORITY\\SERVICE",
+            WellKnownSid::Anonymous => "NT AUTHORITY\\ANONYMOUS LOGON",
+            WellKnownSid::EnterpriseDomainControllers => {
+                "NT AUTHORITY\\ENTERPRISE DOMAIN CONTROLLERS"
+            }
+            WellKnownSid::AuthenticatedUsers => "NT AUTHORITY\\Authenticated Users",
+            WellKnownSid::LocalSystem => "NT AUTHORITY\\SYSTEM",
+            WellKnownSid::LocalService => "NT AUTHORITY\\LOCAL SERVICE",
+            WellKnownSid::NetworkService => "NT AUTHORITY\\NETWORK SERVICE",
+            WellKnownSid::Administrators => "BUILTIN\\Administrators",
+            WellKnownSid::Users => "BUILTIN\\Users",
+            WellKnownSid::Guests => "BUILTIN\\Guests",
+            WellKnownSid::PowerUsers => "BUILTIN\\Power Users",
+            WellKnownSid::BackupOperators => "BUILTIN\\Backup Operators",
+            WellKnownSid::RemoteDesktopUsers => "BUILTIN\\Remote Desktop Users",
+            WellKnownSid::NtServiceTrustedInstaller => "NT SERVICE\\TrustedInstaller",
+            WellKnownSid::HighMandatoryLevel => "Mandatory Label\\High Mandatory Level",
+            WellKnownSid::SystemMandatoryLevel => "Mandatory Label\\System Mandatory Level",
+        }
+    }
+}
+
+/// Well-known accounts & groups every domain has, relative to the domain SID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DomainRid {
+    Administrator,
+    Guest,
+    Krbtgt,
+    DomainAdmins,
+    DomainUsers,
+    DomainGuests,
+    DomainComputers,
+    DomainControllers,
+    CertPublishers,
+    SchemaAdmins,
+    EnterpriseAdmins,
+    GroupPolicyCreatorOwners,
+}
+
+impl DomainRid {
+    pub const ALL: &'static [DomainRid] = &[
+        DomainRid::Administrator,
+        DomainRid::Guest,
+        DomainRid::Krbtgt,
+        DomainRid::DomainAdmins,
+        DomainRid::DomainUsers,
+        DomainRid::DomainGuests,
+        DomainRid::DomainComputers,
+        DomainRid::DomainControllers,
+        DomainRid::CertPublishers,
+        DomainRid::SchemaAdmins,
+        DomainRid::EnterpriseAdmins,
+        DomainRid::GroupPolicyCreatorOwners,
+    ];
+
+    pub fn rid(&self) -> u32 {
+        match self {
+            DomainRid::Administrator => 500,
+            DomainRid::Guest => 501,
+            DomainRid::Krbtgt => 502,
+            DomainRid::DomainAdmins => 512,
+            DomainRid::DomainUsers => 513,
+            DomainRid::DomainGuests => 514,
+            DomainRid::DomainComputers => 515,
+            DomainRid::DomainControllers => 516,
+            DomainRid::CertPublishers => 517,
+            DomainRid::SchemaAdmins => 518,
+            DomainRid::EnterpriseAdmins => 519,
+            DomainRid::GroupPolicyCreatorOwners => 520,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        //! Account name, without the domain
+        match self {
+            DomainRid::Administrator => "Administrator",
+            DomainRid::Guest => "Guest",
+            DomainRid::Krbtgt => "krbtgt",
+            DomainRid::DomainAdmins => "Domain Admins",
+            DomainRid::DomainUsers => "Domain Users",
+            DomainRid::DomainGuests => "Domain Guests",
+            DomainRid::DomainComputers => "Domain Computers",
+            DomainRid::DomainControllers => "Domain Controllers",
+            DomainRid::CertPublishers => "Cert Publishers",
+            DomainRid::SchemaAdmins => "Schema Admins",
+            DomainRid::EnterpriseAdmins => "Enterprise Admins",
+            DomainRid::GroupPolicyCreatorOwners => "Group Policy Creator Owners",
+        }
+    }
+}
+
+/// Hands out account SIDs of one domain; the same account name always
+/// gets the same SID and new accounts get increasing RIDs
+///
+/// ## Example
+/// ```rust
+/// use random::sids::{DomainRid, DomainSids};
+///
+/// let mut sids = DomainSids::new(None);
+/// let jsmith = sids.sid("jsmith");
+/// assert_eq!(sids.sid("JSMITH"), jsmith);
+/// assert_eq!(jsmith.domain().as_ref(), Some(sids.domain_sid()));
+/// println!("{} {}", jsmith, sids.well_known(DomainRid::DomainAdmins));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DomainSids {
+    domain_sid: Sid,
+    next_rid: u32,
+    assigned: HashMap<String, Sid>,
+}
+
+impl DomainSids {
+    pub fn new(domain_sid: Option<Sid>) -> Self {
+        //! Account SIDs of a domain - a random domain if not given
+        let domain_sid = match domain_sid {
+            Some(sid) => sid,
+            None => domain_sid_with(&mut rand::thread_rng()),
+        };
+        DomainSids {
+            domain_sid,
+            next_rid: FIRST_RID,
+            assigned: HashMap::new(),
+        }
+    }
+
+    pub fn domain_sid(&self) -> &Sid {
+        &self.domain_sid
+    }
+
+    pub fn len(&self) -> usize {
+        //! Number of accounts given a SID so far
+        self.assigned.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assigned.is_empty()
+    }
+
+    pub fn get(&self, account: &str) -> Option<&Sid> {
+        //! SID of an account, if it was given one
+        self.assigned.get(&account.to_ascii_lowercase())
+    }
+
+    pub fn sid(&mut self, account: &str) -> Sid {
+        //! SID of an account (user, group or computer) of the domain;
+        //! account names are compared case-insensitively, like windows does
+        let key = account.to_ascii_lowercase();
+        if let Some(sid) = self.assigned.get(&key) {
+            return sid.clone();
+        }
+        let mut sub_authorities = self.domain_sid.sub_authorities.clone();
+        sub_authorities.push(self.next_rid);
+        self.next_rid += 1;
+        let sid = Sid {
+            authority: self.domain_sid.authority,
+            sub_authorities,
+        };
+        self.assigned.insert(key, sid.clone());
+        sid
+    }
+
+    pub fn well_known(&self, rid: DomainRid) -> Sid {
+        //! SID of a built in account of the domain, eg: Domain Admins
+        let mut sub_authorities = self.domain_sid.sub_authorities.clone();
+        sub_authorities.push(rid.rid());
+        Sid {
+            authority: self.domain_sid.authority,
+            sub_authorities,
+        }
+    }
+}
+
+pub fn domain_sids() -> impl Iterator<Item = Sid> {
+    /*
+    Endless stream of random domain SIDs, see domain_sid
+    :return: iterator of domain SIDs
+    */
+    std::iter::repeat_with(domain_sid)
+}
+
+pub(crate) fn domain_sid_with<R: Rng + ?Sized>(rng: &mut R) -> Sid {
+    // the three sub authorities of real domains are never tiny
+    Sid {
+        authority: NT_AUTHORITY,
+        sub_authorities: vec![
+            NT_NON_UNIQUE,
+            rng.gen_range(100_000_000..=u32::MAX),
+            rng.gen_range(100_000_000..=u32::MAX),
+            rng.gen_range(100_000_000..=u32::MAX),
+        ],
+    }
+}
+
+pub fn domain_sid() -> Sid {
+    /*
+    Generate a random domain SID
+    :return: eg: S-1-5-21-3623811015-3361044348-30300820
+    */
+    domain_sid_with(&mut rand::thread_rng())
+}
+
+pub fn user_sid(domain_sid: Option<&Sid>) -> Result<Sid, Error> {
+    /*
+    Generate the SID of a user account created after the domain
+    :param domain_sid: domain of the user - random if not given
+    :return: eg: S-1-5-21-3623811015-3361044348-30300820-1013
+    */
+    let mut trng = rand::thread_rng();
+    let rid = trng.gen_range(FIRST_RID..FIRST_RID + 50_000);
+    match domain_sid {
+        Some(sid) => sid.with_rid(rid),
+        None => domain_sid_with(&mut trng).with_rid(rid),
+    }
+}
+
+pub fn user_sids<'a>(domain_sid: Option<&'a Sid>) -> impl Iterator<Item = Result<Sid, Error>> + 'a {
+    /*
+    Endless stream of user SIDs, see user_sid; use `DomainSids` for
+    RIDs that are unique within a domain
+    :return: iterator of user SIDs
+    */
+    std::iter::repeat_with(move || user_sid(domain_sid))
+}
+
+pub fn well_known_sid(sid: Option<WellKnownSid>) -> Sid {
+    /*
+    SID of a well-known account or group
+    :param sid: which one - random if not given
+    :return: eg: S-1-5-18
+    */
+    sid.unwrap_or_else(WellKnownSid::random).sid()
+}
+
+pub fn well_known_sids(sid: Option<WellKnownSid>) -> impl Iterator<Item = Sid> {
+    /*
+    Endless stream of well-known SIDs, see well_known_sid
+    :return: iterator of SIDs
+    */
+    std::iter::repeat_with(move || well_known_sid(sid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encodings() {
+        let sid: Sid = "S-1-5-21-3623811015-3361044348-30300820-1013"
+            .parse()
+            .unwrap();
+        assert_eq!(sid.rid(), Some(1013));
+        assert_eq!(
+            sid.domain().unwrap().to_string(),
+            "S-1-5-21-3623811015-3361044348-30300820"
+        );
+        let bytes = sid.to_bytes();
+        assert_eq!(bytes.len(), 28);
+        assert_eq!(&bytes[..12], &[1, 5, 0, 0, 0, 0, 0, 5, 21, 0, 0, 0]);
+        assert_eq!(Sid::from_bytes(&bytes).unwrap(), sid);
+
+        assert_eq!(
+            WellKnownSid::LocalSystem.sid().to_bytes(),
+            vec![1, 1, 0, 0, 0, 0, 0, 5, 18, 0, 0, 0]
+        );
+        assert_eq!(
+            WellKnownSid::Administrators.sid().to_string(),
+            "S-1-5-32-544"
+        );
+
+        let big = Sid::new(0x1234_5678_9abc, &[1]).unwrap();
+        assert_eq!(big.to_string(), "S-1-0x123456789ABC-1");
+        assert_eq!(big.to_string().parse::<Sid>().unwrap(), big);
+
+        assert!("S-1".parse::<Sid>().is_err());
+        assert!("S-2-5-18".parse::<Sid>().is_err());
+        assert!("S-1-5-x".parse::<Sid>().is_err());
+        assert!("S-1-+5-+18".parse::<Sid>().is_err());
+        assert!("S-1-5-+18".parse::<Sid>().is_err());
+        assert!("S-1-0x+5-18".parse::<Sid>().is_err());
+        assert!("S-1-0x-18".parse::<Sid>().is_err());
+        assert!("S-1-5--18".parse::<Sid>().is_err());
+        assert_eq!("S-1-0x5-18".parse::<Sid>().unwrap().to_string(), "S-1-5-18");
+        assert!(Sid::from_bytes(&bytes[..27]).is_err());
+    }
+
+    #[test]
+    fn test_domain_sids() {
+        let mut sids = DomainSids::new(None);
+        assert!(sids.domain_sid().is_domain());
+        let alice = sids.sid("alice");
+        let bob = sids.sid("bob");
+        assert_eq!(alice.rid(), Some(FIRST_RID));
+        assert_eq!(bob.rid(), Some(FIRST_RID + 1));
+        assert_eq!(sids.sid("Alice"), alice);
+        assert_eq!(sids.len(), 2);
+        assert_eq!(bob.domain().as_ref(), Some(sids.domain_sid()));
+        assert_eq!(
+            sids.well_known(DomainRid::DomainUsers).rid(),
+            Some(DomainRid::DomainUsers.rid())
+        );
+
+        let sid = user_sid(Some(sids.domain_sid())).unwrap();
+        assert_eq!(sid.domain().as_ref(), Some(sids.domain_sid()));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let domain = domain_sid();
+        assert_eq!(domain_sids().take(10).count(), 10);
+        assert!(user_sids(Some(&domain))
+            .take(10)
+            .all(|sid| sid.unwrap().domain().as_ref() == Some(&domain)));
+        assert!(well_known_sids(Some(WellKnownSid::LocalSystem))
+            .take(10)
+            .all(|sid| sid.to_string() == "S-1-5-18"));
+    }
+}
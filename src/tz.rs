@@ -1,9 +1,45 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, FixedOffset, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName};
 use csv;
 use rand::{seq::SliceRandom, thread_rng};
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Serialize};
 
 type Error = String;
 
+/// time zone of the capital of the countries with more than one
+static CAPITAL_TIME_ZONES: &[(&str, &str)] = &[
+    ("AR", "America/Argentina/Buenos_Aires"),
+    ("AU", "Australia/Sydney"),
+    ("BR", "America/Sao_Paulo"),
+    ("CA", "America/Toronto"),
+    ("CD", "Africa/Lagos"),
+    ("CL", "America/Santiago"),
+    ("CN", "Asia/Shanghai"),
+    ("CY", "Asia/Nicosia"),
+    ("DE", "Europe/Berlin"),
+    ("EC", "America/Guayaquil"),
+    ("ES", "Europe/Madrid"),
+    ("FM", "Pacific/Guadalcanal"),
+    ("ID", "Asia/Jakarta"),
+    ("KI", "Pacific/Tarawa"),
+    ("KZ", "Asia/Almaty"),
+    ("MH", "Pacific/Tarawa"),
+    ("MN", "Asia/Ulaanbaatar"),
+    ("MX", "America/Mexico_City"),
+    ("MY", "Asia/Singapore"),
+    ("NZ", "Pacific/Auckland"),
+    ("PG", "Pacific/Port_Moresby"),
+    ("PT", "Europe/Lisbon"),
+    ("RU", "Europe/Moscow"),
+    ("UA", "Europe/Kyiv"),
+    ("US", "America/New_York"),
+    ("UZ", "Asia/Tashkent"),
+    ("VN", "Asia/Bangkok"),
+];
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Tz {
     alpha_2_code: String,
@@ -11,67 +47,381 @@ pub struct Tz {
     continent: String,
     capital: String,
     name: String,
-    #[serde(deserialize_with = "string_to_vec")]
+    /// canonical IANA time zones of the country, from zone1970.tab
+    #[serde(skip_deserializing)]
     timezones: Vec<String>,
 }
 
-fn string_to_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
-    where
-        D: Deserializer<'de>,
-{
-    let s: &str = Deserialize::deserialize(deserializer)?;
-    Ok(s.split(",").map(|x| x.to_string()).collect::<Vec<String>>())
+/// The countries, parsed once & indexed by every lookup
+struct TzIndex {
+    countries: Vec<Tz>,
+    by_alpha_2: HashMap<String, usize>,
+    by_alpha_3: HashMap<String, usize>,
+    by_name: HashMap<String, usize>,
+    by_capital: HashMap<String, Vec<usize>>,
+    by_continent: HashMap<String, Vec<usize>>,
+    /// canonical zone names
+    by_zone: HashMap<String, Vec<usize>>,
+}
+
+impl TzIndex {
+    fn new(countries: Vec<Tz>) -> Self {
+        let mut index = TzIndex {
+            countries: vec![],
+            by_alpha_2: HashMap::new(),
+            by_alpha_3: HashMap::new(),
+            by_name: HashMap::new(),
+            by_capital: HashMap::new(),
+            by_continent: HashMap::new(),
+            by_zone: HashMap::new(),
+        };
+        for (i, tz) in countries.iter().enumerate() {
+            index.by_alpha_2.insert(key(&tz.alpha_2_code), i);
+            index.by_alpha_3.insert(key(&tz.alpha_3_code), i);
+            index.by_name.insert(key(&tz.name), i);
+            index
+                .by_capital
+                .entry(key(&tz.capital))
+                .or_default()
+                .push(i);
+            index
+                .by_continent
+                .entry(key(&tz.continent))
+                .or_default()
+                .push(i);
+            for zone in &tz.timezones {
+                index.by_zone.entry(key(zone)).or_default().push(i);
+            }
+        }
+        index.countries = countries;
+        index
+    }
+
+    fn one(&'static self, map: &HashMap<String, usize>, q: &str) -> Option<&'static Tz> {
+        map.get(&key(q)).map(|i| &self.countries[*i])
+    }
+
+    fn many(&'static self, map: &HashMap<String, Vec<usize>>, q: &str) -> Vec<&'static Tz> {
+        map.get(&key(q))
+            .map(|found| found.iter().map(|i| &self.countries[*i]).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn key(s: &str) -> String {
+    s.trim().to_lowercase()
+}
+
+fn index() -> Result<&'static TzIndex, Error> {
+    // parsed once, lookups are used in bulk by the user generators
+    static INDEX: OnceLock<Result<TzIndex, Error>> = OnceLock::new();
+    INDEX
+        .get_or_init(|| read_from_tz().map(TzIndex::new))
+        .as_ref()
+        .map_err(|e| e.clone())
 }
 
 impl Tz {
-    fn load() -> Result<Vec<Self>, Error> {
-        //! Loads the Timezones Database
-        read_from_tz()
+    pub fn alpha_2_code(&self) -> &str {
+        //! 2 letter iso country code, eg: IN
+        &self.alpha_2_code
+    }
+
+    pub fn alpha_3_code(&self) -> &str {
+        //! 3 letter iso country code, eg: IND
+        &self.alpha_3_code
+    }
+
+    pub fn continent(&self) -> &str {
+        &self.continent
+    }
+
+    pub fn capital(&self) -> &str {
+        &self.capital
+    }
+
+    pub fn name(&self) -> &str {
+        //! Country name
+        &self.name
+    }
+
+    pub fn timezones(&self) -> &[String] {
+        //! Canonical names of the time zones of the country, eg: Asia/Kolkata
+        &self.timezones
+    }
+
+    pub fn capital_timezone(&self) -> Option<&str> {
+        //! Canonical name of the time zone of the capital,
+        //! eg: America/New_York for Washington, D.C.
+        let capital = CAPITAL_TIME_ZONES
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(&self.alpha_2_code))
+            .map(|(_, zone)| *zone);
+        match capital {
+            Some(zone) => self.timezones.iter().find(|z| *z == zone),
+            None => self.timezones.first(),
+        }
+        .map(String::as_str)
+    }
+
+    pub fn all() -> Result<&'static [Tz], Error> {
+        //! Every country of the Timezones Database
+        Ok(&index()?.countries)
     }
 
     pub fn get_random_tz() -> Result<Tz, Error> {
         //! Gets a Random timezone
-        let tzs = match Self::load() {
-            Ok(t) => t,
-            Err(e) => return Err(format!("Error: {}", e.to_string())),
-        };
-        let res = tzs.choose(&mut thread_rng()).unwrap();
-        Ok(res.clone())
+        Self::all()?
+            .choose(&mut thread_rng())
+            .cloned()
+            .ok_or_else(|| String::from("Error: no timezones"))
+    }
+
+    pub fn by_iso_code(iso_code: &str) -> Result<Option<&'static Tz>, Error> {
+        //! The country of a 2 letter iso country code
+        //! or 3 letter iso country code
+        let index = index()?;
+        Ok(match iso_code.trim().len() {
+            2 => index.one(&index.by_alpha_2, iso_code),
+            3 => index.one(&index.by_alpha_3, iso_code),
+            _ => None,
+        })
+    }
+
+    pub fn by_country(country_name: &str) -> Result<Option<&'static Tz>, Error> {
+        //! The country of a given country name
+        let index = index()?;
+        Ok(index.one(&index.by_name, country_name))
+    }
+
+    pub fn by_capital(capital: &str) -> Result<Vec<&'static Tz>, Error> {
+        let index = index()?;
+        Ok(index.many(&index.by_capital, capital))
+    }
+
+    pub fn by_continent(continent: &str) -> Result<Vec<&'static Tz>, Error> {
+        let index = index()?;
+        Ok(index.many(&index.by_continent, continent))
+    }
+
+    pub fn by_zone(zone_name: &str) -> Result<Vec<&'static Tz>, Error> {
+        //! The countries using a time zone, by its canonical name or
+        //! any of its aliases, eg: Europe/Paris is used in FR & MC
+        let index = index()?;
+        Ok(match canonical(zone_name) {
+            Some(timezone) => index.many(&index.by_zone, timezone.name()),
+            None => vec![],
+        })
     }
 
-    pub fn tz_by_iso_code(iso_code: &str) -> Result<Option<String>, Error> {
+    pub fn tz_by_iso_code(iso_code: &str) -> Result<Option<&'static [String]>, Error> {
         //! Get the countries time zone information based upon
         //! a 2 letter iso country code or 3 letter iso country code
-        Self::tz_query(iso_code)
+        Ok(Self::by_iso_code(iso_code)?.map(|tz| tz.timezones()))
     }
 
-    pub fn tz_by_country(country_name: &str) -> Result<Option<String>, Error> {
+    pub fn tz_by_country(country_name: &str) -> Result<Option<&'static [String]>, Error> {
         //! Get the countries time zone information based upon
         //! a given country name
-        Self::tz_query(country_name)
+        Ok(Self::by_country(country_name)?.map(|tz| tz.timezones()))
     }
 
-    fn tz_query(q: &str) -> Result<Option<String>, Error> {
-        let mut tzs = match Self::load() {
-            Ok(t) => t,
-            Err(e) => return Err(format!("Error: {}", e.to_string())),
+    pub fn zones(&self) -> Result<Vec<Zone>, Error> {
+        //! The time zones of the country, with their current
+        //! UTC offsets, daylight saving time & aliases
+        self.timezones
+            .iter()
+            .map(|name| {
+                Zone::by_name(name)?.ok_or_else(|| format!("Error: unknown time zone '{}'", name))
+            })
+            .collect()
+    }
+}
+
+/// A time zone of the IANA tz database
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Zone {
+    name: String,
+    timezone: chrono_tz::Tz,
+    aliases: Vec<String>,
+    countries: Vec<String>,
+    coordinates: Option<String>,
+    comments: Option<String>,
+}
+
+impl Zone {
+    pub fn by_name(name: &str) -> Result<Option<Zone>, Error> {
+        //! A time zone by its canonical name or any of its
+        //! (deprecated) aliases, eg: Asia/Calcutta is Asia/Kolkata
+        let timezone = match canonical(name) {
+            Some(timezone) => timezone,
+            None => return Ok(None),
         };
+        let entry = zone1970()?
+            .iter()
+            .find(|entry| entry.name == timezone.name());
+        Ok(Some(Zone {
+            name: timezone.name().to_string(),
+            timezone,
+            aliases: links()
+                .iter()
+                .filter(|(target, _)| *target == timezone.name())
+                .map(|(_, alias)| alias.to_string())
+                .collect(),
+            countries: entry.map(|e| e.countries.clone()).unwrap_or_default(),
+            coordinates: entry.map(|e| e.coordinates.clone()),
+            comments: entry.and_then(|e| e.comments.clone()),
+        }))
+    }
+
+    pub fn all() -> Result<Vec<Zone>, Error> {
+        //! The time zones of the countries of the world, as listed in zone1970.tab
+        zone1970()?
+            .iter()
+            .filter_map(|entry| Zone::by_name(&entry.name).transpose())
+            .collect()
+    }
+
+    pub fn name(&self) -> &str {
+        //! Canonical name, eg: Asia/Kolkata
+        &self.name
+    }
 
-        let result: String = tzs
-            .drain(..)
-            .filter(|x| match q.len() {
-                2 => x.alpha_2_code.eq_ignore_ascii_case(q),
-                3 => x.alpha_3_code.eq_ignore_ascii_case(q),
-                _ => x.name.eq_ignore_ascii_case(q),
+    pub fn aliases(&self) -> &[String] {
+        //! Deprecated names linking to this time zone, eg: Asia/Calcutta
+        &self.aliases
+    }
+
+    pub fn is_alias(name: &str) -> bool {
+        //! Whether a name is a deprecated link to a canonical time zone
+        links()
+            .iter()
+            .any(|(_, alias)| alias.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn countries(&self) -> &[String] {
+        //! 2 letter iso codes of the countries using the time zone since 1970
+        &self.countries
+    }
+
+    pub fn coordinates(&self) -> Option<&str> {
+        //! ISO 6709 coordinates of its principal location, eg: +2232+08822
+        self.coordinates.as_deref()
+    }
+
+    pub fn comments(&self) -> Option<&str> {
+        //! Region of the country the time zone is for, if it has several
+        self.comments.as_deref()
+    }
+
+    pub fn timezone(&self) -> chrono_tz::Tz {
+        self.timezone
+    }
+
+    pub fn utc_offset(&self) -> FixedOffset {
+        //! Current offset from UTC
+        self.utc_offset_at(&Utc::now())
+    }
+
+    pub fn utc_offset_at(&self, time: &DateTime<Utc>) -> FixedOffset {
+        //! Offset from UTC at a given time
+        self.convert(time).offset().fix()
+    }
+
+    pub fn is_dst(&self) -> bool {
+        //! Whether daylight saving time is currently in effect
+        self.is_dst_at(&Utc::now())
+    }
+
+    pub fn is_dst_at(&self, time: &DateTime<Utc>) -> bool {
+        //! Whether daylight saving time is in effect at a given time
+        !self.convert(time).offset().dst_offset().is_zero()
+    }
+
+    pub fn abbreviation_at(&self, time: &DateTime<Utc>) -> Option<String> {
+        //! Abbreviation of the offset at a given time, eg: CEST
+        self.convert(time)
+            .offset()
+            .abbreviation()
+            .map(|a| a.to_string())
+    }
+
+    pub fn convert<T: TimeZone>(&self, time: &DateTime<T>) -> DateTime<chrono_tz::Tz> {
+        //! A time in the time zone
+        time.with_timezone(&self.timezone)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Zone1970Entry {
+    countries: Vec<String>,
+    coordinates: String,
+    name: String,
+    comments: Option<String>,
+}
+
+fn canonical(name: &str) -> Option<chrono_tz::Tz> {
+    let name = name.trim();
+    let canonical = links()
+        .iter()
+        .find(|(_, alias)| alias.eq_ignore_ascii_case(name))
+        .map(|(target, _)| *target)
+        .unwrap_or(name);
+    chrono_tz::TZ_VARIANTS
+        .iter()
+        .find(|tz| tz.name().eq_ignore_ascii_case(canonical))
+        .copied()
+}
+
+fn zone1970() -> Result<&'static [Zone1970Entry], Error> {
+    static ZONE1970: OnceLock<Result<Vec<Zone1970Entry>, Error>> = OnceLock::new();
+    ZONE1970
+        .get_or_init(read_zone1970)
+        .as_deref()
+        .map_err(|e| e.clone())
+}
+
+fn links() -> &'static [(&'static str, &'static str)] {
+    static LINKS: OnceLock<Vec<(&'static str, &'static str)>> = OnceLock::new();
+    LINKS.get_or_init(read_links)
+}
+
+fn read_zone1970() -> Result<Vec<Zone1970Entry>, Error> {
+    // tab separated: country codes, coordinates, zone name & optional comments
+    include_str!("../assets/tzdb/zone1970.tab")
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .map(|line| {
+            let fields = line.split('\t').collect::<Vec<&str>>();
+            if fields.len() < 3 {
+                return Err(format!("Error: Not a valid zone1970.tab line '{}'", line));
+            }
+            Ok(Zone1970Entry {
+                countries: fields[0].split(',').map(|c| c.to_string()).collect(),
+                coordinates: fields[1].to_string(),
+                name: fields[2].to_string(),
+                comments: fields.get(3).map(|c| c.to_string()),
             })
-            .map(|x| x.timezones.join(","))
-            .collect();
-        if !result.is_empty() {
-            Ok(Some(result))
-        } else {
-            Ok(None)
+        })
+        .collect()
+}
+
+fn read_links() -> Vec<(&'static str, &'static str)> {
+    // Link TARGET LINK-NAME, in the backward compatibility file & etcetera
+    [
+        include_str!("../assets/tzdb/backward"),
+        include_str!("../assets/tzdb/etcetera"),
+    ]
+    .iter()
+    .flat_map(|file| file.lines())
+    .filter_map(|line| {
+        let mut fields = line.split('#').next()?.split_whitespace();
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some("Link"), Some(target), Some(alias)) => Some((target, alias)),
+            _ => None,
         }
-    }
+    })
+    .collect()
 }
 
 fn csv_de(csv_text: &str) -> Result<Vec<Tz>, csv::Error> {
@@ -82,10 +432,18 @@ fn csv_de(csv_text: &str) -> Result<Vec<Tz>, csv::Error> {
 
 pub(crate) fn read_from_tz() -> Result<Vec<Tz>, Error> {
     let csv_text = include_str!("../assets/tz.csv");
-    let records = match csv_de(csv_text.trim()) {
+    let mut records = match csv_de(csv_text.trim()) {
         Ok(r) => r,
-        Err(e) => return Err(format!("Error: Not a valid tz csv file. {}", e.to_string())),
+        Err(e) => return Err(format!("Error: Not a valid tz csv file. {}", e)),
     };
+    let zones = zone1970()?;
+    for record in records.iter_mut() {
+        record.timezones = zones
+            .iter()
+            .filter(|zone| zone.countries.contains(&record.alpha_2_code))
+            .map(|zone| zone.name.clone())
+            .collect();
+    }
     Ok(records)
 }
 
@@ -95,7 +453,23 @@ mod tests {
     #[test]
     fn test_load_tz() {
         let tzs = read_from_tz();
-        assert!(tzs.is_ok())
+        assert!(tzs.is_ok());
+        assert!(tzs.unwrap().iter().all(|tz| !tz.timezones.is_empty()));
+        // every country with more than one zone knows that of its capital
+        for tz in Tz::all().unwrap() {
+            let zone = tz.capital_timezone().unwrap();
+            assert!(tz.timezones().iter().any(|z| z == zone));
+            assert!(
+                tz.timezones().len() == 1
+                    || CAPITAL_TIME_ZONES
+                        .iter()
+                        .any(|(code, _)| *code == tz.alpha_2_code())
+            );
+        }
+        let capital = |code: &str| Tz::by_iso_code(code).unwrap().unwrap().capital_timezone();
+        assert_eq!(capital("US"), Some("America/New_York"));
+        assert_eq!(capital("RU"), Some("Europe/Moscow"));
+        assert_eq!(capital("IN"), Some("Asia/Kolkata"));
     }
 
     #[test]
@@ -105,14 +479,14 @@ mod tests {
 
         let tz = tz.unwrap();
         assert!(tz.is_some());
-        assert_eq!(tz.unwrap(), "Asia/Calcutta,Asia/Kolkata".to_string());
+        assert_eq!(tz.unwrap(), &["Asia/Kolkata"]);
 
         let tz = Tz::tz_by_iso_code("InD");
         assert!(tz.is_ok());
 
         let tz = tz.unwrap();
         assert!(tz.is_some());
-        assert_eq!(tz.unwrap(), "Asia/Calcutta,Asia/Kolkata".to_string());
+        assert_eq!(tz.unwrap(), &["Asia/Kolkata"]);
     }
 
     #[test]
@@ -126,12 +500,13 @@ mod tests {
 
     #[test]
     fn test_tz_lookup_by_country_name() {
+        // Monaco keeps the time of Paris since 1970
         let tz = Tz::tz_by_country("Monaco");
         assert!(tz.is_ok());
 
         let tz = tz.unwrap();
         assert!(tz.is_some());
-        assert_eq!(tz.unwrap(), "Europe/Monaco".to_string());
+        assert_eq!(tz.unwrap(), &["Europe/Paris"]);
     }
 
     #[test]
@@ -148,4 +523,65 @@ mod tests {
         let tz = Tz::get_random_tz();
         assert!(tz.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_zones() {
+        let kolkata = Zone::by_name("asia/calcutta").unwrap().unwrap();
+        assert_eq!(kolkata.name(), "Asia/Kolkata");
+        assert!(kolkata.aliases().contains(&"Asia/Calcutta".to_string()));
+        assert!(Zone::is_alias("Asia/Calcutta") && !Zone::is_alias("Asia/Kolkata"));
+        assert_eq!(kolkata.countries(), &["IN".to_string()]);
+        assert_eq!(
+            kolkata.utc_offset(),
+            FixedOffset::east_opt(5 * 3600 + 1800).unwrap()
+        );
+        assert!(!kolkata.is_dst());
+
+        let berlin = Zone::by_name("Europe/Berlin").unwrap().unwrap();
+        let (winter, summer) = (
+            Utc.with_ymd_and_hms(2023, 1, 15, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2023, 7, 15, 12, 0, 0).unwrap(),
+        );
+        assert!(!berlin.is_dst_at(&winter) && berlin.is_dst_at(&summer));
+        assert_eq!(berlin.utc_offset_at(&summer).local_minus_utc(), 7200);
+        assert_eq!(berlin.abbreviation_at(&summer), Some("CEST".to_string()));
+        assert_eq!(
+            berlin.convert(&summer).to_rfc3339(),
+            "2023-07-15T14:00:00+02:00"
+        );
+
+        assert_eq!(Zone::by_name("GMT").unwrap().unwrap().name(), "Etc/GMT");
+        assert!(Zone::by_name("Mars/Olympus_Mons").unwrap().is_none());
+        let zones = Zone::all().unwrap();
+        assert!(zones.len() > 300);
+        assert!(zones.iter().all(|zone| !zone.countries().is_empty()));
+
+        let india = Tz::by_iso_code("IN").unwrap().unwrap();
+        assert_eq!(india.zones().unwrap(), vec![kolkata]);
+    }
+
+    #[test]
+    fn test_tz_index() {
+        let india = Tz::by_iso_code("ind").unwrap().unwrap();
+        assert_eq!(india.alpha_2_code(), "IN");
+        assert_eq!(india.name(), "India");
+        assert!(std::ptr::eq(
+            india,
+            Tz::by_country("INDIA").unwrap().unwrap()
+        ));
+        assert_eq!(Tz::by_capital("New Delhi").unwrap(), vec![india]);
+        assert!(Tz::by_continent("Asia").unwrap().contains(&india));
+        assert!(Tz::by_continent("Atlantis").unwrap().is_empty());
+
+        // back from a time zone, or one of its aliases, to its countries
+        assert_eq!(Tz::by_zone("Asia/Calcutta").unwrap(), vec![india]);
+        let paris = Tz::by_zone("europe/paris")
+            .unwrap()
+            .iter()
+            .map(|tz| tz.alpha_2_code())
+            .collect::<Vec<&str>>();
+        assert!(paris.contains(&"FR") && paris.contains(&"MC"));
+        assert!(Tz::by_zone("Etc/UTC").unwrap().is_empty());
+        assert_eq!(Tz::all().unwrap().len(), 193);
+    }
+}
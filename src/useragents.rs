@@ -0,0 +1,697 @@
+/*
+User agent generators
+Desktop & mobile browsers, crawlers, command line tools and the odd
+user agents malware & scanners are known for. Browser / OS / version
+combinations are kept consistent and weighted by market share.
+*/
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+type Error = String;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AgentKind {
+    Desktop,
+    Mobile,
+    Bot,
+    Cli,
+    Malware,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Os {
+    Windows,
+    MacOs,
+    Linux,
+    ChromeOs,
+    Android,
+    Ios,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserAgent {
+    pub kind: AgentKind,
+    /// browser, crawler or tool name, eg: Chrome, Googlebot, curl
+    pub product: String,
+    pub product_version: String,
+    pub os: Option<Os>,
+    pub os_version: Option<String>,
+    /// mobile device model, eg: iPhone, Pixel 7
+    pub device: Option<String>,
+}
+
+// (name, version, homepage)
+static BOTS: &[(&str, &str, &str)] = &[
+    ("Googlebot", "2.1", "http://www.google.com/bot.html"),
+    ("bingbot", "2.0", "http://www.bing.com/bingbot.htm"),
+    ("YandexBot", "3.0", "http://yandex.com/bots"),
+    (
+        "Baiduspider",
+        "2.0",
+        "http://www.baidu.com/search/spider.html",
+    ),
+    (
+        "DuckDuckBot",
+        "1.1",
+        "http://duckduckgo.com/duckduckbot.html",
+    ),
+    ("Applebot", "0.1", "http://www.apple.com/go/applebot"),
+    ("AhrefsBot", "7.0", "http://ahrefs.com/robot/"),
+    ("SemrushBot", "7~bl", "http://www.semrush.com/bot.html"),
+    ("MJ12bot", "v1.4.8", "http://mj12bot.com/"),
+];
+
+// (name, versions)
+static CLI_TOOLS: &[(&str, &[&str])] = &[
+    (
+        "curl",
+        &["7.68.0", "7.81.0", "7.88.1", "8.1.2", "8.4.0", "8.5.0"],
+    ),
+    ("Wget", &["1.20.3", "1.21.2", "1.21.3", "1.21.4"]),
+    ("python-requests", &["2.25.1", "2.28.2", "2.31.0", "2.32.3"]),
+    ("Go-http-client", &["1.1", "2.0"]),
+    ("okhttp", &["3.14.9", "4.9.3", "4.11.0", "4.12.0"]),
+    ("Python-urllib", &["3.8", "3.10", "3.11", "3.12"]),
+    ("libwww-perl", &["6.52", "6.67", "6.72"]),
+    ("Java", &["1.8.0_381", "11.0.20", "17.0.8", "21.0.1"]),
+    (
+        "WindowsPowerShell",
+        &["5.1.17763.316", "5.1.19041.3570", "5.1.22621.2506"],
+    ),
+];
+
+// (name, version, user agent) - hard coded user agents of malware,
+// implants and scanners
+static MALWARE: &[(&str, &str, &str)] = &[
+    (
+        "MSIE",
+        "6.0",
+        "Mozilla/4.0 (compatible; MSIE 6.0; Windows NT 5.1; SV1)",
+    ),
+    (
+        "MSIE",
+        "7.0",
+        "Mozilla/4.0 (compatible; MSIE 7.0; Windows NT 5.1)",
+    ),
+    (
+        "MSIE",
+        "8.0",
+        "Mozilla/4.0 (compatible; MSIE 8.0; Windows NT 6.1; Trident/4.0)",
+    ),
+    (
+        "MSIE",
+        "9.0",
+        "Mozilla/5.0 (compatible; MSIE 9.0; Windows NT 6.1; WOW64; Trident/5.0; MASP)",
+    ),
+    ("Mozilla", "5.0", "Mozilla/5.0"),
+    ("WinHTTP", "", "WinHTTP"),
+    ("Microsoft BITS", "7.8", "Microsoft BITS/7.8"),
+    (
+        "sqlmap",
+        "1.7.2",
+        "sqlmap/1.7.2#stable (https://sqlmap.org)",
+    ),
+    (
+        "Nikto",
+        "2.5.0",
+        "Mozilla/5.00 (Nikto/2.5.0) (Evasions:None) (Test:Port Check)",
+    ),
+    (
+        "Nmap Scripting Engine",
+        "",
+        "Mozilla/5.0 (compatible; Nmap Scripting Engine; https://nmap.org/book/nse.html)",
+    ),
+    (
+        "masscan",
+        "1.3",
+        "masscan/1.3 (https://github.com/robertdavidgraham/masscan)",
+    ),
+    ("zgrab", "0.x", "Mozilla/5.0 zgrab/0.x"),
+    ("Hello World", "", "Hello, World"),
+];
+
+static ANDROID_DEVICES: &[&str] = &[
+    "Pixel 6",
+    "Pixel 7",
+    "Pixel 7 Pro",
+    "Pixel 8",
+    "SM-G991B",
+    "SM-G998B",
+    "SM-S911B",
+    "SM-S918B",
+    "SM-A536B",
+    "SM-A546B",
+    "M2101K6G",
+    "22101316G",
+    "CPH2451",
+    "moto g54 5G",
+    "ONEPLUS A6013",
+];
+
+struct Patterns {
+    bot: Regex,
+    powershell: Regex,
+    cli: Regex,
+    platform: Regex,
+    ios: Regex,
+    android: Regex,
+    mac: Regex,
+    windows: Regex,
+    cros: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    // compiled once, parsing is used in bulk for round trip tests
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        bot: Regex::new(r"^Mozilla/5\.0 \(compatible; ([^/;]+)/([^;]+); \+[^)]+\)$").unwrap(),
+        powershell: Regex::new(
+            r"^Mozilla/5\.0 \(Windows NT; Windows NT ([\d.]+); [\w-]+\) WindowsPowerShell/([\d.]+)$",
+        )
+        .unwrap(),
+        cli: Regex::new(r"^([\w.-]+)/([\w.]+)$").unwrap(),
+        platform: Regex::new(r"^Mozilla/5\.0 \(([^)]+)\)").unwrap(),
+        ios: Regex::new(r"^(iPhone|iPad); CPU (?:iPhone )?OS ([\d_]+) like Mac OS X$").unwrap(),
+        android: Regex::new(r"^(?:Linux; )?Android ([\d.]+); (.+)$").unwrap(),
+        mac: Regex::new(r"^Macintosh; Intel Mac OS X ([\d_.]+)$").unwrap(),
+        windows: Regex::new(r"^Windows NT ([\d.]+)").unwrap(),
+        cros: Regex::new(r"^X11; CrOS \S+ ([\d.]+)$").unwrap(),
+    })
+}
+
+fn pick_weighted<T: Copy, R: Rng + ?Sized>(rng: &mut R, choices: &[(T, u32)]) -> T {
+    choices
+        .choose_weighted(rng, |(_, weight)| *weight)
+        .map(|(item, _)| *item)
+        .unwrap_or(choices[0].0)
+}
+
+fn chromium_version<R: Rng + ?Sized>(rng: &mut R) -> String {
+    format!("{}.0.0.0", rng.gen_range(110..=131))
+}
+
+impl UserAgent {
+    pub fn random(kind: Option<AgentKind>) -> UserAgent {
+        //! Generate a random user agent; without a kind, desktop & mobile
+        //! browsers dominate with a small share of crawlers & tools.
+        //! Malware user agents are only generated when asked for.
+        let mut trng = rand::thread_rng();
+        let kind = kind.unwrap_or_else(|| {
+            pick_weighted(
+                &mut trng,
+                &[
+                    (AgentKind::Desktop, 55),
+                    (AgentKind::Mobile, 40),
+                    (AgentKind::Bot, 4),
+                    (AgentKind::Cli, 1),
+                ],
+            )
+        });
+        match kind {
+            AgentKind::Desktop => Self::random_desktop(&mut trng),
+            AgentKind::Mobile => Self::random_mobile(&mut trng),
+            AgentKind::Bot => {
+                let (name, version, _) = BOTS.choose(&mut trng).unwrap_or(&BOTS[0]);
+                Self::product(kind, name, version)
+            }
+            AgentKind::Cli => {
+                let (name, versions) = CLI_TOOLS.choose(&mut trng).unwrap_or(&CLI_TOOLS[0]);
+                let mut ua = Self::product(kind, name, versions.choose(&mut trng).unwrap_or(&""));
+                if *name == "WindowsPowerShell" {
+                    ua.os = Some(Os::Windows);
+                    ua.os_version = Some("10.0".to_string());
+                }
+                ua
+            }
+            AgentKind::Malware => {
+                let (name, version, _) = MALWARE.choose(&mut trng).unwrap_or(&MALWARE[0]);
+                Self::product(kind, name, version)
+            }
+        }
+    }
+
+    fn product(kind: AgentKind, name: &str, version: &str) -> UserAgent {
+        UserAgent {
+            kind,
+            product: name.to_string(),
+            product_version: version.to_string(),
+            os: None,
+            os_version: None,
+            device: None,
+        }
+    }
+
+    fn random_desktop<R: Rng + ?Sized>(rng: &mut R) -> UserAgent {
+        let os = pick_weighted(
+            rng,
+            &[
+                (Os::Windows, 70),
+                (Os::MacOs, 20),
+                (Os::Linux, 6),
+                (Os::ChromeOs, 4),
+            ],
+        );
+        let browser = match os {
+            Os::Windows => pick_weighted(
+                rng,
+                &[("Chrome", 66), ("Edge", 20), ("Firefox", 9), ("Opera", 5)],
+            ),
+            Os::MacOs => pick_weighted(
+                rng,
+                &[("Chrome", 50), ("Safari", 38), ("Firefox", 6), ("Edge", 6)],
+            ),
+            Os::Linux => pick_weighted(rng, &[("Chrome", 60), ("Firefox", 40)]),
+            _ => "Chrome",
+        };
+        let version = match browser {
+            "Firefox" => format!("{}.0", rng.gen_range(110..=131)),
+            "Safari" => ["15.6.1", "16.5", "16.6", "17.0", "17.1", "17.2.1", "17.4.1"]
+                .choose(rng)
+                .unwrap_or(&"17.1")
+                .to_string(),
+            "Opera" => format!("{}.0.0.0", rng.gen_range(96..=114)),
+            _ => chromium_version(rng),
+        };
+        let os_version = match os {
+            Os::Windows => Some("10.0".to_string()),
+            // firefox reports a shorter macOS version than the others
+            Os::MacOs if browser == "Firefox" => Some("10.15".to_string()),
+            Os::MacOs => Some("10.15.7".to_string()),
+            Os::ChromeOs => Some(format!("{}.0.0", rng.gen_range(14000..16000))),
+            _ => None,
+        };
+        UserAgent {
+            kind: AgentKind::Desktop,
+            product: browser.to_string(),
+            product_version: version,
+            os: Some(os),
+            os_version,
+            device: None,
+        }
+    }
+
+    fn random_mobile<R: Rng + ?Sized>(rng: &mut R) -> UserAgent {
+        let os = pick_weighted(rng, &[(Os::Android, 70), (Os::Ios, 30)]);
+        if os == Os::Ios {
+            let device = pick_weighted(rng, &[("iPhone", 85), ("iPad", 15)]);
+            let os_version = format!("{}_{}", rng.gen_range(15..=17), rng.gen_range(0..=6));
+            let browser = pick_weighted(rng, &[("Safari", 90), ("Chrome", 10)]);
+            let version = if browser == "Safari" {
+                os_version.replace('_', ".")
+            } else {
+                chromium_version(rng)
+            };
+            return UserAgent {
+                kind: AgentKind::Mobile,
+                product: browser.to_string(),
+                product_version: version,
+                os: Some(os),
+                os_version: Some(os_version.replace('_', ".")),
+                device: Some(device.to_string()),
+            };
+        }
+
+        let browser = pick_weighted(
+            rng,
+            &[("Chrome", 80), ("Samsung Internet", 15), ("Firefox", 5)],
+        );
+        let device = if browser == "Samsung Internet" {
+            // samsung's browser ships on samsung phones
+            ANDROID_DEVICES
+                .iter()
+                .filter(|d| d.starts_with("SM-"))
+                .collect::<Vec<_>>()
+                .choose(rng)
+                .map(|d| d.to_string())
+        } else if browser == "Firefox" {
+            // firefox for android does not report the device
+            None
+        } else {
+            ANDROID_DEVICES.choose(rng).map(|d| d.to_string())
+        };
+        let version = match browser {
+            "Firefox" => format!("{}.0", rng.gen_range(110..=131)),
+            "Samsung Internet" => format!("{}.0", rng.gen_range(20..=25)),
+            _ => chromium_version(rng),
+        };
+        UserAgent {
+            kind: AgentKind::Mobile,
+            product: browser.to_string(),
+            product_version: version,
+            os: Some(os),
+            os_version: Some(rng.gen_range(10..=14).to_string()),
+            device,
+        }
+    }
+
+    fn platform(&self) -> String {
+        let os_version = self.os_version.clone().unwrap_or_default();
+        match self.os {
+            Some(Os::Windows) => format!("Windows NT {}; Win64; x64", os_version),
+            Some(Os::MacOs) if self.product == "Firefox" => {
+                format!("Macintosh; Intel Mac OS X {}", os_version)
+            }
+            Some(Os::MacOs) => {
+                format!("Macintosh; Intel Mac OS X {}", os_version.replace('.', "_"))
+            }
+            Some(Os::ChromeOs) => format!("X11; CrOS x86_64 {}", os_version),
+            _ => "X11; Linux x86_64".to_string(),
+        }
+    }
+
+    fn fmt_desktop(&self) -> String {
+        let v = &self.product_version;
+        let chrome = |extra: &str, chrome_version: &str| {
+            format!(
+                "Mozilla/5.0 ({}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{} Safari/537.36{}",
+                self.platform(),
+                chrome_version,
+                extra
+            )
+        };
+        match self.product.as_str() {
+            "Firefox" => format!(
+                "Mozilla/5.0 ({}; rv:{}) Gecko/20100101 Firefox/{}",
+                self.platform(),
+                v,
+                v
+            ),
+            "Safari" => format!(
+                "Mozilla/5.0 ({}) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/{} Safari/605.1.15",
+                self.platform(),
+                v
+            ),
+            "Edge" => chrome(&format!(" Edg/{}", v), v),
+            "Opera" => chrome(&format!(" OPR/{}", v), &opera_chrome_version(v)),
+            _ => chrome("", v),
+        }
+    }
+
+    fn fmt_mobile(&self) -> String {
+        let v = &self.product_version;
+        let os_version = self.os_version.clone().unwrap_or_default();
+        let device = self.device.clone().unwrap_or_default();
+        if self.os == Some(Os::Ios) {
+            let cpu = if device == "iPad" { "OS" } else { "iPhone OS" };
+            let engine = if self.product == "Chrome" {
+                format!("CriOS/{}", v)
+            } else {
+                format!("Version/{}", v)
+            };
+            return format!(
+                "Mozilla/5.0 ({}; CPU {} {} like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) {} Mobile/15E148 Safari/604.1",
+                device,
+                cpu,
+                os_version.replace('.', "_"),
+                engine
+            );
+        }
+        match self.product.as_str() {
+            "Firefox" => format!(
+                "Mozilla/5.0 (Android {}; Mobile; rv:{}) Gecko/{} Firefox/{}",
+                os_version, v, v, v
+            ),
+            "Samsung Internet" => format!(
+                "Mozilla/5.0 (Linux; Android {}; {}) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/{} Chrome/{} Mobile Safari/537.36",
+                os_version,
+                device,
+                v,
+                samsung_chrome_version(v)
+            ),
+            _ => format!(
+                "Mozilla/5.0 (Linux; Android {}; {}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{} Mobile Safari/537.36",
+                os_version, device, v
+            ),
+        }
+    }
+
+    pub fn parse(ua: &str) -> Option<UserAgent> {
+        //! Decompose a user agent string back into its components
+        //!
+        //! ## Example
+        //! ```rust
+        //! use random::useragents::{AgentKind, Os, UserAgent};
+        //!
+        //! let ua = UserAgent::parse("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0").unwrap();
+        //! assert_eq!(ua.kind, AgentKind::Desktop);
+        //! assert_eq!(ua.product, "Firefox");
+        //! assert_eq!(ua.product_version, "120.0");
+        //! assert_eq!(ua.os, Some(Os::Windows));
+        //! ```
+        let ua = ua.trim();
+        if let Some((name, version, _)) = MALWARE.iter().find(|(_, _, m)| *m == ua) {
+            return Some(Self::product(AgentKind::Malware, name, version));
+        }
+
+        let patterns = patterns();
+        if let Some(caps) = patterns.bot.captures(ua) {
+            return Some(Self::product(AgentKind::Bot, &caps[1], &caps[2]));
+        }
+
+        if let Some(caps) = patterns.powershell.captures(ua) {
+            let mut parsed = Self::product(AgentKind::Cli, "WindowsPowerShell", &caps[2]);
+            parsed.os = Some(Os::Windows);
+            parsed.os_version = Some(caps[1].to_string());
+            return Some(parsed);
+        }
+
+        if let Some(caps) = patterns.cli.captures(ua) {
+            if !caps[1].eq("Mozilla") {
+                return Some(Self::product(AgentKind::Cli, &caps[1], &caps[2]));
+            }
+        }
+
+        Self::parse_browser(ua)
+    }
+
+    fn parse_browser(ua: &str) -> Option<UserAgent> {
+        let patterns = patterns();
+        let platform = patterns.platform.captures(ua)?.get(1)?.as_str();
+        let token = |name: &str| -> Option<String> {
+            let start = ua.find(&format!("{}/", name))? + name.len() + 1;
+            let version: String = ua[start..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '_')
+                .collect();
+            Some(version)
+        };
+
+        let (product, product_version) = if let Some(v) = token("Edg") {
+            ("Edge", v)
+        } else if let Some(v) = token("OPR") {
+            ("Opera", v)
+        } else if let Some(v) = token("SamsungBrowser") {
+            ("Samsung Internet", v)
+        } else if let Some(v) = token("CriOS") {
+            ("Chrome", v)
+        } else if let Some(v) = token("Firefox") {
+            ("Firefox", v)
+        } else if let Some(v) = token("Chrome") {
+            ("Chrome", v)
+        } else if let (Some(v), Some(_)) = (token("Version"), token("Safari")) {
+            ("Safari", v)
+        } else {
+            return None;
+        };
+
+        // firefox appends its gecko revision to the platform
+        let platform = match platform.rfind("; rv:") {
+            Some(i) => &platform[..i],
+            None => platform,
+        };
+        let (kind, os, os_version, device) = if let Some(caps) = patterns.ios.captures(platform) {
+            (
+                AgentKind::Mobile,
+                Os::Ios,
+                Some(caps[2].replace('_', ".")),
+                Some(caps[1].to_string()),
+            )
+        } else if let Some(caps) = patterns.android.captures(platform) {
+            let device = match &caps[2] {
+                "Mobile" => None,
+                d => Some(d.to_string()),
+            };
+            (
+                AgentKind::Mobile,
+                Os::Android,
+                Some(caps[1].to_string()),
+                device,
+            )
+        } else if let Some(caps) = patterns.mac.captures(platform) {
+            (
+                AgentKind::Desktop,
+                Os::MacOs,
+                Some(caps[1].replace('_', ".")),
+                None,
+            )
+        } else if let Some(caps) = patterns.windows.captures(platform) {
+            (
+                AgentKind::Desktop,
+                Os::Windows,
+                Some(caps[1].to_string()),
+                None,
+            )
+        } else if let Some(caps) = patterns.cros.captures(platform) {
+            (
+                AgentKind::Desktop,
+                Os::ChromeOs,
+                Some(caps[1].to_string()),
+                None,
+            )
+        } else if platform.contains("Linux") {
+            (AgentKind::Desktop, Os::Linux, None, None)
+        } else {
+            return None;
+        };
+
+        Some(UserAgent {
+            kind,
+            product: product.to_string(),
+            product_version,
+            os: Some(os),
+            os_version,
+            device,
+        })
+    }
+}
+
+fn opera_chrome_version(opera_version: &str) -> String {
+    // opera runs 14 majors behind the chromium it is built on
+    let major: u32 = opera_version
+        .split('.')
+        .next()
+        .and_then(|m| m.parse().ok())
+        .unwrap_or(100);
+    format!("{}.0.0.0", major + 14)
+}
+
+fn samsung_chrome_version(samsung_version: &str) -> String {
+    let major: u32 = samsung_version
+        .split('.')
+        .next()
+        .and_then(|m| m.parse().ok())
+        .unwrap_or(23);
+    format!("{}.0.0.0", major + 92)
+}
+
+impl fmt::Display for UserAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ua = match self.kind {
+            AgentKind::Desktop => self.fmt_desktop(),
+            AgentKind::Mobile => self.fmt_mobile(),
+            AgentKind::Bot => {
+                let url = BOTS
+                    .iter()
+                    .find(|(name, _, _)| *name == self.product)
+                    .map(|(_, _, url)| *url)
+                    .unwrap_or("http://www.example.com/bot.html");
+                format!(
+                    "Mozilla/5.0 (compatible; {}/{}; +{})",
+                    self.product, self.product_version, url
+                )
+            }
+            AgentKind::Cli if self.product == "WindowsPowerShell" => format!(
+                "Mozilla/5.0 (Windows NT; Windows NT {}; en-US) WindowsPowerShell/{}",
+                self.os_version.as_deref().unwrap_or("10.0"),
+                self.product_version
+            ),
+            AgentKind::Cli => format!("{}/{}", self.product, self.product_version),
+            AgentKind::Malware => MALWARE
+                .iter()
+                .find(|(name, version, _)| {
+                    *name == self.product && *version == self.product_version
+                })
+                .map(|(_, _, ua)| ua.to_string())
+                .unwrap_or_else(|| format!("{}/{}", self.product, self.product_version)),
+        };
+        f.write_str(&ua)
+    }
+}
+
+impl FromStr for UserAgent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UserAgent::parse(s).ok_or_else(|| format!("Error: unrecognised user agent '{}'", s))
+    }
+}
+
+pub fn user_agent(kind: Option<AgentKind>) -> String {
+    /*
+    Generate a user agent string
+    :param kind: Desktop, Mobile, Bot, Cli or Malware - random browser heavy mix if not given
+    :return: user agent as str
+    */
+    UserAgent::random(kind).to_string()
+}
+
+pub fn user_agents(kind: Option<AgentKind>) -> impl Iterator<Item = String> {
+    /*
+    Endless stream of user agent strings, see user_agent
+    :return: iterator of user agents
+    */
+    std::iter::repeat_with(move || user_agent(kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let kinds = [
+            AgentKind::Desktop,
+            AgentKind::Mobile,
+            AgentKind::Bot,
+            AgentKind::Cli,
+            AgentKind::Malware,
+        ];
+        for kind in kinds {
+            for _ in 0..500 {
+                let ua = UserAgent::random(Some(kind));
+                assert_eq!(
+                    UserAgent::parse(&ua.to_string()),
+                    Some(ua.clone()),
+                    "{}",
+                    ua
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_known_agents() {
+        let ua: UserAgent = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1"
+            .parse()
+            .unwrap();
+        assert_eq!(ua.kind, AgentKind::Mobile);
+        assert_eq!(ua.product, "Safari");
+        assert_eq!(ua.os, Some(Os::Ios));
+        assert_eq!(ua.os_version.as_deref(), Some("17.1"));
+        assert_eq!(ua.device.as_deref(), Some("iPhone"));
+
+        let ua: UserAgent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0"
+            .parse()
+            .unwrap();
+        assert_eq!(ua.product, "Edge");
+        assert_eq!(ua.os, Some(Os::Windows));
+
+        let ua: UserAgent = "curl/8.4.0".parse().unwrap();
+        assert_eq!(ua.kind, AgentKind::Cli);
+        assert_eq!(ua.product_version, "8.4.0");
+
+        assert!("not a user agent at all".parse::<UserAgent>().is_err());
+    }
+
+    #[test]
+    fn test_iterators() {
+        assert!(user_agents(Some(AgentKind::Bot))
+            .take(10)
+            .all(|a| UserAgent::parse(&a).is_some()));
+    }
+}
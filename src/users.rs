@@ -0,0 +1,383 @@
+/*
+Synthetic user identity generators
+A whole, internally consistent employee record: name, account names,
+email, phone, department & job title, employer, office (country,
+capital & timezone), workstation and its private IP address
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use ipnetwork::Ipv4Network;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::accounts::{
+    down_level_logon_name, user_principal_name, AccountName, ServiceAccountStyle, UsernameFormat,
+    UsernameGenerator,
+};
+use crate::core::constants::{
+    COUNTRY_CALLING_CODES, DEPARTMENT_JOB_TITLES, DUMMY_CORPS, HOSTNAME_SUFFIX, TLDS,
+};
+use crate::names::{Locale, PersonName};
+use crate::tz::Tz;
+
+type Error = String;
+
+/// NetBIOS limit on the name of a computer account
+pub const MAX_COMPUTER_NAME_LENGTH: usize = 15;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Office {
+    pub country: String,
+    /// 2 letter iso country code
+    pub country_code: String,
+    pub calling_code: String,
+    /// offices are located in the capital
+    pub city: String,
+    pub continent: String,
+    /// time zone of the capital, eg: America/New_York
+    pub timezone: String,
+    /// private network of the office, eg: 10.42.0.0/16
+    pub subnet: String,
+}
+
+impl Office {
+    fn from_tz(tz: &Tz, subnet: String) -> Office {
+        let calling_code = COUNTRY_CALLING_CODES
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(tz.alpha_2_code()))
+            .map(|(_, calling_code)| calling_code.to_string())
+            .unwrap_or_else(|| "1".to_string());
+        Office {
+            country: tz.name().to_string(),
+            country_code: tz.alpha_2_code().to_string(),
+            calling_code,
+            city: tz.capital().to_string(),
+            continent: tz.continent().to_string(),
+            timezone: tz.capital_timezone().unwrap_or_default().to_string(),
+            subnet,
+        }
+    }
+
+    pub fn locale(&self) -> Locale {
+        //! Locale of the names of the people working in the office
+        locale_for_country(&self.country_code)
+    }
+
+    pub fn phone_number(&self) -> String {
+        //! Random phone number in the country of the office,
+        //! eg: +44 207 946 0123
+        let mut trng = rand::thread_rng();
+        format!(
+            "+{} {:03} {:03} {:04}",
+            self.calling_code,
+            trng.gen_range(100..1000),
+            trng.gen_range(0..1000),
+            trng.gen_range(0..10000)
+        )
+    }
+}
+
+fn locale_for_country(country_code: &str) -> Locale {
+    if let Some(locale) = Locale::from_country_code(country_code) {
+        return locale;
+    }
+    match country_code.to_ascii_uppercase().as_str() {
+        "AT" | "CH" | "LI" | "LU" => Locale::DeDe,
+        "BE" | "MC" | "SN" | "CI" | "CM" | "HT" => Locale::FrFr,
+        "SM" | "VA" => Locale::ItIt,
+        "PT" | "AO" | "MZ" | "CV" | "ST" => Locale::PtBr,
+        "BY" | "KZ" | "KG" | "UA" => Locale::RuRu,
+        "AU" | "NZ" | "IE" | "ZA" | "SG" | "MT" => Locale::EnGb,
+        "MX" | "AR" | "CO" | "CL" | "PE" | "VE" | "EC" | "BO" | "PY" | "UY" | "CR" | "CU"
+        | "DO" | "GT" | "HN" | "NI" | "PA" | "SV" => Locale::EsEs,
+        "NP" | "BD" | "PK" | "LK" => Locale::HiIn,
+        _ => Locale::EnUs,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    pub name: PersonName,
+    pub account: AccountName,
+    pub email: String,
+    pub phone: String,
+    pub department: String,
+    pub job_title: String,
+    pub employer: String,
+    /// dns domain of the employer, eg: acme.com
+    pub domain: String,
+    pub office: Office,
+    /// NetBIOS name of the user's computer, unique within the employer;
+    /// its suffix tells the os, eg: JSMITH04-MAC
+    pub workstation: String,
+    pub ip_address: String,
+}
+
+/// Generates users of one employer; usernames are unique across
+/// everything generated and every user works in one of the employer's offices
+///
+/// ## Example
+/// ```rust
+/// use random::users::UserGenerator;
+///
+/// let mut generator = UserGenerator::new(Some("Acme"), Some(3), None).unwrap();
+/// for _ in 0..5 {
+///     let user = generator.user().unwrap();
+///     println!(
+///         "{} <{}> {} / {} ({})",
+///         user.name, user.email, user.job_title, user.office.city, user.office.timezone
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UserGenerator {
+    employer: String,
+    domain: String,
+    offices: Vec<Office>,
+    usernames: UsernameGenerator,
+    workstations: HashSet<String>,
+    /// host offsets given out so far, per office subnet
+    ip_addresses: HashMap<String, HashSet<u32>>,
+}
+
+impl UserGenerator {
+    pub fn new(
+        employer: Option<&str>,
+        offices: Option<usize>,
+        format: Option<UsernameFormat>,
+    ) -> Result<Self, Error> {
+        //! New generator for an employer
+        //! - `employer`: company name - random from `DUMMY_CORPS` if not given
+        //! - `offices`: number of offices (countries) - 1 to 5 if not given
+        //! - `format`: username convention of the company - random if not given
+        let mut trng = rand::thread_rng();
+        let employer = match employer {
+            Some(x) => x.to_string(),
+            None => DUMMY_CORPS
+                .choose(&mut trng)
+                .unwrap_or(&DUMMY_CORPS[0])
+                .to_string(),
+        };
+        let domain = format!(
+            "{}.{}",
+            employer
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_ascii_lowercase(),
+            TLDS.choose(&mut trng).unwrap_or(&TLDS[0])
+        );
+
+        let tzs = Tz::all()?;
+        let office_count = offices
+            .unwrap_or_else(|| trng.gen_range(1..=5))
+            .clamp(1, 254);
+        // every office gets its own 10.x.0.0/16
+        let mut octets: Vec<u8> = (1..=254).collect();
+        octets.shuffle(&mut trng);
+        let offices = tzs
+            .choose_multiple(&mut trng, office_count)
+            .zip(octets)
+            .map(|(tz, octet)| Office::from_tz(tz, format!("10.{}.0.0/16", octet)))
+            .collect::<Vec<Office>>();
+        if offices.is_empty() {
+            return Err(String::from("Error: no office locations available"));
+        }
+
+        Ok(UserGenerator {
+            employer,
+            domain,
+            offices,
+            usernames: UsernameGenerator::new(format.unwrap_or_else(UsernameFormat::random), None),
+            workstations: HashSet::new(),
+            ip_addresses: HashMap::new(),
+        })
+    }
+
+    pub fn employer(&self) -> &str {
+        &self.employer
+    }
+
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    pub fn offices(&self) -> &[Office] {
+        &self.offices
+    }
+
+    pub fn user(&mut self) -> Result<User, Error> {
+        //! Generate the next user of the employer; fails once the
+        //! subnet of the office the user works in has no free address
+        let mut trng = rand::thread_rng();
+        let office = self
+            .offices
+            .choose(&mut trng)
+            .cloned()
+            .unwrap_or_else(|| self.offices[0].clone());
+        let ip_address = workstation_ip(
+            &office.subnet,
+            self.ip_addresses.entry(office.subnet.clone()).or_default(),
+        )?;
+
+        let name = crate::names::person_name_with(&mut trng, None, Some(office.locale()));
+        let account = self.usernames.account(&name, &self.domain);
+
+        let (department, titles) = DEPARTMENT_JOB_TITLES
+            .choose(&mut trng)
+            .unwrap_or(&DEPARTMENT_JOB_TITLES[0]);
+        let job_title = titles.choose(&mut trng).unwrap_or(&titles[0]);
+
+        let workstation = self.workstation(&account.username);
+
+        Ok(User {
+            email: format!("{}@{}", account.username, self.domain),
+            phone: office.phone_number(),
+            department: department.to_string(),
+            job_title: job_title.to_string(),
+            employer: self.employer.clone(),
+            domain: self.domain.clone(),
+            workstation,
+            ip_address,
+            office,
+            account,
+            name,
+        })
+    }
+
+    fn workstation(&mut self, username: &str) -> String {
+        // the name is cut to make room for the suffix (& a number) so that it
+        // fits NetBIOS as it is; the suffix, which tells the os, is kept whole
+        let mut trng = rand::thread_rng();
+        let suffix = HOSTNAME_SUFFIX.choose(&mut trng).unwrap_or(&"-PC");
+        let stem: String = username
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let stem = if stem.is_empty() {
+            "WS".to_string()
+        } else {
+            stem
+        };
+        let room = MAX_COMPUTER_NAME_LENGTH - suffix.len();
+
+        let prefix: String = stem.chars().take(room - 2).collect();
+        let candidate = crate::operatingsystems::hostname(&prefix, Some(suffix), None);
+        if self.workstations.insert(candidate.clone()) {
+            return candidate;
+        }
+        let mut n: u32 = 1;
+        loop {
+            let number = format!("{:02}", n);
+            let prefix: String = stem.chars().take(room - number.len()).collect();
+            let candidate = format!("{}{}{}", prefix, number, suffix);
+            if self.workstations.insert(candidate.clone()) {
+                return candidate;
+            }
+            n += 1;
+        }
+    }
+
+    pub fn service_account(
+        &mut self,
+        application: Option<&str>,
+        style: Option<ServiceAccountStyle>,
+    ) -> AccountName {
+        //! Service account of the employer; never collides with a user's name
+        let username = self.usernames.service_account(application, style);
+        AccountName {
+            down_level_logon_name: down_level_logon_name(&self.domain, &username),
+            user_principal_name: user_principal_name(&username, &self.domain),
+            username,
+        }
+    }
+}
+
+fn workstation_ip(subnet: &str, issued: &mut HashSet<u32>) -> Result<String, Error> {
+    // skip the network, gateway & broadcast addresses and those
+    // issued already: from a random host on, the first free one
+    let network: Ipv4Network = subnet
+        .parse()
+        .map_err(|e| format!("Error: invalid subnet '{}': {}", subnet, e))?;
+    let base = u32::from(network.network());
+    let hosts = network.size().saturating_sub(3);
+    if issued.len() as u32 >= hosts {
+        return Err(format!("Error: no free address left in {}", subnet));
+    }
+    let start = rand::thread_rng().gen_range(0..hosts);
+    let offset = (0..hosts)
+        .map(|i| (start + i) % hosts + 2)
+        .find(|offset| !issued.contains(offset))
+        .unwrap_or(2);
+    issued.insert(offset);
+    Ok(std::net::Ipv4Addr::from(base + offset).to_string())
+}
+
+pub fn user() -> Result<User, Error> {
+    /*
+    Generate a single, internally consistent user
+    :return: User of a random employer working in a random office
+    */
+    UserGenerator::new(None, Some(1), None)?.user()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_is_consistent() {
+        let mut generator = UserGenerator::new(Some("Acme"), Some(3), None).unwrap();
+        assert_eq!(generator.offices().len(), 3);
+
+        let mut workstations = HashSet::new();
+        let mut ip_addresses = HashSet::new();
+        for _ in 0..20 {
+            let user = generator.user().unwrap();
+            assert!(workstations.insert(user.workstation.clone()));
+            assert_eq!(user.employer, "Acme");
+            assert!(user.domain.starts_with("acme."));
+            assert_eq!(
+                user.email,
+                format!("{}@{}", user.account.username, user.domain)
+            );
+            assert_eq!(user.account.user_principal_name, user.email);
+            assert!(user.account.down_level_logon_name.starts_with("ACME\\"));
+            assert!(user
+                .phone
+                .starts_with(&format!("+{} ", user.office.calling_code)));
+            assert!(generator.offices().contains(&user.office));
+
+            assert!(user.workstation.len() <= MAX_COMPUTER_NAME_LENGTH);
+            assert!(HOSTNAME_SUFFIX
+                .iter()
+                .any(|s| user.workstation.ends_with(s)));
+            assert!(user
+                .workstation
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-'));
+
+            let subnet: Ipv4Network = user.office.subnet.parse().unwrap();
+            assert!(subnet.contains(user.ip_address.parse().unwrap()));
+            assert!(ip_addresses.insert(user.ip_address.clone()));
+
+            let tz = Tz::by_iso_code(&user.office.country_code).unwrap().unwrap();
+            assert_eq!(tz.capital_timezone(), Some(user.office.timezone.as_str()));
+            assert_eq!(tz.capital(), user.office.city);
+        }
+
+        // a /29 has 5 addresses for workstations: .2 to .6
+        let mut issued = HashSet::new();
+        let mut addresses = (0..5)
+            .map(|_| workstation_ip("10.0.0.0/29", &mut issued).unwrap())
+            .collect::<Vec<String>>();
+        addresses.sort();
+        assert_eq!(
+            addresses,
+            ["10.0.0.2", "10.0.0.3", "10.0.0.4", "10.0.0.5", "10.0.0.6"]
+        );
+        assert!(workstation_ip("10.0.0.0/29", &mut issued).is_err());
+    }
+}
@@ -0,0 +1,473 @@
+/*
+UUID & GUID generators
+Time based (v1, v6, v7) UUIDs within a date/time range, name based
+(v3, v5) UUIDs and random (v4) ones; formatted the way Microsoft
+writes GUIDs when needed
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use uuid::{Builder, Uuid};
+
+use crate::core::constants::{ENG_NOUNS, TLDS};
+use crate::core::utils::hex_byte;
+
+type Error = String;
+
+/// 100ns intervals between the gregorian (1582-10-15) and unix epochs
+const GREGORIAN_OFFSET: i64 = 0x01B2_1DD2_1381_4000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UuidVersion {
+    /// time & MAC address
+    V1,
+    /// MD5 name based
+    V3,
+    /// random
+    V4,
+    /// SHA-1 name based
+    V5,
+    /// v1 reordered to sort by time
+    V6,
+    /// unix milliseconds & random, sorts by time
+    V7,
+}
+
+impl UuidVersion {
+    pub const ALL: &'static [UuidVersion] = &[
+        UuidVersion::V1,
+        UuidVersion::V3,
+        UuidVersion::V4,
+        UuidVersion::V5,
+        UuidVersion::V6,
+        UuidVersion::V7,
+    ];
+
+    pub fn number(&self) -> usize {
+        match self {
+            UuidVersion::V1 => 1,
+            UuidVersion::V3 => 3,
+            UuidVersion::V4 => 4,
+            UuidVersion::V5 => 5,
+            UuidVersion::V6 => 6,
+            UuidVersion::V7 => 7,
+        }
+    }
+}
+
+/// Namespaces of name based (v3 & v5) UUIDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    Dns,
+    Url,
+    Oid,
+    X500,
+    Custom(Uuid),
+}
+
+impl Namespace {
+    pub fn uuid(&self) -> Uuid {
+        match self {
+            Namespace::Dns => Uuid::NAMESPACE_DNS,
+            Namespace::Url => Uuid::NAMESPACE_URL,
+            Namespace::Oid => Uuid::NAMESPACE_OID,
+            Namespace::X500 => Uuid::NAMESPACE_X500,
+            Namespace::Custom(uuid) => *uuid,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuidFormat {
+    /// 6b29fc40-ca47-1067-b31d-00dd010662da
+    Hyphenated,
+    /// 6B29FC40-CA47-1067-B31D-00DD010662DA
+    Uppercase,
+    /// 6b29fc40ca471067b31d00dd010662da (.NET "N")
+    Simple,
+    /// {6b29fc40-ca47-1067-b31d-00dd010662da} (.NET "B")
+    Braced,
+    /// {6B29FC40-CA47-1067-B31D-00DD010662DA} as in the registry & COM CLSIDs
+    Registry,
+    /// urn:uuid:6b29fc40-ca47-1067-b31d-00dd010662da
+    Urn,
+}
+
+impl GuidFormat {
+    pub const ALL: &'static [GuidFormat] = &[
+        GuidFormat::Hyphenated,
+        GuidFormat::Uppercase,
+        GuidFormat::Simple,
+        GuidFormat::Braced,
+        GuidFormat::Registry,
+        GuidFormat::Urn,
+    ];
+
+    pub fn format(&self, uuid: &Uuid) -> String {
+        //! Write a UUID in this format
+        match self {
+            GuidFormat::Hyphenated => uuid.hyphenated().to_string(),
+            GuidFormat::Uppercase => uuid.hyphenated().to_string().to_ascii_uppercase(),
+            GuidFormat::Simple => uuid.simple().to_string(),
+            GuidFormat::Braced => uuid.braced().to_string(),
+            GuidFormat::Registry => uuid.braced().to_string().to_ascii_uppercase(),
+            GuidFormat::Urn => uuid.urn().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for UuidVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.number())
+    }
+}
+
+impl FromStr for UuidVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s.trim().trim_start_matches(['v', 'V']);
+        UuidVersion::ALL
+            .iter()
+            .find(|v| v.number().to_string() == number)
+            .copied()
+            .ok_or_else(|| format!("Error: unknown uuid version '{}'", s))
+    }
+}
+
+fn time_between<R: Rng + ?Sized>(
+    rng: &mut R,
+    start: Option<&DateTime<Utc>>,
+    end: Option<&DateTime<Utc>>,
+) -> Result<DateTime<Utc>, Error> {
+    // end defaults to now & start to end: no range at all means "now"
+    let end = end.copied().unwrap_or_else(Utc::now);
+    let start = start.copied().unwrap_or(end);
+    if start > end {
+        return Err(String::from(
+            "start date/time is greater than end date/time",
+        ));
+    }
+    let span = end
+        .signed_duration_since(start)
+        .num_nanoseconds()
+        .ok_or_else(|| String::from("Error: date/time range is too large"))?;
+    if span == 0 {
+        return Ok(start);
+    }
+    Ok(start + Duration::nanoseconds(rng.gen_range(0..span)))
+}
+
+fn gregorian_ticks(time: &DateTime<Utc>) -> Result<u64, Error> {
+    // 100ns intervals since 1582-10-15, the epoch of v1 & v6 UUIDs
+    let ticks = time.timestamp() as i128 * 10_000_000
+        + i128::from(time.timestamp_subsec_nanos() / 100)
+        + i128::from(GREGORIAN_OFFSET);
+    if !(0..1 << 60).contains(&ticks) {
+        return Err(format!("Error: {} is out of range of v1/v6 uuids", time));
+    }
+    Ok(ticks as u64)
+}
+
+fn unix_millis(time: &DateTime<Utc>) -> Result<u64, Error> {
+    let millis = time.timestamp_millis();
+    if !(0..1 << 48).contains(&millis) {
+        return Err(format!("Error: {} is out of range of v7 uuids", time));
+    }
+    Ok(millis as u64)
+}
+
+fn node_id(mac: Option<&str>) -> Result<[u8; 6], Error> {
+    let mac = match mac {
+        Some(x) => x.to_string(),
+        None => crate::networking::mac_address(None, None),
+    };
+    let bytes = mac
+        .split([':', '-'])
+        .map(hex_byte)
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| format!("Error: invalid mac address '{}'", mac))?;
+    <[u8; 6]>::try_from(bytes.as_slice())
+        .map_err(|_| format!("Error: invalid mac address '{}'", mac))
+}
+
+pub(crate) fn uuid_v7_with<R: Rng + ?Sized>(
+    rng: &mut R,
+    time: &DateTime<Utc>,
+) -> Result<Uuid, Error> {
+    let mut random_bytes = [0u8; 10];
+    rng.fill(&mut random_bytes);
+    Ok(Builder::from_unix_timestamp_millis(unix_millis(time)?, &random_bytes).into_uuid())
+}
+
+pub(crate) fn uuid_v4_with<R: Rng + ?Sized>(rng: &mut R) -> Uuid {
+    let mut random_bytes = [0u8; 16];
+    rng.fill(&mut random_bytes);
+    Builder::from_random_bytes(random_bytes).into_uuid()
+}
+
+pub fn uuid_v1(
+    mac: Option<&str>,
+    start: Option<&DateTime<Utc>>,
+    end: Option<&DateTime<Utc>>,
+) -> Result<Uuid, Error> {
+    /*
+    Generate a time & node based (version 1) UUID
+    :param mac: MAC address of the node, eg: 00:dd:01:06:62:da - random if not given
+    :param start: earliest time of the uuid - defaults to end
+    :param end: latest time of the uuid - defaults to now
+    :return: v1 uuid
+    */
+    let mut trng = rand::thread_rng();
+    let time = time_between(&mut trng, start, end)?;
+    let clock_sequence = trng.gen_range(0..1 << 14);
+    Ok(
+        Builder::from_gregorian_timestamp(gregorian_ticks(&time)?, clock_sequence, &node_id(mac)?)
+            .into_uuid(),
+    )
+}
+
+pub fn uuid_v6(
+    mac: Option<&str>,
+    start: Option<&DateTime<Utc>>,
+    end: Option<&DateTime<Utc>>,
+) -> Result<Uuid, Error> {
+    /*
+    Generate a time ordered, node based (version 6) UUID
+    :param mac: MAC address of the node - random if not given
+    :param start: earliest time of the uuid - defaults to end
+    :param end: latest time of the uuid - defaults to now
+    :return: v6 uuid
+    */
+    let mut trng = rand::thread_rng();
+    let time = time_between(&mut trng, start, end)?;
+    let clock_sequence = trng.gen_range(0..1 << 14);
+    Ok(Builder::from_sorted_gregorian_timestamp(
+        gregorian_ticks(&time)?,
+        clock_sequence,
+        &node_id(mac)?,
+    )
+    .into_uuid())
+}
+
+pub fn uuid_v7(start: Option<&DateTime<Utc>>, end: Option<&DateTime<Utc>>) -> Result<Uuid, Error> {
+    /*
+    Generate a time ordered (version 7) UUID
+    :param start: earliest time of the uuid - defaults to end
+    :param end: latest time of the uuid - defaults to now
+    :return: v7 uuid
+    */
+    let mut trng = rand::thread_rng();
+    let time = time_between(&mut trng, start, end)?;
+    uuid_v7_with(&mut trng, &time)
+}
+
+pub fn uuid_v3(namespace: Option<Namespace>, name: &str) -> Uuid {
+    /*
+    Generate a MD5 name based (version 3) UUID; the same name always gives the same uuid
+    :param namespace: namespace of the name - dns if not given
+    :param name: eg: www.example.com
+    :return: v3 uuid
+    */
+    Uuid::new_v3(&namespace.unwrap_or(Namespace::Dns).uuid(), name.as_bytes())
+}
+
+pub fn uuid_v5(namespace: Option<Namespace>, name: &str) -> Uuid {
+    /*
+    Generate a SHA-1 name based (version 5) UUID; the same name always gives the same uuid
+    :param namespace: namespace of the name - dns if not given
+    :param name: eg: www.example.com
+    :return: v5 uuid
+    */
+    Uuid::new_v5(&namespace.unwrap_or(Namespace::Dns).uuid(), name.as_bytes())
+}
+
+pub fn uuid(
+    version: Option<UuidVersion>,
+    start: Option<&DateTime<Utc>>,
+    end: Option<&DateTime<Utc>>,
+) -> Result<Uuid, Error> {
+    /*
+    Generate a UUID of any version
+    :param version: uuid version - v4 if not given
+    :param start: earliest time of time based uuids - defaults to end
+    :param end: latest time of time based uuids - defaults to now
+    :return: uuid; name based ones are made of a random dns name
+    */
+    let mut trng = rand::thread_rng();
+    match version.unwrap_or(UuidVersion::V4) {
+        UuidVersion::V1 => uuid_v1(None, start, end),
+        UuidVersion::V6 => uuid_v6(None, start, end),
+        UuidVersion::V7 => uuid_v7(start, end),
+        UuidVersion::V4 => Ok(uuid_v4_with(&mut trng)),
+        version => {
+            let name = format!(
+                "{}.{}",
+                ENG_NOUNS.choose(&mut trng).unwrap_or(&ENG_NOUNS[0]),
+                TLDS.choose(&mut trng).unwrap_or(&TLDS[0])
+            );
+            Ok(if version == UuidVersion::V3 {
+                uuid_v3(None, &name)
+            } else {
+                uuid_v5(None, &name)
+            })
+        }
+    }
+}
+
+pub fn uuids(
+    version: Option<UuidVersion>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> impl Iterator<Item = Result<Uuid, Error>> {
+    /*
+    Endless stream of UUIDs, see uuid
+    :return: iterator of uuids, eg: uuids(Some(UuidVersion::V7), None, None).take(1000)
+    */
+    std::iter::repeat_with(move || uuid(version, start.as_ref(), end.as_ref()))
+}
+
+pub fn uuid_time(uuid: &Uuid) -> Option<DateTime<Utc>> {
+    /*
+    Time of a time based (v1, v6, v7) UUID
+    :param uuid: the uuid
+    :return: its time; None for other versions
+    */
+    let bytes = uuid.as_bytes();
+    let field = |range: std::ops::Range<usize>| {
+        bytes[range]
+            .iter()
+            .fold(0u64, |acc, b| acc << 8 | u64::from(*b))
+    };
+    // v1 & v6 times are read from the raw gregorian ticks, which
+    // go back before 1970, rather than from unsigned unix seconds
+    let ticks = match uuid.get_version_num() {
+        1 => (field(6..8) & 0x0fff) << 48 | field(4..6) << 32 | field(0..4),
+        6 => field(0..4) << 28 | field(4..6) << 12 | (field(6..8) & 0x0fff),
+        7 => {
+            let (seconds, nanos) = uuid.get_timestamp()?.to_unix();
+            return Utc.timestamp_opt(seconds as i64, nanos).single();
+        }
+        _ => return None,
+    };
+    let unix_ticks = ticks as i64 - GREGORIAN_OFFSET;
+    Utc.timestamp_opt(
+        unix_ticks.div_euclid(10_000_000),
+        unix_ticks.rem_euclid(10_000_000) as u32 * 100,
+    )
+    .single()
+}
+
+pub fn guid(format: Option<GuidFormat>) -> String {
+    /*
+    Generate a random GUID written the way windows does
+    :param format: how to write it - registry style if not given,
+                   eg: {6B29FC40-CA47-1067-B31D-00DD010662DA}
+    :return: formatted guid
+    */
+    format
+        .unwrap_or(GuidFormat::Registry)
+        .format(&uuid_v4_with(&mut rand::thread_rng()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions() {
+        for version in UuidVersion::ALL {
+            let uuid = uuid(Some(*version), None, None).unwrap();
+            assert_eq!(uuid.get_version_num(), version.number());
+            assert_eq!(version.to_string().parse::<UuidVersion>(), Ok(*version));
+        }
+
+        let start = Utc.with_ymd_and_hms(2001, 9, 9, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 9, 10, 0, 0, 0).unwrap();
+        let mac = "00:dd:01:06:62:da";
+        for uuid in [
+            uuid_v1(Some(mac), Some(&start), Some(&end)).unwrap(),
+            uuid_v6(Some(mac), Some(&start), Some(&end)).unwrap(),
+        ] {
+            let time = uuid_time(&uuid).unwrap();
+            assert!(start <= time && time <= end);
+            assert_eq!(
+                uuid.get_node_id(),
+                Some([0x00, 0xdd, 0x01, 0x06, 0x62, 0xda])
+            );
+        }
+        let uuid = uuid_v7(Some(&start), Some(&end)).unwrap();
+        let time = uuid_time(&uuid).unwrap();
+        assert!(start <= time && time <= end);
+
+        assert!(uuid_v7(Some(&end), Some(&start)).is_err());
+        for mac in &[
+            "not a mac",
+            "+1:23:45:67:89:ab",
+            "01:23:45:67:89:0ab",
+            "1:23:45:67:89:ab",
+        ] {
+            assert!(uuid_v1(Some(mac), None, None).is_err());
+        }
+        let before_unix = Utc.with_ymd_and_hms(1960, 1, 1, 0, 0, 0).unwrap();
+        assert!(uuid_v7(None, Some(&before_unix)).is_err());
+        assert!(uuid_v1(None, None, Some(&before_unix)).is_ok());
+        let gregorian_epoch = Utc.with_ymd_and_hms(1582, 10, 15, 0, 0, 0).unwrap();
+        for time in [
+            before_unix,
+            before_unix + Duration::nanoseconds(1234567800),
+            gregorian_epoch,
+        ] {
+            for uuid in [
+                uuid_v1(None, Some(&time), Some(&time)).unwrap(),
+                uuid_v6(None, Some(&time), Some(&time)).unwrap(),
+            ] {
+                assert_eq!(uuid_time(&uuid), Some(time));
+            }
+        }
+    }
+
+    #[test]
+    fn test_name_based() {
+        assert_eq!(
+            uuid_v5(None, "www.example.com").to_string(),
+            "2ed6657d-e927-568b-95e1-2665a8aea6a2"
+        );
+        assert_eq!(
+            uuid_v3(Some(Namespace::Dns), "www.example.com").to_string(),
+            "5df41881-3aed-3515-88a7-2f4a814cf09e"
+        );
+    }
+
+    #[test]
+    fn test_formats() {
+        let uuid = Uuid::parse_str("6b29fc40-ca47-1067-b31d-00dd010662da").unwrap();
+        assert_eq!(
+            GuidFormat::Registry.format(&uuid),
+            "{6B29FC40-CA47-1067-B31D-00DD010662DA}"
+        );
+        assert_eq!(
+            GuidFormat::Braced.format(&uuid),
+            "{6b29fc40-ca47-1067-b31d-00dd010662da}"
+        );
+        assert_eq!(
+            GuidFormat::Simple.format(&uuid),
+            "6b29fc40ca471067b31d00dd010662da"
+        );
+        assert_eq!(
+            GuidFormat::Urn.format(&uuid),
+            "urn:uuid:6b29fc40-ca47-1067-b31d-00dd010662da"
+        );
+        assert_eq!(guid(None).len(), 38);
+
+        // v7 uuids sort by time
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let later = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        assert!(uuid_v7(None, Some(&start)).unwrap() < uuid_v7(None, Some(&later)).unwrap());
+    }
+}